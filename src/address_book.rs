@@ -0,0 +1,113 @@
+//! # Address book shared between test suites and external tools
+
+use crate::error::AnyResult;
+use cosmwasm_std::{from_json, to_json_string, Addr};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Deterministic collection of named addresses.
+///
+/// Address book can be exported to JSON and imported back, so multiple test suites
+/// and external scripts can reference the same addresses. In JSON, the address book
+/// is a plain object mapping names to addresses, sorted by name.
+///
+/// Use [App::address_book](crate::App::address_book) to get the address book filled with
+/// all named user accounts, module accounts and instantiated contracts, add other accounts
+/// with [insert](Self::insert), and import the address book back into an application
+/// with [App::import_address_book](crate::App::import_address_book).
+///
+/// # Example
+///
+/// ```
+/// use cw_multi_test::{AddressBook, App};
+///
+/// let app = App::default();
+///
+/// let mut address_book = app.address_book().unwrap();
+/// address_book.insert("owner", app.api().addr_make("owner"));
+///
+/// let json = address_book.to_json().unwrap();
+/// let imported = AddressBook::from_json(&json).unwrap();
+///
+/// assert_eq!(Some(&app.api().addr_make("owner")), imported.get("owner"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(transparent)]
+pub struct AddressBook {
+    entries: BTreeMap<String, Addr>,
+}
+
+impl AddressBook {
+    /// Creates an empty address book.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the address under specified name, returns the address previously stored
+    /// under the same name, if any.
+    pub fn insert(&mut self, name: impl Into<String>, addr: Addr) -> Option<Addr> {
+        self.entries.insert(name.into(), addr)
+    }
+
+    /// Returns the address stored under specified name.
+    pub fn get(&self, name: &str) -> Option<&Addr> {
+        self.entries.get(name)
+    }
+
+    /// Returns the name under which specified address is stored.
+    pub fn name_of(&self, addr: &Addr) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(_, a)| *a == addr)
+            .map(|(name, _)| name.as_str())
+    }
+
+    /// Returns an iterator over all names and addresses, sorted by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Addr)> {
+        self.entries
+            .iter()
+            .map(|(name, addr)| (name.as_str(), addr))
+    }
+
+    /// Adds all addresses from the other address book, replacing addresses stored under the same names.
+    pub fn extend(&mut self, other: AddressBook) {
+        self.entries.extend(other.entries);
+    }
+
+    /// Returns the number of addresses in this address book.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` when this address book contains no addresses.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Exports this address book to JSON.
+    pub fn to_json(&self) -> AnyResult<String> {
+        Ok(to_json_string(self)?)
+    }
+
+    /// Imports the address book from JSON.
+    pub fn from_json(json: &str) -> AnyResult<Self> {
+        Ok(from_json(json)?)
+    }
+
+    /// Adds the address under specified name, unless the address is already stored.
+    /// When the name is already used, the address is stored under the name
+    /// with the first free numeric suffix, like `name_2`.
+    pub(crate) fn insert_unique(&mut self, label: &str, addr: Addr) {
+        if self.name_of(&addr).is_some() {
+            return;
+        }
+        let mut name = label.to_string();
+        let mut index = 1;
+        while self.entries.contains_key(&name) {
+            index += 1;
+            name = format!("{}_{}", label, index);
+        }
+        self.entries.insert(name, addr);
+    }
+}
@@ -4,6 +4,7 @@ use crate::error::AnyResult;
 use crate::{MockApiBech32, MockApiBech32m};
 use cosmwasm_std::testing::MockApi;
 use cosmwasm_std::{instantiate2_address, Addr, Api, CanonicalAddr, Storage};
+use cw_storage_plus::Item;
 use sha2::digest::Update;
 use sha2::{Digest, Sha256};
 
@@ -195,3 +196,140 @@ fn instantiate_address(code_id: u64, instance_id: u64) -> CanonicalAddr {
 pub struct SimpleAddressGenerator;
 
 impl AddressGenerator for SimpleAddressGenerator {}
+
+/// Contract address generator mixing a salt into non-predictable contract addresses.
+///
+/// Addresses of contracts instantiated with `WasmMsg::Instantiate` are derived
+/// from the salt, code identifier and instance identifier, so tests using different salts
+/// get different, but still deterministic, sequences of contract addresses.
+/// Predictable addresses (`WasmMsg::Instantiate2`) are generated like on a real chain.
+///
+/// # Example
+///
+/// ```
+/// # use cosmwasm_std::testing::{MockApi, MockStorage};
+/// # use cw_multi_test::{AddressGenerator, SaltedAddressGenerator, SimpleAddressGenerator};
+/// # let api = MockApi::default();
+/// # let mut storage = MockStorage::default();
+/// let generator = SaltedAddressGenerator::new("my-test");
+///
+/// let addr = generator.contract_address(&api, &mut storage, 1, 1).unwrap();
+/// let simple_addr = SimpleAddressGenerator.contract_address(&api, &mut storage, 1, 1).unwrap();
+/// assert_ne!(simple_addr, addr);
+/// ```
+pub struct SaltedAddressGenerator {
+    salt: Vec<u8>,
+}
+
+impl SaltedAddressGenerator {
+    /// Creates a new address generator using specified salt.
+    pub fn new(salt: impl Into<Vec<u8>>) -> Self {
+        Self { salt: salt.into() }
+    }
+}
+
+impl AddressGenerator for SaltedAddressGenerator {
+    fn contract_address(
+        &self,
+        api: &dyn Api,
+        _storage: &mut dyn Storage,
+        code_id: u64,
+        instance_id: u64,
+    ) -> AnyResult<Addr> {
+        let canonical_addr = Sha256::new()
+            .chain(b"salted\0")
+            .chain((self.salt.len() as u64).to_be_bytes())
+            .chain(&self.salt)
+            .chain(code_id.to_be_bytes())
+            .chain(instance_id.to_be_bytes())
+            .finalize()
+            .to_vec();
+        Ok(api.addr_humanize(&canonical_addr.into())?)
+    }
+}
+
+/// Generator of user account addresses, see [App::account_address](crate::App::account_address).
+///
+/// The default implementation derives the address from the account name,
+/// exactly like [MockApi::addr_make] does, but using the address format of the configured [Api].
+pub trait AccountGenerator {
+    /// Generates the address of the account with specified name.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cosmwasm_std::testing::{MockApi, MockStorage};
+    /// # use cw_multi_test::AccountGenerator;
+    /// # let api = MockApi::default();
+    /// # let mut storage = MockStorage::default();
+    /// struct MyAccountGenerator;
+    ///
+    /// impl AccountGenerator for MyAccountGenerator {}
+    ///
+    /// let addr = MyAccountGenerator.account_address(&api, &mut storage, "alice").unwrap();
+    /// assert_eq!(api.addr_make("alice"), addr);
+    /// ```
+    fn account_address(
+        &self,
+        api: &dyn Api,
+        _storage: &mut dyn Storage,
+        name: &str,
+    ) -> AnyResult<Addr> {
+        let canonical_addr = Sha256::digest(name.as_bytes()).to_vec();
+        Ok(api.addr_humanize(&canonical_addr.into())?)
+    }
+}
+
+/// Default account generator, deriving account addresses from their names.
+pub struct NamedAccountGenerator;
+
+impl AccountGenerator for NamedAccountGenerator {}
+
+/// Account generator returning a new address on every call, ignoring account names.
+///
+/// Addresses are derived from a counter kept in the application storage,
+/// so the sequence of generated addresses is the same in every test run.
+pub struct SequentialAccountGenerator;
+
+impl AccountGenerator for SequentialAccountGenerator {
+    fn account_address(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        _name: &str,
+    ) -> AnyResult<Addr> {
+        const ACCOUNT_COUNT: Item<u64> = Item::new("account_count");
+        let count = ACCOUNT_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+        ACCOUNT_COUNT.save(storage, &count)?;
+        let canonical_addr = Sha256::new()
+            .chain(b"account")
+            .chain(count.to_be_bytes())
+            .finalize()
+            .to_vec();
+        Ok(api.addr_humanize(&canonical_addr.into())?)
+    }
+}
+
+/// Account generator returning module account addresses, like Cosmos SDK does
+/// for modules' accounts (e.g. `gov`, `distribution` or `fee_collector`).
+///
+/// The address is built from the first 20 bytes of SHA-256 digest of the module name.
+pub struct ModuleAccountGenerator;
+
+impl AccountGenerator for ModuleAccountGenerator {
+    fn account_address(
+        &self,
+        api: &dyn Api,
+        _storage: &mut dyn Storage,
+        name: &str,
+    ) -> AnyResult<Addr> {
+        module_address(api, name)
+    }
+}
+
+/// Returns the address of the module account with specified name,
+/// see [ModuleAccountGenerator] for details.
+pub(crate) fn module_address(api: &dyn Api, name: &str) -> AnyResult<Addr> {
+    let canonical_addr = Sha256::digest(name.as_bytes())[..20].to_vec();
+    Ok(api.addr_humanize(&canonical_addr.into())?)
+}
@@ -1,26 +1,46 @@
+use crate::address_book::AddressBook;
+use crate::addresses::{module_address, AccountGenerator};
 use crate::bank::{Bank, BankKeeper, BankSudo};
+use crate::capabilities::{Capabilities, QueryCapabilitiesResponse, CAPABILITIES_QUERY_PATH};
 use crate::contracts::Contract;
-use crate::error::{bail, AnyResult};
-use crate::executor::{AppResponse, Executor};
-use crate::gov::Gov;
+use crate::error::{bail, AnyResult, AppError, Error};
+use crate::event_log::EventLog;
+use crate::executor::{AppResponse, Executor, TxMetadata};
+use crate::gov::{Gov, GovSudo};
 use crate::ibc::Ibc;
 use crate::module::{FailingModule, Module};
 use crate::prefixed_storage::{
     prefixed, prefixed_multilevel, prefixed_multilevel_read, prefixed_read,
 };
+use crate::profiler::ProfilingReport;
+use crate::query_handle::QueryHandle;
+use crate::randomness;
+use crate::snapshot::{AppSnapshot, AppState};
 use crate::staking::{Distribution, DistributionKeeper, StakeKeeper, Staking, StakingSudo};
-use crate::transactions::transactional;
-use crate::wasm::{ContractData, Wasm, WasmKeeper, WasmSudo};
+use crate::trace::Trace;
+use crate::transactions::{
+    transactional, transactional_with_writes, StorageTransaction, StorageWrite,
+};
+use crate::wasm::{
+    contracts_by_code, ContractCodeHistoryEntry, ContractData, MsgPinCodes, MsgUnpinCodes, Wasm,
+    WasmAdminSudo, WasmKeeper, WasmSudo, MSG_PIN_CODES_TYPE_URL, MSG_UNPIN_CODES_TYPE_URL,
+};
 use crate::{AppBuilder, GovFailingModule, IbcFailingModule, Stargate, StargateFailing};
 use cosmwasm_std::testing::{MockApi, MockStorage};
 use cosmwasm_std::{
-    from_json, to_json_binary, Addr, Api, Binary, BlockInfo, ContractResult, CosmosMsg, CustomMsg,
-    CustomQuery, Empty, Querier, QuerierResult, QuerierWrapper, QueryRequest, Record, Storage,
+    from_json, to_json_binary, to_json_vec, Addr, AnyMsg, Api, Binary, BlockInfo, Coin,
+    ContractResult, CosmosMsg, CustomMsg, CustomQuery, Empty, Event, GrpcQuery, HexBinary,
+    IbcPacketReceiveMsg, Querier, QuerierResult, QuerierWrapper, QueryRequest, Record, Storage,
     SystemError, SystemResult,
 };
+use cw_storage_plus::{Item, Map, PrimaryKey};
+use prost::Message;
 use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::sync::Arc;
 
 /// Advances the blockchain environment to the next block in tests, enabling developers to simulate
 /// time-dependent contract behaviors and block-related triggers efficiently.
@@ -29,6 +49,27 @@ pub fn next_block(block: &mut BlockInfo) {
     block.height += 1;
 }
 
+/// Parameters of automatic block advancement, see
+/// [with_auto_block_advance](AppBuilder::with_auto_block_advance).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BlockParams {
+    /// Number of seconds between two consecutive blocks.
+    pub seconds_per_block: u64,
+    /// Number of blocks the chain advances after every executed transaction.
+    pub blocks_per_tx: u64,
+}
+
+impl Default for BlockParams {
+    /// Returns parameters advancing one block of 5 seconds after every transaction,
+    /// just like [next_block] does.
+    fn default() -> Self {
+        Self {
+            seconds_per_block: 5,
+            blocks_per_tx: 1,
+        }
+    }
+}
+
 /// A type alias for the default-built App. It simplifies storage and handling in typical scenarios,
 /// streamlining the use of the App structure in standard test setups.
 pub type BasicApp<ExecC = Empty, QueryC = Empty> = App<
@@ -64,6 +105,56 @@ pub struct App<
     pub(crate) api: Api,
     pub(crate) storage: Storage,
     pub(crate) block: BlockInfo,
+    /// Responses of all successfully executed transactions, indexed by transaction hash.
+    pub(crate) transactions: BTreeMap<String, Vec<AppResponse>>,
+    /// Index of the next transaction in the current block.
+    pub(crate) tx_index: u32,
+    /// Maximum gas consumed by all transactions in a single block, unlimited when `None`.
+    pub(crate) block_gas_limit: Option<u64>,
+    /// Gas consumed by transactions executed in the current block.
+    pub(crate) block_gas_used: u64,
+    /// Read-only handle refreshed after every committed state change, created on demand.
+    pub(crate) query_handle: Option<QueryHandle>,
+    /// Block advancement applied after every executed transaction, disabled when `None`.
+    pub(crate) auto_block_advance: Option<BlockParams>,
+    /// Privileged messages executed at the end of every block.
+    pub(crate) end_blockers: Vec<SudoMsg>,
+    /// Results of end blockers executed at the end of the last block.
+    pub(crate) end_block_results: Vec<Result<AppResponse, String>>,
+    /// Storage writes committed by the last executed transaction or sudo call.
+    pub(crate) last_storage_writes: Vec<StorageWrite>,
+    /// Generator of user account addresses.
+    pub(crate) account_generator: Arc<dyn AccountGenerator + Send + Sync>,
+    /// Named user accounts, created with the account generator or imported.
+    pub(crate) accounts: AddressBook,
+    /// Chain identifiers scheduled to be set when the block height is reached.
+    pub(crate) chain_upgrades: BTreeMap<u64, String>,
+    /// Events of committed transactions, sudo calls and end blockers, recorded when enabled.
+    pub(crate) event_log: EventLog,
+}
+
+/// Names of standard module accounts included in the [AddressBook] of the application.
+const MODULE_ACCOUNTS: [&str; 6] = [
+    "bonded_tokens_pool",
+    "distribution",
+    "fee_collector",
+    "gov",
+    "mint",
+    "not_bonded_tokens_pool",
+];
+
+/// Returns the revision number of the chain identifier formatted like `{name}-{revision}`,
+/// zero for chain identifiers without the revision number.
+fn chain_revision(chain_id: &str) -> u64 {
+    chain_id
+        .rsplit_once('-')
+        .filter(|(name, revision)| {
+            !name.is_empty()
+                && !revision.starts_with('0')
+                && revision.bytes().all(|b| b.is_ascii_digit())
+        })
+        .and_then(|(_, revision)| revision.parse().ok())
+        .unwrap_or_default()
 }
 
 /// No-op application initialization function.
@@ -133,6 +224,7 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, Starga
 where
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
     ApiT: Api,
@@ -157,6 +249,7 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, Starga
 where
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
     ApiT: Api,
@@ -173,6 +266,14 @@ where
         let res = all.pop().unwrap();
         Ok(res)
     }
+
+    fn execute_cosmos_msgs(
+        &mut self,
+        sender: Addr,
+        msgs: Vec<CosmosMsg<CustomT::ExecT>>,
+    ) -> AnyResult<Vec<AppResponse>> {
+        self.execute_multi(sender, msgs)
+    }
 }
 
 impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
@@ -211,6 +312,145 @@ where
         &mut self.storage
     }
 
+    /// Returns the address of the user account with specified name,
+    /// generated by the account generator set with
+    /// [with_account_generator](AppBuilder::with_account_generator).
+    ///
+    /// By default, the address is derived from the name, like [MockApi::addr_make] does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{no_init, AppBuilder, SequentialAccountGenerator};
+    ///
+    /// let mut app = AppBuilder::default()
+    ///     .with_account_generator(SequentialAccountGenerator)
+    ///     .build(no_init);
+    ///
+    /// let first = app.account_address("alice").unwrap();
+    /// let second = app.account_address("alice").unwrap();
+    /// assert_ne!(first, second);
+    /// ```
+    pub fn account_address(&mut self, name: &str) -> AnyResult<Addr> {
+        let addr = self
+            .account_generator
+            .account_address(&self.api, &mut self.storage, name)?;
+        self.accounts.insert_unique(name, addr.clone());
+        Ok(addr)
+    }
+
+    /// Returns the address of the module account with specified name, like `gov`,
+    /// derived the same way as Cosmos SDK does, see [ModuleAccountGenerator](crate::ModuleAccountGenerator).
+    pub fn module_address(&self, module_name: &str) -> AnyResult<Addr> {
+        module_address(&self.api, module_name)
+    }
+
+    /// Returns capabilities advertised by the simulated chain,
+    /// like supported CosmWasm features, enabled modules, Bech32 address prefix and IBC availability.
+    /// Contracts can query the same with [CAPABILITIES_QUERY_PATH](crate::CAPABILITIES_QUERY_PATH).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::App;
+    ///
+    /// let app = App::default();
+    /// let capabilities = app.capabilities();
+    ///
+    /// assert_eq!("cosmwasm", capabilities.bech32_prefix);
+    /// assert!(!capabilities.ibc_enabled);
+    /// assert!(capabilities.has_module("bank"));
+    /// ```
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::new(&self.api, self.router.enabled_modules())
+    }
+
+    /// Returns a cloneable, **read-only** handle to the application state,
+    /// that can be used from other threads. See [QueryHandle] for details.
+    ///
+    /// All handles returned from this function share the same snapshot, which is refreshed
+    /// after every successfully executed transaction, sudo call and block update.
+    /// Changes made directly through [storage_mut](Self::storage_mut) are visible
+    /// after the next refresh.
+    pub fn query_handle(&mut self) -> QueryHandle {
+        match &self.query_handle {
+            Some(handle) => {
+                handle.publish(&self.storage, &self.block);
+                handle.clone()
+            }
+            None => {
+                let handle = QueryHandle::new(&self.storage, &self.block);
+                self.query_handle = Some(handle.clone());
+                handle
+            }
+        }
+    }
+
+    /// Takes a snapshot of the whole application state, i.e. the storage of all modules
+    /// and the current block info.
+    ///
+    /// The snapshot can be restored later with [restore](Self::restore), so long test scenarios
+    /// can branch into multiple continuations without setting up the application again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::coins;
+    /// use cw_multi_test::{App, BankSudo, SudoMsg};
+    ///
+    /// let mut app = App::default();
+    /// let user = app.api().addr_make("user");
+    ///
+    /// let snapshot = app.snapshot();
+    ///
+    /// app.sudo(SudoMsg::Bank(BankSudo::Mint {
+    ///     to_address: user.to_string(),
+    ///     amount: coins(100, "uatom"),
+    /// }))
+    /// .unwrap();
+    /// assert_eq!(100, app.wrap().query_balance(&user, "uatom").unwrap().amount.u128());
+    ///
+    /// app.restore(&snapshot);
+    /// assert_eq!(0, app.wrap().query_balance(&user, "uatom").unwrap().amount.u128());
+    /// ```
+    pub fn snapshot(&self) -> AppSnapshot {
+        let app_state = AppState {
+            transactions: self.transactions.clone(),
+            tx_index: self.tx_index,
+            block_gas_used: self.block_gas_used,
+            end_block_results: self.end_block_results.clone(),
+            chain_upgrades: self.chain_upgrades.clone(),
+            event_cursor: self.event_log.cursor(),
+        };
+        AppSnapshot::new(&self.storage, &self.block, app_state)
+    }
+
+    /// Restores the application state saved in the snapshot taken with [snapshot](Self::snapshot).
+    ///
+    /// The whole storage content, the block info and the state kept by the application,
+    /// like executed transactions or the transaction index, are replaced, see [AppSnapshot].
+    /// Contract codes stored after the snapshot was taken remain available.
+    pub fn restore(&mut self, snapshot: &AppSnapshot) {
+        snapshot.restore_storage(&mut self.storage);
+        self.block = snapshot.block().clone();
+        let app_state = snapshot.app_state.clone();
+        self.transactions = app_state.transactions;
+        self.tx_index = app_state.tx_index;
+        self.block_gas_used = app_state.block_gas_used;
+        self.end_block_results = app_state.end_block_results;
+        self.chain_upgrades = app_state.chain_upgrades;
+        self.event_log.truncate(app_state.event_cursor);
+        self.publish_snapshot();
+    }
+
+    /// Refreshes the snapshot shared by query handles, if any handle was created.
+    fn publish_snapshot(&self) {
+        // handles created later get the snapshot refreshed by query_handle
+        if let Some(handle) = self.query_handle.as_ref().filter(|h| h.is_shared()) {
+            handle.publish(&self.storage, &self.block);
+        }
+    }
+
     /// Initializes modules.
     pub fn init_modules<F, T>(&mut self, init_fn: F) -> T
     where
@@ -234,6 +474,76 @@ where
     {
         query_fn(&self.router, &self.api, &self.storage)
     }
+
+    /// Replaces the stargate module of an already built application.
+    ///
+    /// All other modules, the storage and the block info are preserved,
+    /// so the behavior of stargate messages and queries can be changed between
+    /// test phases without rebuilding and setting up the application again.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{App, StargateAccepting};
+    ///
+    /// let app = App::default();
+    /// // default application rejects all stargate messages and queries...
+    /// let app = app.replace_stargate(StargateAccepting);
+    /// // ...and from now on, all stargate messages and queries are accepted
+    /// ```
+    pub fn replace_stargate<NewStargateT: Stargate>(
+        self,
+        stargate: NewStargateT,
+    ) -> App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, NewStargateT>
+    {
+        let Self {
+            router,
+            api,
+            storage,
+            block,
+            transactions,
+            tx_index,
+            block_gas_limit,
+            block_gas_used,
+            query_handle,
+            auto_block_advance,
+            end_blockers,
+            end_block_results,
+            last_storage_writes,
+            account_generator,
+            accounts,
+            chain_upgrades,
+            event_log,
+        } = self;
+        App {
+            router: Router {
+                wasm: router.wasm,
+                bank: router.bank,
+                custom: router.custom,
+                staking: router.staking,
+                distribution: router.distribution,
+                ibc: router.ibc,
+                gov: router.gov,
+                stargate,
+            },
+            api,
+            storage,
+            block,
+            transactions,
+            tx_index,
+            block_gas_limit,
+            block_gas_used,
+            query_handle,
+            auto_block_advance,
+            end_blockers,
+            end_block_results,
+            last_storage_writes,
+            account_generator,
+            accounts,
+            chain_upgrades,
+            event_log,
+        }
+    }
 }
 
 // Helper functions to call some custom WasmKeeper logic.
@@ -253,6 +563,7 @@ where
     StargateT: Stargate,
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
 {
     /// Registers contract code (like uploading wasm bytecode on a chain),
     /// so it can later be used to instantiate a contract.
@@ -341,6 +652,282 @@ where
         self.router.wasm.contract_data(&self.storage, address)
     }
 
+    /// Makes the contract with specified address use the code with specified identifier,
+    /// without sending a migration message, so the contract's state is kept untouched
+    /// and no admin permissions are needed. Useful for replacing the code of a contract
+    /// with instrumented or patched implementation in tests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{Addr, Empty};
+    /// use cw_multi_test::{App, Contract, ContractWrapper};
+    /// # use cosmwasm_std::{Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+    /// # fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> { Ok(Response::default()) }
+    /// # fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> { Ok(Response::default()) }
+    /// # fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> { Ok(Binary::default()) }
+    /// # fn contract() -> Box<dyn Contract<Empty>> { Box::new(ContractWrapper::new(execute, instantiate, query)) }
+    /// use cw_multi_test::Executor;
+    ///
+    /// let mut app = App::default();
+    /// let owner = app.api().addr_make("owner");
+    /// let code_id = app.store_code(contract());
+    /// let patched_code_id = app.store_code(contract());
+    /// let contract_addr = app
+    ///     .instantiate_contract(code_id, owner, &Empty {}, &[], "contract", None)
+    ///     .unwrap();
+    ///
+    /// app.override_contract_code(&contract_addr, patched_code_id).unwrap();
+    /// assert_eq!(patched_code_id, app.contract_data(&contract_addr).unwrap().code_id);
+    /// ```
+    pub fn override_contract_code(&mut self, address: &Addr, code_id: u64) -> AnyResult<()> {
+        self.router
+            .wasm
+            .override_contract_code(&mut self.storage, address, code_id)
+    }
+
+    /// Pins the code with specified identifier by executing [MsgPinCodes] sent
+    /// by the governance module account, like a passed governance proposal does on a real chain.
+    /// Contracts instantiated from pinned codes are reported as pinned in their contract info.
+    ///
+    /// The same message can be sent in `CosmosMsg::Any` by a governance proposal,
+    /// see [GovKeeper](crate::GovKeeper).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+    /// use cw_multi_test::{App, ContractWrapper, Executor};
+    ///
+    /// fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    /// fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    /// fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    ///     Ok(Binary::default())
+    /// }
+    ///
+    /// let mut app = App::default();
+    /// let owner = app.api().addr_make("owner");
+    /// let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    /// let contract_addr = app
+    ///     .instantiate_contract(code_id, owner, &Empty {}, &[], "contract", None)
+    ///     .unwrap();
+    ///
+    /// app.pin_code(code_id).unwrap();
+    /// assert!(app.wrap().query_wasm_contract_info(&contract_addr).unwrap().pinned);
+    ///
+    /// app.unpin_code(code_id).unwrap();
+    /// assert!(!app.wrap().query_wasm_contract_info(&contract_addr).unwrap().pinned);
+    /// ```
+    pub fn pin_code(&mut self, code_id: u64) -> AnyResult<AppResponse> {
+        let authority = self.module_address("gov")?;
+        let msg = MsgPinCodes {
+            authority: authority.to_string(),
+            code_ids: vec![code_id],
+        };
+        self.execute_module_msg(
+            authority,
+            AnyMsg {
+                type_url: MSG_PIN_CODES_TYPE_URL.to_string(),
+                value: msg.encode_to_vec().into(),
+            },
+        )
+    }
+
+    /// Unpins the code with specified identifier by executing [MsgUnpinCodes]
+    /// sent by the governance module account, see [pin_code](Self::pin_code).
+    pub fn unpin_code(&mut self, code_id: u64) -> AnyResult<AppResponse> {
+        let authority = self.module_address("gov")?;
+        let msg = MsgUnpinCodes {
+            authority: authority.to_string(),
+            code_ids: vec![code_id],
+        };
+        self.execute_module_msg(
+            authority,
+            AnyMsg {
+                type_url: MSG_UNPIN_CODES_TYPE_URL.to_string(),
+                value: msg.encode_to_vec().into(),
+            },
+        )
+    }
+
+    /// Executes specified message sent by the module account, outside of any transaction.
+    fn execute_module_msg(&mut self, sender: Addr, msg: AnyMsg) -> AnyResult<AppResponse> {
+        self.execute_outside_tx(|router, api, storage, block| {
+            router.execute(api, storage, block, sender, CosmosMsg::Any(msg))
+        })
+    }
+
+    /// Returns `true` when the code with specified identifier is pinned.
+    pub fn is_code_pinned(&self, code_id: u64) -> AnyResult<bool> {
+        self.router.wasm.is_code_pinned(&self.storage, code_id)
+    }
+
+    /// Returns the address of the contract that would be instantiated now
+    /// from the code with specified identifier by `WasmMsg::Instantiate2` message
+    /// sent by the specified creator with specified salt.
+    ///
+    /// With the default address generator, the address is the same as computed by `wasmd`
+    /// (from the code checksum, the creator address and the salt),
+    /// so tests can pre-compute contract addresses before instantiating contracts.
+    /// The application state is not changed.
+    pub fn predict_instantiate2_address(
+        &self,
+        code_id: u64,
+        creator: &Addr,
+        salt: &[u8],
+    ) -> AnyResult<Addr> {
+        let mut cache = StorageTransaction::new(&self.storage);
+        self.router
+            .wasm
+            .predictable_contract_address(&self.api, &mut cache, code_id, creator, salt)
+    }
+
+    /// Returns the trace of all contract calls made by the last executed transaction
+    /// or sudo call, also when it failed. Returns `None` when tracing is not enabled,
+    /// see [WasmKeeper::with_tracing].
+    pub fn last_trace(&self) -> Option<Trace> {
+        self.router.wasm.trace()
+    }
+
+    /// Returns per-contract and per-entry-point statistics of all contract calls
+    /// made since profiling was enabled or reset. Returns `None` when profiling is not enabled,
+    /// see [WasmKeeper::with_profiling].
+    pub fn profiling_report(&self) -> Option<ProfilingReport> {
+        self.router.wasm.profiling_report()
+    }
+
+    /// Removes all statistics accumulated by the profiler,
+    /// see [profiling_report](Self::profiling_report).
+    pub fn reset_profiling(&self) {
+        self.router.wasm.reset_profiling()
+    }
+
+    /// Returns all keys written by the last executed transaction or sudo call, sorted by key,
+    /// with their values before and after the call. Returns nothing when the call failed,
+    /// as its changes were reverted.
+    ///
+    /// Keys from the contract storage are relative to the storage of the contract
+    /// and have the contract's address set, all other keys are raw keys of the application storage.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// assert!(app.last_storage_writes().is_empty());
+    /// ```
+    pub fn last_storage_writes(&self) -> &[StorageWrite] {
+        &self.last_storage_writes
+    }
+
+    /// Returns an [AddressBook] with the addresses of:
+    /// - user accounts created with [account_address](Self::account_address) and accounts
+    ///   imported with [import_address_book](Self::import_address_book), stored under their names,
+    /// - standard module accounts, like `gov` or `fee_collector`, stored under module names,
+    /// - all instantiated contracts, stored under contract labels.
+    ///
+    /// Every address is stored once, under the first name found in the order above.
+    /// When the name is already used by another address, the first free numeric suffix
+    /// is appended to it, like `label_2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// let alice = app.account_address("alice").unwrap();
+    ///
+    /// let address_book = app.address_book().unwrap();
+    /// assert_eq!(Some(&alice), address_book.get("alice"));
+    /// assert_eq!(Some(&app.module_address("gov").unwrap()), address_book.get("gov"));
+    /// ```
+    pub fn address_book(&self) -> AnyResult<AddressBook> {
+        let mut address_book = self.accounts.clone();
+        for module_name in MODULE_ACCOUNTS {
+            address_book.insert_unique(module_name, self.module_address(module_name)?);
+        }
+        for (addr, contract_data) in self.router.wasm.contracts(&self.storage)? {
+            address_book.insert_unique(&contract_data.label, addr);
+        }
+        Ok(address_book)
+    }
+
+    /// Imports named addresses, like an address book exported by another test suite
+    /// with [address_book](Self::address_book). Imported addresses are included
+    /// in the address book returned by this application, under the same names.
+    pub fn import_address_book(&mut self, address_book: AddressBook) {
+        self.accounts.extend(address_book);
+    }
+
+    /// Returns the history of code changes of the contract with specified address,
+    /// oldest first: its instantiation followed by all migrations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+    /// use cw_multi_test::{App, ContractCodeHistoryOperation, ContractWrapper, Executor};
+    ///
+    /// fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    /// fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    /// fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    ///     Ok(Binary::default())
+    /// }
+    /// fn migrate(_: DepsMut, _: Env, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    ///
+    /// let mut app = App::default();
+    /// let owner = app.api().addr_make("owner");
+    /// let code = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
+    /// let code_id = app.store_code(Box::new(code));
+    /// let new_code_id = app.duplicate_code(code_id).unwrap();
+    /// let contract_addr = app
+    ///     .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "contract", Some(owner.to_string()))
+    ///     .unwrap();
+    /// app.migrate_contract(owner, contract_addr.clone(), &Empty {}, new_code_id).unwrap();
+    ///
+    /// let history = app.contract_history(&contract_addr).unwrap();
+    /// assert_eq!(2, history.len());
+    /// assert_eq!(ContractCodeHistoryOperation::Migrate, history[1].operation);
+    /// assert_eq!(new_code_id, history[1].code_id);
+    /// ```
+    pub fn contract_history(
+        &self,
+        contract_addr: &Addr,
+    ) -> AnyResult<Vec<ContractCodeHistoryEntry>> {
+        self.router
+            .wasm
+            .contract_history(&self.storage, contract_addr)
+    }
+
+    /// Returns identifiers of all stored codes, in ascending order.
+    pub fn code_ids(&self) -> Vec<u64> {
+        self.router.wasm.code_ids()
+    }
+
+    /// Returns addresses of all contracts using the code with specified identifier,
+    /// in the order of their instantiation (or migration to this code),
+    /// like the `ContractsByCode` query of `wasmd`.
+    pub fn contracts_by_code(&self, code_id: u64) -> AnyResult<Vec<Addr>> {
+        Ok(
+            contracts_by_code(&self.router.wasm, &self.storage, code_id)?
+                .into_iter()
+                .map(|(_, addr)| addr)
+                .collect(),
+        )
+    }
+
     /// Returns a raw state dump of all key-values held by a contract with specified address.
     pub fn dump_wasm_raw(&self, address: &Addr) -> Vec<Record> {
         self.router.wasm.dump_wasm_raw(&self.storage, address)
@@ -360,6 +947,104 @@ where
             .contract_storage_mut(&mut self.storage, contract_addr)
     }
 
+    /// Writes the raw value under specified key in the storage of the contract
+    /// with specified address, without executing any transaction.
+    /// Useful for patching the state of a contract in tests.
+    pub fn set_contract_storage(&mut self, contract_addr: &Addr, key: &[u8], value: &[u8]) {
+        self.contract_storage_mut(contract_addr).set(key, value);
+    }
+
+    /// Saves the value of a [cw_storage_plus::Item] with specified storage key
+    /// in the storage of the contract with specified address.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::App;
+    /// use cw_storage_plus::Item;
+    ///
+    /// const CONFIG: Item<String> = Item::new("config");
+    ///
+    /// let mut app = App::default();
+    /// let contract_addr = app.api().addr_make("contract");
+    ///
+    /// app.save_item(&contract_addr, "config", &"patched".to_string()).unwrap();
+    /// assert_eq!("patched", CONFIG.load(&*app.contract_storage(&contract_addr)).unwrap());
+    /// ```
+    pub fn save_item<T: Serialize + DeserializeOwned>(
+        &mut self,
+        contract_addr: &Addr,
+        storage_key: &str,
+        value: &T,
+    ) -> AnyResult<()> {
+        let item: Item<T> = Item::new_dyn(storage_key.to_string());
+        Ok(item.save(self.contract_storage_mut(contract_addr).as_mut(), value)?)
+    }
+
+    /// Saves the entry with specified key of a [cw_storage_plus::Map] with specified namespace
+    /// in the storage of the contract with specified address.
+    pub fn save_map_entry<'k, K, V>(
+        &mut self,
+        contract_addr: &Addr,
+        namespace: &str,
+        key: K,
+        value: &V,
+    ) -> AnyResult<()>
+    where
+        K: PrimaryKey<'k>,
+        V: Serialize + DeserializeOwned,
+    {
+        let map: Map<K, V> = Map::new_dyn(namespace.to_string());
+        Ok(map.save(
+            self.contract_storage_mut(contract_addr).as_mut(),
+            key,
+            value,
+        )?)
+    }
+
+    /// Loads the value of a [cw_storage_plus::Item] with specified storage key
+    /// from the storage of the contract with specified address.
+    ///
+    /// Allows to assert on the internal state of a contract without adding
+    /// dedicated queries to the contract.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// let contract_addr = app.api().addr_make("contract");
+    ///
+    /// app.save_item(&contract_addr, "count", &12u64).unwrap();
+    /// let count: u64 = app.load_item(&contract_addr, "count").unwrap();
+    /// assert_eq!(12, count);
+    /// ```
+    pub fn load_item<T: Serialize + DeserializeOwned>(
+        &self,
+        contract_addr: &Addr,
+        storage_key: &str,
+    ) -> AnyResult<T> {
+        let item: Item<T> = Item::new_dyn(storage_key.to_string());
+        Ok(item.load(self.contract_storage(contract_addr).as_ref())?)
+    }
+
+    /// Loads the entry with specified key of a [cw_storage_plus::Map] with specified namespace
+    /// from the storage of the contract with specified address.
+    pub fn load_map_entry<'k, K, V>(
+        &self,
+        contract_addr: &Addr,
+        namespace: &str,
+        key: K,
+    ) -> AnyResult<V>
+    where
+        K: PrimaryKey<'k>,
+        V: Serialize + DeserializeOwned,
+    {
+        let map: Map<K, V> = Map::new_dyn(namespace.to_string());
+        Ok(map.load(self.contract_storage(contract_addr).as_ref(), key)?)
+    }
+
     /// Returns **read-only** prefixed storage with specified namespace.
     pub fn prefixed_storage<'a>(&'a self, namespace: &[u8]) -> Box<dyn Storage + 'a> {
         Box::new(prefixed_read(&self.storage, namespace))
@@ -392,6 +1077,7 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, Starga
 where
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
     ApiT: Api,
@@ -403,22 +1089,286 @@ where
     GovT: Gov,
     StargateT: Stargate,
 {
+    /// Stores the built-in [cw20](crate::cw20) token contract and instantiates it
+    /// with specified name (used also as the symbol) and initial balances.
+    /// The owner becomes the admin and the minter of the token.
+    ///
+    /// Returns the address of the token contract.
+    #[cfg(feature = "cw20")]
+    pub fn create_cw20(
+        &mut self,
+        owner: &Addr,
+        name: &str,
+        initial_balances: Vec<crate::cw20::Cw20Coin>,
+    ) -> AnyResult<Addr> {
+        let code_id = self.store_code_with_creator(owner.clone(), crate::cw20::contract());
+        let msg = crate::cw20::instantiate_msg(owner, name, initial_balances);
+        self.instantiate_contract(
+            code_id,
+            owner.clone(),
+            &msg,
+            &[],
+            name,
+            Some(owner.to_string()),
+        )
+    }
+
+    /// Returns the balance of the address in the [cw20](crate::cw20) token contract.
+    #[cfg(feature = "cw20")]
+    pub fn cw20_balance(&self, token: &Addr, address: &Addr) -> AnyResult<cosmwasm_std::Uint128> {
+        let response: crate::cw20::BalanceResponse = self.wrap().query_wasm_smart(
+            token,
+            &crate::cw20::Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )?;
+        Ok(response.balance)
+    }
+
     /// Sets the initial block properties.
+    ///
+    /// Matured unbondings are processed using the new block time,
+    /// see [update_block](Self::update_block) for details.
     pub fn set_block(&mut self, block: BlockInfo) {
-        self.router
-            .staking
-            .process_queue(&self.api, &mut self.storage, &self.router, &self.block)
-            .unwrap();
-        self.block = block;
+        self.switch_block(block);
     }
 
     /// Updates the current block applying the specified closure, usually [next_block].
+    ///
+    /// Unbondings that matured up to the new block time (`unbonding_time` from [StakingInfo](crate::StakingInfo)
+    /// after undelegating) are paid out, so the funds are available right after the block is updated.
     pub fn update_block<F: Fn(&mut BlockInfo)>(&mut self, action: F) {
+        let mut block = self.block.clone();
+        action(&mut block);
+        self.switch_block(block);
+    }
+
+    /// Switches to specified block. When the height changes, the current block is ended first,
+    /// so end blockers run with the info of the ending block, like on a real chain.
+    fn switch_block(&mut self, block: BlockInfo) {
+        if block.height != self.block.height {
+            self.run_end_blockers();
+            self.tx_index = 0;
+            self.block_gas_used = 0;
+        }
+        self.block = block;
+        self.apply_chain_upgrades();
+        self.process_staking_queue();
+        self.publish_snapshot();
+    }
+
+    /// Sets the chain identifier reported in the block info.
+    pub fn set_chain_id(&mut self, chain_id: impl Into<String>) {
+        self.block.chain_id = chain_id.into();
+    }
+
+    /// Returns the revision number of the current chain identifier,
+    /// following the IBC convention for chain identifiers like `{name}-{revision}`.
+    /// Chain identifiers not following this convention have revision number zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// app.set_chain_id("osmosis-1");
+    /// assert_eq!(1, app.chain_revision());
+    ///
+    /// app.set_chain_id("testing");
+    /// assert_eq!(0, app.chain_revision());
+    /// ```
+    pub fn chain_revision(&self) -> u64 {
+        chain_revision(&self.block.chain_id)
+    }
+
+    /// Schedules a chain upgrade: starting from the block with specified height,
+    /// blocks report the new chain identifier, like after a chain upgrade
+    /// changing the revision number.
+    ///
+    /// The upgrade is applied when the block is updated with [update_block](Self::update_block)
+    /// or [set_block](Self::set_block) to the specified height or above.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{next_block, App};
+    ///
+    /// let mut app = App::default();
+    /// let height = app.block_info().height;
+    /// app.set_chain_id("testing-1");
+    /// app.schedule_chain_upgrade(height + 2, "testing-2");
+    ///
+    /// app.update_block(next_block);
+    /// assert_eq!("testing-1", app.block_info().chain_id);
+    ///
+    /// app.update_block(next_block);
+    /// assert_eq!("testing-2", app.block_info().chain_id);
+    /// assert_eq!(2, app.chain_revision());
+    /// ```
+    pub fn schedule_chain_upgrade(&mut self, height: u64, chain_id: impl Into<String>) {
+        self.chain_upgrades.insert(height, chain_id.into());
+    }
+
+    /// Applies all scheduled chain upgrades up to the current block height.
+    fn apply_chain_upgrades(&mut self) {
+        let pending = self.chain_upgrades.split_off(&(self.block.height + 1));
+        let applied = std::mem::replace(&mut self.chain_upgrades, pending);
+        if let Some((_, chain_id)) = applied.into_iter().next_back() {
+            self.block.chain_id = chain_id;
+        }
+    }
+
+    /// Processes the staking queue using the current block, like an end blocker does.
+    fn process_staking_queue(&mut self) {
         self.router
             .staking
             .process_queue(&self.api, &mut self.storage, &self.router, &self.block)
             .unwrap();
-        action(&mut self.block);
+    }
+
+    /// Registers a privileged message executed every time the block height changes,
+    /// before the block is updated, like end blockers of chain modules do.
+    /// End blockers run with the info of the ending block, so `env.block` passed
+    /// to contracts reports the block before the update.
+    /// Usually used to call the `sudo` entry-point of contracts driven by chain-side schedulers,
+    /// like the cron module on Neutron.
+    ///
+    /// End blockers are executed in the order of registration, once per block update,
+    /// even when the height is advanced by more than one block.
+    /// Each end blocker runs in its own transaction, the state changes made by a failing
+    /// end blocker are reverted and remaining end blockers are still executed.
+    /// Results are available with [end_block_results](Self::end_block_results).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::coins;
+    /// use cw_multi_test::{next_block, App, BankSudo};
+    ///
+    /// let mut app = App::default();
+    /// let addr = app.api().addr_make("rewards");
+    ///
+    /// app.add_end_blocker(BankSudo::Mint {
+    ///     to_address: addr.to_string(),
+    ///     amount: coins(10, "reward"),
+    /// });
+    ///
+    /// app.update_block(next_block);
+    /// app.update_block(next_block);
+    ///
+    /// assert_eq!(20, app.wrap().query_balance(addr, "reward").unwrap().amount.u128());
+    /// ```
+    pub fn add_end_blocker(&mut self, msg: impl Into<SudoMsg>) {
+        self.end_blockers.push(msg.into());
+    }
+
+    /// Removes all registered end blockers.
+    pub fn clear_end_blockers(&mut self) {
+        self.end_blockers.clear();
+    }
+
+    /// Returns the results of end blockers executed at the end of the last block,
+    /// in the order of registration. Failures are reported as error messages.
+    pub fn end_block_results(&self) -> &[Result<AppResponse, String>] {
+        &self.end_block_results
+    }
+
+    /// Enables or disables recording emitted events, see [events_since](Self::events_since).
+    /// Recording is disabled by default, so the log does not grow in tests that do not use it.
+    pub fn set_event_log_enabled(&mut self, enabled: bool) {
+        self.event_log.set_enabled(enabled);
+    }
+
+    /// Removes all events recorded so far, cursors taken before remain valid.
+    pub fn clear_event_log(&mut self) {
+        self.event_log.clear();
+    }
+
+    /// Returns the cursor pointing past the last event recorded so far,
+    /// to be passed to [events_since](Self::events_since) later.
+    pub fn event_cursor(&self) -> usize {
+        self.event_log.cursor()
+    }
+
+    /// Returns all events recorded since the cursor was taken with [event_cursor](Self::event_cursor),
+    /// in emission order, including events of nested submessages.
+    ///
+    /// Events are recorded only when enabled with [set_event_log_enabled](Self::set_event_log_enabled),
+    /// for every committed transaction, sudo call and end blocker.
+    /// Events of failed (reverted) executions and simulations are not recorded,
+    /// events recorded after a snapshot are removed when the snapshot is [restored](Self::restore).
+    /// Passing zero returns all events recorded since the log was last cleared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{coins, BankMsg};
+    /// use cw_multi_test::{App, Executor};
+    ///
+    /// let mut app = App::default();
+    /// app.set_event_log_enabled(true);
+    /// let alice = app.api().addr_make("alice");
+    /// let bob = app.api().addr_make("bob");
+    /// app.init_balances(&[(alice.clone(), coins(100, "uatom"))]).unwrap();
+    ///
+    /// let cursor = app.event_cursor();
+    /// let msg = BankMsg::Send { to_address: bob.to_string(), amount: coins(10, "uatom") };
+    /// app.execute(alice.clone(), msg.into()).unwrap();
+    /// let msg = BankMsg::Send { to_address: bob.to_string(), amount: coins(1000, "uatom") };
+    /// app.execute(alice, msg.into()).unwrap_err();
+    ///
+    /// let transfers = app
+    ///     .events_since(cursor)
+    ///     .iter()
+    ///     .filter(|event| event.ty == "transfer")
+    ///     .count();
+    /// assert_eq!(1, transfers);
+    /// ```
+    pub fn events_since(&self, cursor: usize) -> &[Event] {
+        self.event_log.since(cursor)
+    }
+
+    /// Appends events of committed responses to the event log.
+    fn record_events<'a>(&mut self, responses: impl IntoIterator<Item = &'a AppResponse>) {
+        for response in responses {
+            self.event_log.record(&response.events);
+        }
+    }
+
+    /// Executes all registered end blockers, each one in its own transaction.
+    fn run_end_blockers(&mut self) {
+        let Self {
+            block,
+            router,
+            api,
+            storage,
+            end_blockers,
+            ..
+        } = self;
+        self.end_block_results = end_blockers
+            .iter()
+            .map(|msg| {
+                transactional(&mut *storage, |write_cache, _| {
+                    router.sudo(&*api, write_cache, block, msg.clone())
+                })
+                .map_err(|err| err.to_string())
+            })
+            .collect();
+        for response in self.end_block_results.iter().flatten() {
+            self.event_log.record(&response.events);
+        }
+    }
+
+    /// Sets the seed of deterministic per-block randomness,
+    /// see [AppBuilder::with_random_seed](crate::AppBuilder::with_random_seed) for details.
+    pub fn set_random_seed(&mut self, seed: impl Into<Binary>) {
+        randomness::save_seed(&mut self.storage, &seed.into()).unwrap();
+    }
+
+    /// Returns the randomness of the current block, when the random seed is set.
+    pub fn block_randomness(&self) -> Option<Binary> {
+        randomness::block_randomness(&self.storage, &self.block).unwrap()
     }
 
     /// Returns a copy of the current block_info
@@ -432,32 +1382,376 @@ where
         QuerierWrapper::new(self)
     }
 
+    /// Returns the responses of a successfully executed transaction with specified hash,
+    /// one response per message included in the transaction.
+    ///
+    /// Transaction hashes are reported in [TxMetadata] attached to every [AppResponse]
+    /// returned from [execute](Executor::execute) or [execute_multi](Self::execute_multi).
+    pub fn tx(&self, hash: &str) -> Option<&[AppResponse]> {
+        self.transactions.get(hash).map(Vec::as_slice)
+    }
+
+    /// Sets the maximum amount of gas that can be consumed by all transactions
+    /// executed in a single block, `None` removes the limit.
+    ///
+    /// A transaction exceeding the gas left in the current block fails with an out of gas error.
+    /// Gas is metered only when enabled in [WasmKeeper](crate::WasmKeeper::with_gas_costs).
+    pub fn set_block_gas_limit(&mut self, limit: Option<u64>) {
+        self.block_gas_limit = limit;
+    }
+
+    /// Returns the gas consumed by transactions executed in the current block,
+    /// including failed transactions.
+    pub fn block_gas_used(&self) -> u64 {
+        self.block_gas_used
+    }
+
     /// Runs multiple CosmosMsg in one atomic operation.
     /// This will create a cache before the execution, so no state changes are persisted if any of them
     /// return an error. But all writes are persisted on success.
+    ///
+    /// All messages are processed as a single transaction, so all returned responses
+    /// share the same [TxMetadata].
     pub fn execute_multi(
         &mut self,
         sender: Addr,
         msgs: Vec<CosmosMsg<CustomT::ExecT>>,
+    ) -> AnyResult<Vec<AppResponse>> {
+        self.execute_tx(sender, msgs, None)
+    }
+
+    /// Starts a new block (see [next_block]) and executes specified transactions in it,
+    /// one after another, like a block proposer does. Every transaction, also the failed one,
+    /// gets the next index in the block, passed to contracts in `env.transaction.index`
+    /// and reported in [TxMetadata]. Failed transactions do not stop processing the block,
+    /// their errors are returned in place of their responses.
+    ///
+    /// The block ends when the next block starts, that is when end blockers are run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{coins, BankMsg};
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// let alice = app.api().addr_make("alice");
+    /// let bob = app.api().addr_make("bob");
+    /// app.init_balances(&[(alice.clone(), coins(100, "uatom"))]).unwrap();
+    ///
+    /// let send = |to: &str| BankMsg::Send { to_address: to.to_string(), amount: coins(10, "uatom") }.into();
+    /// let results = app.execute_block(vec![
+    ///     (alice.clone(), vec![send(bob.as_str())]),
+    ///     (bob.clone(), vec![send(alice.as_str()), send(alice.as_str())]),
+    ///     (alice.clone(), vec![send(bob.as_str())]),
+    /// ]);
+    ///
+    /// assert_eq!(0, results[0].as_ref().unwrap()[0].tx.as_ref().unwrap().index);
+    /// assert!(results[1].is_err());
+    /// assert_eq!(2, results[2].as_ref().unwrap()[0].tx.as_ref().unwrap().index);
+    /// ```
+    pub fn execute_block(
+        &mut self,
+        txs: Vec<(Addr, Vec<CosmosMsg<CustomT::ExecT>>)>,
+    ) -> Vec<AnyResult<Vec<AppResponse>>> {
+        self.update_block(next_block);
+        txs.into_iter()
+            .map(|(sender, msgs)| self.deliver_tx(sender, msgs, None))
+            .collect()
+    }
+
+    /// Runs a single CosmosMsg in one atomic operation, like [execute](Executor::execute) does,
+    /// but fails with an out of gas error when contracts consume more than `gas_limit` gas.
+    ///
+    /// Gas is metered only when enabled in [WasmKeeper](crate::WasmKeeper::with_gas_costs),
+    /// the consumed gas is reported in [TxMetadata] of the returned response.
+    pub fn execute_with_gas_limit(
+        &mut self,
+        sender: Addr,
+        msg: CosmosMsg<CustomT::ExecT>,
+        gas_limit: u64,
+    ) -> AnyResult<AppResponse> {
+        let mut responses = self.execute_tx(sender, vec![msg], Some(gas_limit))?;
+        Ok(responses.pop().unwrap())
+    }
+
+    /// Executes the contract on behalf of the module account with specified name,
+    /// like when the message is executed by a passed governance proposal.
+    /// Useful for testing authority-gated entry-points that check
+    /// whether `info.sender` is the module address.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::Empty;
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// let gov = app.module_address("gov").unwrap();
+    /// assert_eq!("cosmwasm10d07y265gmmuvt4z0w9aw880jnsr700jgytlue", gov.as_str());
+    ///
+    /// // there is no such contract, so the execution fails
+    /// let contract_addr = app.api().addr_make("contract");
+    /// assert!(app.execute_as_module("gov", contract_addr, &Empty {}).is_err());
+    /// ```
+    pub fn execute_as_module<T: Serialize + Debug>(
+        &mut self,
+        module_name: &str,
+        contract_addr: Addr,
+        msg: &T,
+    ) -> AnyResult<AppResponse> {
+        let sender = self.module_address(module_name)?;
+        self.execute_contract(sender, contract_addr, msg, &[])
+    }
+
+    /// Simulates the execution of a single CosmosMsg, like the transaction simulation
+    /// endpoint of a chain does. The message is executed against a cache which is discarded
+    /// afterwards, so the application state is never changed.
+    ///
+    /// Returns the response with events and data, the gas that would be consumed
+    /// is reported in [TxMetadata] of the response (when gas is metered).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{coins, BankMsg};
+    /// use cw_multi_test::App;
+    ///
+    /// let app = App::default();
+    /// let sender = app.api().addr_make("sender");
+    ///
+    /// // sender has no funds, so the transaction would fail
+    /// let msg = BankMsg::Burn { amount: coins(10, "uatom") };
+    /// assert!(app.simulate(sender, msg.into()).is_err());
+    /// ```
+    pub fn simulate(&self, sender: Addr, msg: CosmosMsg<CustomT::ExecT>) -> AnyResult<AppResponse> {
+        let mut tx = self.next_tx_metadata(&sender, std::slice::from_ref(&msg))?;
+        let mut cache = StorageTransaction::new(&self.storage);
+        let (res, gas_used) = self.router.wasm.run_simulation(tx.index, || {
+            self.router
+                .execute(&self.api, &mut cache, &self.block, sender, msg)
+        });
+        let mut response = res?;
+        tx.gas_used = gas_used;
+        response.tx = Some(tx);
+        Ok(response)
+    }
+
+    /// Sets the block advancement applied after every executed transaction,
+    /// `None` disables automatic block advancement.
+    pub fn set_auto_block_advance(&mut self, params: Option<BlockParams>) {
+        self.auto_block_advance = params;
+    }
+
+    /// Executes messages as a single transaction, then advances the block
+    /// when automatic block advancement is enabled, also when the transaction failed.
+    fn execute_tx(
+        &mut self,
+        sender: Addr,
+        msgs: Vec<CosmosMsg<CustomT::ExecT>>,
+        gas_limit: Option<u64>,
+    ) -> AnyResult<Vec<AppResponse>> {
+        let res = self.deliver_tx(sender, msgs, gas_limit);
+        if let Some(params) = self.auto_block_advance {
+            self.update_block(|block| {
+                block.height += params.blocks_per_tx;
+                block.time = block
+                    .time
+                    .plus_seconds(params.seconds_per_block * params.blocks_per_tx);
+            });
+        }
+        res
+    }
+
+    /// Executes messages as a single transaction, limiting the gas consumed
+    /// by the transaction and by all transactions in the current block.
+    fn deliver_tx(
+        &mut self,
+        sender: Addr,
+        msgs: Vec<CosmosMsg<CustomT::ExecT>>,
+        gas_limit: Option<u64>,
     ) -> AnyResult<Vec<AppResponse>> {
         // we need to do some caching of storage here, once in the entry point:
         // meaning, wrap current state, all writes go to a cache, only when execute
         // returns a success do we flush it (otherwise drop it)
 
+        let mut tx = self.next_tx_metadata(&sender, &msgs)?;
+        let gas_limit = match self.block_gas_limit {
+            Some(block_limit) => {
+                let gas_left = block_limit.saturating_sub(self.block_gas_used);
+                Some(gas_limit.map_or(gas_left, |limit| limit.min(gas_left)))
+            }
+            None => gas_limit,
+        };
+        let gas_before = self.router.wasm.gas_used();
+        self.router.wasm.set_gas_limit(gas_limit);
+        self.router.wasm.set_transaction_index(tx.index);
+        self.router.wasm.reset_trace();
+        self.last_storage_writes.clear();
+
         let Self {
             block,
             router,
             api,
             storage,
+            ..
         } = self;
 
-        transactional(&mut *storage, |write_cache, _| {
-            msgs.into_iter()
-                .map(|msg| router.execute(&*api, write_cache, block, sender.clone(), msg))
-                .collect()
+        let res = transactional_with_writes(&mut *storage, |write_cache, _| {
+            let responses = msgs
+                .into_iter()
+                .enumerate()
+                .map(|(index, msg)| {
+                    router
+                        .execute(&*api, write_cache, block, sender.clone(), msg)
+                        .map_err(|mut err| {
+                            AppError::set_msg_index(&mut err, index);
+                            err
+                        })
+                })
+                .collect::<AnyResult<Vec<_>>>()?;
+            // storage accesses after the last contract call are checked here
+            let gas_used = router.wasm.gas_used() - gas_before;
+            match gas_limit {
+                Some(limit) if gas_used > limit => bail!(Error::out_of_gas(gas_used, limit)),
+                _ => Ok(responses),
+            }
+        });
+        self.router.wasm.set_gas_limit(None);
+        self.router.wasm.set_transaction_index(0);
+        // failed transactions are included in the block too, so they take up an index
+        // and consume gas, at most up to their gas limit
+        self.tx_index += 1;
+        let gas_used = self.router.wasm.gas_used() - gas_before;
+        tx.gas_used = gas_limit.map_or(gas_used, |limit| gas_used.min(limit));
+        self.block_gas_used += tx.gas_used;
+        let (mut responses, writes) = res?;
+        self.last_storage_writes = writes;
+
+        for response in responses.iter_mut() {
+            response.tx = Some(tx.clone());
+        }
+        self.record_events(&responses);
+        self.transactions.insert(tx.hash, responses.clone());
+        self.publish_snapshot();
+        Ok(responses)
+    }
+
+    /// Returns the metadata of the next transaction executed in the current block.
+    ///
+    /// The hash is a SHA-256 digest of the block height, transaction index,
+    /// sender address and the JSON-serialized messages, so it is fully deterministic.
+    fn next_tx_metadata(
+        &self,
+        sender: &Addr,
+        msgs: &[CosmosMsg<CustomT::ExecT>],
+    ) -> AnyResult<TxMetadata> {
+        let digest = Sha256::new()
+            .chain_update(self.block.height.to_be_bytes())
+            .chain_update(self.tx_index.to_be_bytes())
+            .chain_update(sender.as_bytes())
+            .chain_update(to_json_vec(msgs)?)
+            .finalize();
+        Ok(TxMetadata {
+            hash: HexBinary::from(digest.as_slice()).to_hex().to_uppercase(),
+            height: self.block.height,
+            index: self.tx_index,
+            gas_used: 0,
         })
     }
 
+    /// Delivers a hand-crafted IBC packet to the `ibc_packet_receive` entry-point
+    /// of the contract with specified address, as if it was relayed from the counterparty chain.
+    /// Useful for testing how contracts validate packets sent by misbehaving counterparties.
+    ///
+    /// Like [wasm_sudo](Self::wasm_sudo), no state changes are persisted if this returns an error.
+    /// The acknowledgement written by the contract is returned as response data.
+    pub fn inject_ibc_packet_receive(
+        &mut self,
+        contract_addr: &Addr,
+        msg: IbcPacketReceiveMsg,
+    ) -> AnyResult<AppResponse> {
+        self.execute_outside_tx(|router, api, storage, block| {
+            router
+                .wasm
+                .ibc_packet_receive(api, storage, router, block, contract_addr.clone(), msg)
+        })
+    }
+
+    /// Migrates the contract to the code with specified identifier regardless of its admin,
+    /// like a passed governance proposal with `MsgMigrateContract` does in `wasmd`.
+    ///
+    /// Like [wasm_sudo](Self::wasm_sudo), no state changes are persisted if this returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult};
+    /// use cw_multi_test::{App, ContractWrapper, Executor};
+    ///
+    /// fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    /// fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    /// fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    ///     Ok(Binary::default())
+    /// }
+    /// fn migrate(_: DepsMut, _: Env, _: Empty) -> StdResult<Response> {
+    ///     Ok(Response::default())
+    /// }
+    ///
+    /// let mut app = App::default();
+    /// let owner = app.api().addr_make("owner");
+    /// let code = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
+    /// let code_id = app.store_code(Box::new(code));
+    /// let new_code_id = app.duplicate_code(code_id).unwrap();
+    ///
+    /// // the contract has no admin, so only the governance can migrate it
+    /// let contract_addr = app
+    ///     .instantiate_contract(code_id, owner, &Empty {}, &[], "contract", None)
+    ///     .unwrap();
+    /// app.sudo_migrate(&contract_addr, new_code_id, &Empty {}).unwrap();
+    /// assert_eq!(new_code_id, app.contract_data(&contract_addr).unwrap().code_id);
+    /// ```
+    pub fn sudo_migrate<T: Serialize>(
+        &mut self,
+        contract_addr: &Addr,
+        new_code_id: u64,
+        msg: &T,
+    ) -> AnyResult<AppResponse> {
+        self.sudo(
+            WasmAdminSudo::Migrate {
+                contract_addr: contract_addr.clone(),
+                new_code_id,
+                msg: to_json_binary(msg)?,
+            }
+            .into(),
+        )
+    }
+
+    /// Sets the admin of the contract regardless of its current admin,
+    /// like a passed governance proposal with `MsgUpdateAdmin` does in `wasmd`.
+    /// `None` clears the admin.
+    ///
+    /// Setting the admin to the governance module account, returned by
+    /// [module_address](Self::module_address) for `gov`, makes the contract
+    /// managed by the governance.
+    pub fn sudo_update_admin(
+        &mut self,
+        contract_addr: &Addr,
+        admin: Option<Addr>,
+    ) -> AnyResult<AppResponse> {
+        self.sudo(
+            WasmAdminSudo::UpdateAdmin {
+                contract_addr: contract_addr.clone(),
+                admin,
+            }
+            .into(),
+        )
+    }
+
     /// Call a smart contract in "sudo" mode.
     /// This will create a cache before the execution, so no state changes are persisted if this
     /// returns an error, but all are persisted on success.
@@ -470,16 +1764,8 @@ where
             contract_addr: contract_addr.into(),
             message: to_json_binary(msg)?,
         };
-
-        let Self {
-            block,
-            router,
-            api,
-            storage,
-        } = self;
-
-        transactional(&mut *storage, |write_cache, _| {
-            router.wasm.sudo(&*api, write_cache, router, block, msg)
+        self.execute_outside_tx(|router, api, storage, block| {
+            router.wasm.sudo(api, storage, router, block, msg)
         })
     }
 
@@ -487,19 +1773,69 @@ where
     /// This will create a cache before the execution, so no state changes are persisted if this
     /// returns an error, but all are persisted on success.
     pub fn sudo(&mut self, msg: SudoMsg) -> AnyResult<AppResponse> {
+        self.execute_outside_tx(|router, api, storage, block| router.sudo(api, storage, block, msg))
+    }
+
+    /// Executes specified action outside of any transaction.
+    fn execute_outside_tx<F>(&mut self, action: F) -> AnyResult<AppResponse>
+    where
+        F: FnOnce(
+            &Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>,
+            &dyn Api,
+            &mut dyn Storage,
+            &BlockInfo,
+        ) -> AnyResult<AppResponse>,
+    {
         // we need to do some caching of storage here, once in the entry point:
         // meaning, wrap current state, all writes go to a cache, only when execute
         // returns a success do we flush it (otherwise drop it)
+        self.router.wasm.reset_trace();
+        self.last_storage_writes.clear();
         let Self {
             block,
             router,
             api,
             storage,
+            ..
         } = self;
 
-        transactional(&mut *storage, |write_cache, _| {
-            router.sudo(&*api, write_cache, block, msg)
-        })
+        let (res, writes) = transactional_with_writes(&mut *storage, |write_cache, _| {
+            action(router, &*api, write_cache, block)
+        })?;
+        self.last_storage_writes = writes;
+        self.record_events([&res]);
+        self.publish_snapshot();
+        Ok(res)
+    }
+
+    /// Mints specified amounts of tokens for many accounts at once,
+    /// in a single [BankSudo::MintMany] message.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::coins;
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// let alice = app.api().addr_make("alice");
+    /// let bob = app.api().addr_make("bob");
+    ///
+    /// app.init_balances(&[
+    ///     (alice.clone(), coins(100, "uatom")),
+    ///     (bob.clone(), coins(200, "uatom")),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(100, app.wrap().query_balance(alice, "uatom").unwrap().amount.u128());
+    /// assert_eq!(200, app.wrap().query_balance(bob, "uatom").unwrap().amount.u128());
+    /// ```
+    pub fn init_balances(&mut self, balances: &[(Addr, Vec<Coin>)]) -> AnyResult<AppResponse> {
+        let mints = balances
+            .iter()
+            .map(|(address, amount)| (address.to_string(), amount.clone()))
+            .collect();
+        self.sudo(BankSudo::MintMany { mints }.into())
     }
 }
 /// The Router plays a critical role in managing and directing
@@ -529,6 +1865,7 @@ impl<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 where
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
     CustomT: Module,
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
@@ -552,19 +1889,68 @@ where
             block_info,
         }
     }
+
+    /// Handles the [CAPABILITIES_QUERY_PATH] query.
+    fn query_capabilities(&self, api: &dyn Api) -> AnyResult<Binary> {
+        let response: QueryCapabilitiesResponse =
+            Capabilities::new(api, self.enabled_modules()).into();
+        Ok(response.encode_to_vec().into())
+    }
+}
+
+impl<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+where
+    BankT: Bank,
+    CustomT: Module,
+    StakingT: Staking,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    StargateT: Stargate,
+{
+    /// Returns names of enabled modules, see [Module::is_enabled].
+    pub(crate) fn enabled_modules(&self) -> Vec<String> {
+        [
+            ("bank", self.bank.is_enabled()),
+            ("custom", self.custom.is_enabled()),
+            ("distribution", self.distribution.is_enabled()),
+            ("gov", self.gov.is_enabled()),
+            ("ibc", self.ibc.is_enabled()),
+            ("staking", self.staking.is_enabled()),
+            ("stargate", self.stargate.is_enabled()),
+            ("wasm", true),
+        ]
+        .into_iter()
+        .filter(|(_, enabled)| *enabled)
+        .map(|(module, _)| module.to_string())
+        .collect()
+    }
 }
 
 /// We use it to allow calling into modules from another module in sudo mode.
 /// Things like gov proposals belong here.
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum SudoMsg {
     /// Bank privileged actions.
     Bank(BankSudo),
-    /// Custom privileged actions.
-    Custom(Empty),
+    /// Custom privileged actions, encoded as JSON and passed
+    /// to [sudo_custom](crate::Module::sudo_custom) of the custom module.
+    Custom(Binary),
+    /// Governance privileged actions.
+    Gov(GovSudo),
     /// Staking privileged actions.
     Staking(StakingSudo),
     /// Wasm privileged actions.
     Wasm(WasmSudo),
+    /// Privileged contract administration actions.
+    WasmAdmin(WasmAdminSudo),
+}
+
+impl From<WasmAdminSudo> for SudoMsg {
+    fn from(wasm_admin: WasmAdminSudo) -> Self {
+        SudoMsg::WasmAdmin(wasm_admin)
+    }
 }
 
 impl From<WasmSudo> for SudoMsg {
@@ -579,6 +1965,12 @@ impl From<BankSudo> for SudoMsg {
     }
 }
 
+impl From<GovSudo> for SudoMsg {
+    fn from(gov: GovSudo) -> Self {
+        SudoMsg::Gov(gov)
+    }
+}
+
 impl From<StakingSudo> for SudoMsg {
     fn from(staking: StakingSudo) -> Self {
         SudoMsg::Staking(staking)
@@ -629,6 +2021,7 @@ impl<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT> CosmosRoute
 where
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
     CustomT: Module,
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
@@ -660,12 +2053,27 @@ where
             CosmosMsg::Ibc(msg) => self.ibc.execute(api, storage, self, block, sender, msg),
             CosmosMsg::Gov(msg) => self.gov.execute(api, storage, self, block, sender, msg),
             #[allow(deprecated)]
-            CosmosMsg::Stargate { type_url, value } => self
-                .stargate
-                .execute_stargate(api, storage, self, block, sender, type_url, value),
-            CosmosMsg::Any(msg) => self
-                .stargate
-                .execute_any(api, storage, self, block, sender, msg),
+            CosmosMsg::Stargate { type_url, value } => {
+                let msg = AnyMsg { type_url, value };
+                match self.wasm.execute_any(api, storage, &sender, &msg)? {
+                    Some(res) => Ok(res),
+                    None => self.stargate.execute_stargate(
+                        api,
+                        storage,
+                        self,
+                        block,
+                        sender,
+                        msg.type_url,
+                        msg.value,
+                    ),
+                }
+            }
+            CosmosMsg::Any(msg) => match self.wasm.execute_any(api, storage, &sender, &msg)? {
+                Some(res) => Ok(res),
+                None => self
+                    .stargate
+                    .execute_any(api, storage, self, block, sender, msg),
+            },
             _ => bail!("Cannot execute {:?}", msg),
         }
     }
@@ -686,13 +2094,38 @@ where
             QueryRequest::Bank(req) => self.bank.query(api, storage, &querier, block, req),
             QueryRequest::Custom(req) => self.custom.query(api, storage, &querier, block, req),
             QueryRequest::Staking(req) => self.staking.query(api, storage, &querier, block, req),
+            #[cfg(feature = "cosmwasm_1_3")]
+            QueryRequest::Distribution(req) => self
+                .distribution
+                .query_distribution(api, storage, &querier, block, req),
             QueryRequest::Ibc(req) => self.ibc.query(api, storage, &querier, block, req),
             #[allow(deprecated)]
-            QueryRequest::Stargate { path, data } => self
-                .stargate
-                .query_stargate(api, storage, &querier, block, path, data),
-            QueryRequest::Grpc(req) => self.stargate.query_grpc(api, storage, &querier, block, req),
-            _ => unimplemented!(),
+            QueryRequest::Stargate { path, .. } if path == CAPABILITIES_QUERY_PATH => {
+                self.query_capabilities(api)
+            }
+            QueryRequest::Grpc(req) if req.path == CAPABILITIES_QUERY_PATH => {
+                self.query_capabilities(api)
+            }
+            #[allow(deprecated)]
+            QueryRequest::Stargate { path, data } => {
+                let request = GrpcQuery { path, data };
+                match self.wasm.query_grpc(storage, &request)? {
+                    Some(res) => Ok(res),
+                    None => self.stargate.query_stargate(
+                        api,
+                        storage,
+                        &querier,
+                        block,
+                        request.path,
+                        request.data,
+                    ),
+                }
+            }
+            QueryRequest::Grpc(req) => match self.wasm.query_grpc(storage, &req)? {
+                Some(res) => Ok(res),
+                None => self.stargate.query_grpc(api, storage, &querier, block, req),
+            },
+            _ => bail!("Unsupported query request"),
         }
     }
 
@@ -705,9 +2138,22 @@ where
     ) -> AnyResult<AppResponse> {
         match msg {
             SudoMsg::Wasm(msg) => self.wasm.sudo(api, storage, self, block, msg),
+            SudoMsg::WasmAdmin(WasmAdminSudo::Migrate {
+                contract_addr,
+                new_code_id,
+                msg,
+            }) => {
+                self.wasm
+                    .sudo_migrate(api, storage, self, block, contract_addr, new_code_id, msg)
+            }
+            SudoMsg::WasmAdmin(WasmAdminSudo::UpdateAdmin {
+                contract_addr,
+                admin,
+            }) => self.wasm.sudo_update_admin(storage, &contract_addr, admin),
             SudoMsg::Bank(msg) => self.bank.sudo(api, storage, self, block, msg),
             SudoMsg::Staking(msg) => self.staking.sudo(api, storage, self, block, msg),
-            SudoMsg::Custom(_) => unimplemented!(),
+            SudoMsg::Gov(msg) => self.gov.sudo_gov(api, storage, self, block, msg),
+            SudoMsg::Custom(msg) => self.custom.sudo_custom(api, storage, self, block, msg),
         }
     }
 }
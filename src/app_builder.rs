@@ -1,14 +1,17 @@
 //! AppBuilder helps you set up your test blockchain environment step by step [App].
 
+use crate::randomness;
 use crate::{
-    App, Bank, BankKeeper, Distribution, DistributionKeeper, FailingModule, Gov, GovFailingModule,
-    Ibc, IbcFailingModule, Module, Router, StakeKeeper, Staking, Stargate, StargateFailing, Wasm,
+    AccountGenerator, App, Bank, BankKeeper, BlockParams, ChainProfile, Distribution,
+    DistributionKeeper, FailingModule, Gov, GovFailingModule, Ibc, IbcFailingModule, Module,
+    NamedAccountGenerator, Router, StakeKeeper, Staking, Stargate, StargateFailing, Wasm,
     WasmKeeper,
 };
 use cosmwasm_std::testing::{mock_env, MockApi, MockStorage};
-use cosmwasm_std::{Api, BlockInfo, CustomMsg, CustomQuery, Empty, Storage};
+use cosmwasm_std::{Api, Binary, BlockInfo, CustomMsg, CustomQuery, Empty, Storage};
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
+use std::sync::Arc;
 
 /// This is essential to create a custom app with custom module.
 ///
@@ -55,6 +58,10 @@ pub struct AppBuilder<Bank, Api, Storage, Custom, Wasm, Staking, Distr, Ibc, Gov
     ibc: Ibc,
     gov: Gov,
     stargate: Stargate,
+    account_generator: Arc<dyn AccountGenerator + Send + Sync>,
+    auto_block_advance: Option<BlockParams>,
+    random_seed: Option<Binary>,
+    chain_profile: Option<ChainProfile>,
 }
 
 impl Default
@@ -104,6 +111,10 @@ impl
             ibc: IbcFailingModule::new(),
             gov: GovFailingModule::new(),
             stargate: StargateFailing,
+            account_generator: Arc::new(NamedAccountGenerator),
+            auto_block_advance: None,
+            random_seed: None,
+            chain_profile: None,
         }
     }
 }
@@ -140,6 +151,10 @@ where
             ibc: IbcFailingModule::new(),
             gov: GovFailingModule::new(),
             stargate: StargateFailing,
+            account_generator: Arc::new(NamedAccountGenerator),
+            auto_block_advance: None,
+            random_seed: None,
+            chain_profile: None,
         }
     }
 }
@@ -171,6 +186,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -186,6 +205,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -206,6 +229,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -221,6 +248,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -241,6 +272,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -256,6 +291,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -276,6 +315,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -291,6 +334,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -315,6 +362,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -330,6 +381,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -350,6 +405,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -365,6 +424,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -395,6 +458,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -410,6 +477,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -436,6 +507,10 @@ where
             distribution,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -451,6 +526,10 @@ where
             distribution,
             ibc,
             gov,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -471,6 +550,10 @@ where
             distribution,
             ibc,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -486,6 +569,10 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
@@ -506,6 +593,10 @@ where
             distribution,
             ibc,
             gov,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
             ..
         } = self;
 
@@ -521,15 +612,96 @@ where
             ibc,
             gov,
             stargate,
+            account_generator,
+            auto_block_advance,
+            random_seed,
+            chain_profile,
         }
     }
 
     /// Overwrites the initial block.
+    ///
+    /// The chain identifier set by [with_chain_profile](Self::with_chain_profile)
+    /// is overwritten too, when this function is called after it.
     pub fn with_block(mut self, block: BlockInfo) -> Self {
         self.block = block;
         self
     }
 
+    /// Overwrites the default account generator used by [App::account_address].
+    pub fn with_account_generator<G: AccountGenerator + Send + Sync + 'static>(
+        mut self,
+        account_generator: G,
+    ) -> Self {
+        self.account_generator = Arc::new(account_generator);
+        self
+    }
+
+    /// Enables automatic block advancement: after every executed transaction
+    /// the block height and time are advanced according to specified parameters,
+    /// like on a real chain. By default, the block changes only when updated explicitly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{no_init, AppBuilder, BlockParams};
+    ///
+    /// let app = AppBuilder::default()
+    ///     .with_auto_block_advance(BlockParams {
+    ///         seconds_per_block: 6,
+    ///         blocks_per_tx: 1,
+    ///     })
+    ///     .build(no_init);
+    /// ```
+    pub fn with_auto_block_advance(mut self, params: BlockParams) -> Self {
+        self.auto_block_advance = Some(params);
+        self
+    }
+
+    /// Sets the seed of deterministic per-block randomness. Contracts can query
+    /// the randomness of the current block with [RANDOMNESS_QUERY_PATH](crate::RANDOMNESS_QUERY_PATH),
+    /// when [StargateKeeper](crate::StargateKeeper) is used, and tests can read it with
+    /// [App::block_randomness](crate::App::block_randomness). A new value is derived for every block.
+    pub fn with_random_seed(mut self, seed: impl Into<Binary>) -> Self {
+        self.random_seed = Some(seed.into());
+        self
+    }
+
+    /// Configures the application for the chain described by specified profile.
+    ///
+    /// Replaces the api with [MockApi] using the profile's Bech32 prefix,
+    /// sets the chain identifier of the initial block and the bonded denomination
+    /// of the staking module, so all of them stay consistent. The bonded denomination
+    /// is passed to [Staking::setup_staking_info] of the configured staking module,
+    /// so custom staking modules should be set before building the application.
+    ///
+    /// The chain identifier of the initial block is set immediately, so calling
+    /// [with_block](Self::with_block) after this function overwrites it,
+    /// while calling it before keeps the block with the chain identifier from the profile.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{no_init, AppBuilder, ChainProfile};
+    ///
+    /// let app = AppBuilder::default()
+    ///     .with_chain_profile(ChainProfile::osmosis())
+    ///     .build(no_init);
+    ///
+    /// assert_eq!("osmosis-1", app.block_info().chain_id);
+    /// assert!(app.api().addr_make("user").as_str().starts_with("osmo1"));
+    /// ```
+    pub fn with_chain_profile(
+        self,
+        profile: ChainProfile,
+    ) -> AppBuilder<BankT, MockApi, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    {
+        let mut builder = self.with_api(profile.api());
+        builder.block.chain_id = profile.chain_id.to_string();
+        builder.chain_profile = Some(profile);
+        builder
+    }
+
     /// Builds final `App`. At this point all components type have to be properly related to each
     /// other. If there are some generics related compilation errors, make sure that all components
     /// are properly relating to each other.
@@ -570,7 +742,29 @@ where
             api: self.api,
             block: self.block,
             storage: self.storage,
+            transactions: Default::default(),
+            tx_index: 0,
+            block_gas_limit: None,
+            block_gas_used: 0,
+            query_handle: None,
+            auto_block_advance: self.auto_block_advance,
+            end_blockers: vec![],
+            end_block_results: vec![],
+            last_storage_writes: vec![],
+            account_generator: self.account_generator,
+            accounts: Default::default(),
+            chain_upgrades: Default::default(),
+            event_log: Default::default(),
         };
+        if let Some(seed) = self.random_seed {
+            randomness::save_seed(&mut app.storage, &seed).unwrap();
+        }
+        if let Some(profile) = self.chain_profile {
+            app.router
+                .staking
+                .setup_staking_info(&mut app.storage, profile.staking_info())
+                .unwrap();
+        }
         app.init_modules(init_fn);
         app
     }
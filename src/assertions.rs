@@ -0,0 +1,154 @@
+//! # Helpers for assertions in tests
+
+use cosmwasm_std::{Order, Storage};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Single difference between two compared storages.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageDiff {
+    /// The key exists only in the left storage.
+    OnlyLeft {
+        /// The key.
+        key: Vec<u8>,
+        /// Value stored under the key in the left storage.
+        value: Vec<u8>,
+    },
+    /// The key exists only in the right storage.
+    OnlyRight {
+        /// The key.
+        key: Vec<u8>,
+        /// Value stored under the key in the right storage.
+        value: Vec<u8>,
+    },
+    /// The key exists in both storages, but values differ.
+    Changed {
+        /// The key.
+        key: Vec<u8>,
+        /// Value stored under the key in the left storage.
+        left: Vec<u8>,
+        /// Value stored under the key in the right storage.
+        right: Vec<u8>,
+    },
+}
+
+impl StorageDiff {
+    /// Returns the key this difference refers to.
+    pub fn key(&self) -> &[u8] {
+        match self {
+            Self::OnlyLeft { key, .. }
+            | Self::OnlyRight { key, .. }
+            | Self::Changed { key, .. } => key,
+        }
+    }
+}
+
+impl fmt::Display for StorageDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OnlyLeft { key, value } => {
+                write!(f, "- {} = {}", key.escape_ascii(), value.escape_ascii())
+            }
+            Self::OnlyRight { key, value } => {
+                write!(f, "+ {} = {}", key.escape_ascii(), value.escape_ascii())
+            }
+            Self::Changed { key, left, right } => write!(
+                f,
+                "~ {} = {} -> {}",
+                key.escape_ascii(),
+                left.escape_ascii(),
+                right.escape_ascii()
+            ),
+        }
+    }
+}
+
+/// Returns a key-wise difference between two storages, sorted by key.
+///
+/// Only keys for which the `key_filter` returns `true` are compared.
+pub fn storage_diff<F>(left: &dyn Storage, right: &dyn Storage, key_filter: F) -> Vec<StorageDiff>
+where
+    F: Fn(&[u8]) -> bool,
+{
+    let collect = |storage: &dyn Storage| -> BTreeMap<Vec<u8>, Vec<u8>> {
+        storage
+            .range(None, None, Order::Ascending)
+            .filter(|(key, _)| key_filter(key))
+            .collect()
+    };
+    let left = collect(left);
+    let mut right = collect(right);
+    let mut diffs = vec![];
+    for (key, value) in left {
+        match right.remove(&key) {
+            None => diffs.push(StorageDiff::OnlyLeft { key, value }),
+            Some(other) if other != value => diffs.push(StorageDiff::Changed {
+                key,
+                left: value,
+                right: other,
+            }),
+            Some(_) => {}
+        }
+    }
+    diffs.extend(
+        right
+            .into_iter()
+            .map(|(key, value)| StorageDiff::OnlyRight { key, value }),
+    );
+    diffs.sort_by(|a, b| a.key().cmp(b.key()));
+    diffs
+}
+
+/// Asserts that two storages hold exactly the same key-value pairs.
+///
+/// Usually used to compare the storage of two contracts, possibly living in different
+/// applications, e.g. to verify migration correctness or that a contract replayed
+/// the same way as its counterpart. Panics with a list of all differences.
+///
+/// # Example
+///
+/// ```
+/// use cosmwasm_std::Storage;
+/// use cw_multi_test::{assert_same_storage, App};
+///
+/// let mut app_a = App::default();
+/// let mut app_b = App::default();
+///
+/// app_a.storage_mut().set(b"key", b"value");
+/// app_b.storage_mut().set(b"key", b"value");
+///
+/// assert_same_storage(app_a.storage(), app_b.storage());
+/// ```
+///
+/// To compare the storage of contracts, use [App::contract_storage](crate::App::contract_storage):
+///
+/// ```ignore
+/// assert_same_storage(
+///     &*app_a.contract_storage(&addr_a),
+///     &*app_b.contract_storage(&addr_b),
+/// );
+/// ```
+#[track_caller]
+pub fn assert_same_storage(left: &dyn Storage, right: &dyn Storage) {
+    assert_same_storage_filtered(left, right, |_| true)
+}
+
+/// Asserts that two storages hold the same key-value pairs,
+/// comparing only keys for which the `key_filter` returns `true`.
+///
+/// See [assert_same_storage] for details.
+#[track_caller]
+pub fn assert_same_storage_filtered<F>(left: &dyn Storage, right: &dyn Storage, key_filter: F)
+where
+    F: Fn(&[u8]) -> bool,
+{
+    let diffs = storage_diff(left, right, key_filter);
+    if !diffs.is_empty() {
+        let lines = diffs
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        panic!("storages differ ({} keys):\n{}", diffs.len(), lines);
+    }
+}
@@ -5,13 +5,14 @@ use crate::module::Module;
 use crate::prefixed_storage::{prefixed, prefixed_read};
 use cosmwasm_std::{
     coin, to_json_binary, Addr, AllBalanceResponse, AllDenomMetadataResponse, Api, BalanceResponse,
-    BankMsg, BankQuery, Binary, BlockInfo, Coin, DenomMetadata, DenomMetadataResponse, Event,
-    Order, Querier, StdResult, Storage, SupplyResponse, Uint128,
+    BankMsg, BankQuery, Binary, BlockInfo, Coin, Coins, DenomMetadata, DenomMetadataResponse,
+    Event, Order, PageRequest, Querier, StdResult, Storage, SupplyResponse, Uint128,
 };
-use cw_storage_plus::Map;
+use cw_storage_plus::{Bound, Map};
 use cw_utils::NativeBalance;
 use itertools::Itertools;
 use schemars::JsonSchema;
+use std::collections::{BTreeMap, BTreeSet};
 
 /// Collection of bank balances.
 const BALANCES: Map<&Addr, NativeBalance> = Map::new("balances");
@@ -32,6 +33,70 @@ pub enum BankSudo {
         /// Amount of the minted tokens.
         amount: Vec<Coin>,
     },
+    /// Privileged action burning tokens of any account.
+    Burn {
+        /// Address of the account the tokens will be burned from.
+        from_address: String,
+        /// Amount of the burned tokens.
+        amount: Vec<Coin>,
+    },
+    /// Privileged action minting tokens for many accounts at once,
+    /// e.g. to set up initial balances in large tests.
+    MintMany {
+        /// Destination addresses with the amounts of tokens minted for them.
+        mints: Vec<(String, Vec<Coin>)>,
+    },
+    /// Privileged action setting the metadata of a denomination.
+    SetDenomMetadata {
+        /// Denomination the metadata is set for.
+        denom: String,
+        /// Metadata of the denomination.
+        metadata: DenomMetadata,
+    },
+}
+
+/// Returns a page of the total supply of all denominations held in the bank storage,
+/// see [BankKeeper::total_supply] for details.
+pub(crate) fn total_supply(
+    storage: &dyn Storage,
+    pagination: Option<PageRequest>,
+) -> AnyResult<(Vec<Coin>, Option<Binary>)> {
+    let PageRequest {
+        key,
+        limit,
+        reverse,
+    } = pagination.unwrap_or(PageRequest {
+        key: None,
+        limit: 0,
+        reverse: false,
+    });
+    let bank_storage = prefixed_read(storage, NAMESPACE_BANK);
+    let mut supply = BTreeMap::<String, Uint128>::new();
+    for item in BALANCES.range(&bank_storage, None, None, Order::Ascending) {
+        for coin in item?.1.into_vec() {
+            *supply.entry(coin.denom).or_default() += coin.amount;
+        }
+    }
+    let start = key.map(|key| String::from_utf8(key.to_vec())).transpose()?;
+    let coins: Box<dyn Iterator<Item = (String, Uint128)>> = match (start, reverse) {
+        (None, false) => Box::new(supply.into_iter()),
+        (None, true) => Box::new(supply.into_iter().rev()),
+        (Some(start), false) => Box::new(supply.into_iter().filter(move |(d, _)| *d >= start)),
+        (Some(start), true) => Box::new(supply.into_iter().rev().filter(move |(d, _)| *d <= start)),
+    };
+    let limit = if limit == 0 {
+        usize::MAX
+    } else {
+        limit as usize
+    };
+    let mut page = vec![];
+    for (denom, amount) in coins {
+        if page.len() == limit {
+            return Ok((page, Some(Binary::from(denom.as_bytes()))));
+        }
+        page.push(Coin::new(amount, denom));
+    }
+    Ok((page, None))
 }
 
 /// This trait defines the interface for simulating banking operations.
@@ -46,8 +111,26 @@ pub trait Bank: Module<ExecT = BankMsg, QueryT = BankQuery, SudoT = BankSudo> {}
 /// Manages financial interactions in CosmWasm tests, such as simulating token transactions
 /// and account balances. This is particularly important for contracts that deal with financial
 /// operations in the Cosmos ecosystem.
+///
+/// Balances are always stored normalized: coins with zero amount are removed,
+/// coins with the same denomination are merged, and the remaining coins are sorted
+/// by denomination. This means that all balances returned from bank queries
+/// (like [BankQuery::AllBalances]) are sorted by denomination, so they can be
+/// safely compared in assertions regardless of the order in which the coins were added.
 #[derive(Default)]
-pub struct BankKeeper {}
+pub struct BankKeeper {
+    /// Flag indicating if missing funds should be minted for the sender instead of failing.
+    auto_funding: bool,
+    /// Addresses that are not allowed to receive funds, like module accounts.
+    blocked_addresses: BTreeSet<String>,
+    /// Restrictions applied to sending tokens, keyed by denomination.
+    send_restrictions: BTreeMap<String, SendRestriction>,
+}
+
+/// Restriction applied to sending tokens of a single denomination. It receives the sender,
+/// the recipient and the sent coin, and returns the address that finally receives the coin.
+/// Returning an error rejects the whole transfer.
+type SendRestriction = Box<dyn Fn(&Addr, &Addr, &Coin) -> AnyResult<Addr>>;
 
 impl BankKeeper {
     /// Creates a new instance of a bank keeper with default settings.
@@ -55,7 +138,90 @@ impl BankKeeper {
         Self::default()
     }
 
+    /// Enables or disables automatic funding of senders in an existing [BankKeeper].
+    ///
+    /// By default, sending tokens from an account with insufficient balance fails (strict mode).
+    /// When automatic funding is enabled, the missing amount is minted for the sender
+    /// before the transfer, and an `auto_funding` event is emitted as a warning.
+    /// This is a test convenience that speeds up exploratory tests, it does not reflect
+    /// the behavior of a real blockchain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{AppBuilder, BankKeeper, no_init};
+    ///
+    /// let bank_keeper = BankKeeper::new().with_auto_funding(true);
+    ///
+    /// let mut app = AppBuilder::default().with_bank(bank_keeper).build(no_init);
+    /// ```
+    pub fn with_auto_funding(mut self, auto_funding: bool) -> Self {
+        self.auto_funding = auto_funding;
+        self
+    }
+
+    /// Blocks receiving funds by specified addresses, like Cosmos SDK does
+    /// for module accounts (e.g. `distribution`). Sending tokens to blocked addresses
+    /// with [BankMsg::Send] fails with the same error as on a real chain.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{coins, BankMsg};
+    /// use cw_multi_test::{AppBuilder, BankKeeper, Executor, no_init};
+    ///
+    /// let mut app = AppBuilder::default().build(no_init);
+    /// let distribution = app.module_address("distribution").unwrap();
+    ///
+    /// let bank_keeper = BankKeeper::new().with_blocked_addresses([distribution.clone()]);
+    /// let mut app = AppBuilder::default().with_bank(bank_keeper).build(no_init);
+    /// let owner = app.api().addr_make("owner");
+    /// app.init_balances(&[(owner.clone(), coins(10, "uatom"))]).unwrap();
+    ///
+    /// let msg = BankMsg::Send { to_address: distribution.to_string(), amount: coins(10, "uatom") };
+    /// assert!(app.execute(owner, msg.into()).is_err());
+    /// ```
+    pub fn with_blocked_addresses(mut self, addresses: impl IntoIterator<Item = Addr>) -> Self {
+        self.blocked_addresses
+            .extend(addresses.into_iter().map(String::from));
+        self
+    }
+
+    /// Registers a restriction applied to every sent coin of specified denomination,
+    /// like the before-send hooks of token factory denominations.
+    /// The restriction may reject the transfer by returning an error,
+    /// or redirect the coin by returning other recipient address.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{coins, BankMsg};
+    /// use cw_multi_test::{AppBuilder, BankKeeper, Executor, no_init};
+    ///
+    /// let bank_keeper = BankKeeper::new().with_send_restriction("frozen", |_, _, _| {
+    ///     anyhow::bail!("denom frozen is not transferable")
+    /// });
+    /// let mut app = AppBuilder::default().with_bank(bank_keeper).build(no_init);
+    /// let owner = app.api().addr_make("owner");
+    /// let receiver = app.api().addr_make("receiver");
+    /// app.init_balances(&[(owner.clone(), coins(10, "frozen"))]).unwrap();
+    ///
+    /// let msg = BankMsg::Send { to_address: receiver.to_string(), amount: coins(10, "frozen") };
+    /// assert!(app.execute(owner, msg.into()).is_err());
+    /// ```
+    pub fn with_send_restriction<F>(mut self, denom: &str, restriction: F) -> Self
+    where
+        F: Fn(&Addr, &Addr, &Coin) -> AnyResult<Addr> + 'static,
+    {
+        self.send_restrictions
+            .insert(denom.to_string(), Box::new(restriction));
+        self
+    }
+
     /// Administration function for adjusting bank accounts in genesis.
+    ///
+    /// The provided amount is normalized before being stored,
+    /// see [BankKeeper] for details.
     pub fn init_balance(
         &self,
         storage: &mut dyn Storage,
@@ -66,6 +232,23 @@ impl BankKeeper {
         self.set_balance(&mut bank_storage, account, amount)
     }
 
+    /// Administration function for adjusting bank accounts in genesis
+    /// using [Coins] instead of a vector of [Coin].
+    pub fn init_balance_coins(
+        &self,
+        storage: &mut dyn Storage,
+        account: &Addr,
+        amount: Coins,
+    ) -> AnyResult<()> {
+        self.init_balance(storage, account, amount.into_vec())
+    }
+
+    /// Returns the balance of specified account as [Coins], sorted by denomination.
+    pub fn balance(&self, storage: &dyn Storage, account: &Addr) -> AnyResult<Coins> {
+        let bank_storage = prefixed_read(storage, NAMESPACE_BANK);
+        Ok(Coins::try_from(self.get_balance(&bank_storage, account)?)?)
+    }
+
     /// Administration function for adjusting bank accounts.
     fn set_balance(
         &self,
@@ -92,6 +275,62 @@ impl BankKeeper {
             .map_err(Into::into)
     }
 
+    /// Returns a page of denomination metadata, sorted by denomination,
+    /// and the key of the next page, if there are more items.
+    ///
+    /// The key of the page is the denomination of its first item,
+    /// the limit equal to zero means no limit.
+    fn get_all_denom_metadata(
+        &self,
+        storage: &dyn Storage,
+        pagination: Option<PageRequest>,
+    ) -> AnyResult<(Vec<DenomMetadata>, Option<Binary>)> {
+        let PageRequest {
+            key,
+            limit,
+            reverse,
+        } = pagination.unwrap_or(PageRequest {
+            key: None,
+            limit: 0,
+            reverse: false,
+        });
+        let bound = key
+            .map(|key| String::from_utf8(key.to_vec()).map(Bound::inclusive))
+            .transpose()?;
+        let items = if reverse {
+            DENOM_METADATA.range(storage, None, bound, Order::Descending)
+        } else {
+            DENOM_METADATA.range(storage, bound, None, Order::Ascending)
+        };
+        let limit = if limit == 0 {
+            usize::MAX
+        } else {
+            limit as usize
+        };
+        let mut metadata = vec![];
+        for item in items {
+            let (denom, meta) = item?;
+            if metadata.len() == limit {
+                return Ok((metadata, Some(Binary::from(denom.as_bytes()))));
+            }
+            metadata.push(meta);
+        }
+        Ok((metadata, None))
+    }
+
+    /// Returns a page of the total supply of all denominations, sorted by denomination,
+    /// and the key of the next page, if there are more items.
+    ///
+    /// The key of the page is the denomination of its first item,
+    /// the limit equal to zero means no limit.
+    pub fn total_supply(
+        &self,
+        storage: &dyn Storage,
+        pagination: Option<PageRequest>,
+    ) -> AnyResult<(Vec<Coin>, Option<Binary>)> {
+        total_supply(storage, pagination)
+    }
+
     /// Returns balance for specified address.
     fn get_balance(&self, bank_storage: &dyn Storage, addr: &Addr) -> AnyResult<Vec<Coin>> {
         let val = BALANCES.may_load(bank_storage, addr)?;
@@ -116,6 +355,39 @@ impl BankKeeper {
         Ok(coin(supply.into(), denom))
     }
 
+    /// Fails when specified address is not allowed to receive funds.
+    fn ensure_not_blocked(&self, address: &str) -> AnyResult<()> {
+        if self.blocked_addresses.contains(address) {
+            bail!("{} is not allowed to receive funds: unauthorized", address);
+        }
+        Ok(())
+    }
+
+    /// Applies send restrictions to the transferred coins and returns
+    /// the final recipients together with coins they receive.
+    fn apply_send_restrictions(
+        &self,
+        sender: &Addr,
+        to_address: Addr,
+        amount: Vec<Coin>,
+    ) -> AnyResult<Vec<(Addr, Vec<Coin>)>> {
+        if self.send_restrictions.is_empty() || amount.is_empty() {
+            return Ok(vec![(to_address, amount)]);
+        }
+        let mut transfers: Vec<(Addr, Vec<Coin>)> = vec![];
+        for coin in amount {
+            let recipient = match self.send_restrictions.get(&coin.denom) {
+                Some(restriction) => restriction(sender, &to_address, &coin)?,
+                None => to_address.clone(),
+            };
+            match transfers.iter_mut().find(|(addr, _)| *addr == recipient) {
+                Some((_, coins)) => coins.push(coin),
+                None => transfers.push((recipient, vec![coin])),
+            }
+        }
+        Ok(transfers)
+    }
+
     fn send(
         &self,
         bank_storage: &mut dyn Storage,
@@ -151,6 +423,32 @@ impl BankKeeper {
         self.set_balance(bank_storage, &from_address, a.into_vec())
     }
 
+    /// Mints the part of the amount that exceeds the balance of specified account.
+    /// Returns minted coins, the returned vector is empty when the balance is sufficient.
+    fn fund_shortfall(
+        &self,
+        bank_storage: &mut dyn Storage,
+        account: &Addr,
+        amount: &[Coin],
+    ) -> AnyResult<Vec<Coin>> {
+        let balance = self.get_balance(bank_storage, account)?;
+        let shortfall: Vec<Coin> = amount
+            .iter()
+            .filter_map(|c| {
+                let available = balance
+                    .iter()
+                    .find(|b| b.denom == c.denom)
+                    .map(|b| b.amount)
+                    .unwrap_or_default();
+                (c.amount > available).then(|| coin((c.amount - available).u128(), &c.denom))
+            })
+            .collect();
+        if !shortfall.is_empty() {
+            self.mint(bank_storage, account.clone(), shortfall.clone())?;
+        }
+        Ok(shortfall)
+    }
+
     /// Filters out all `0` value coins and returns an error if the resulting vector is empty.
     fn normalize_amount(&self, amount: Vec<Coin>) -> AnyResult<Vec<Coin>> {
         let res: Vec<_> = amount.into_iter().filter(|x| !x.amount.is_zero()).collect();
@@ -188,18 +486,39 @@ impl Module for BankKeeper {
         let mut bank_storage = prefixed(storage, NAMESPACE_BANK);
         match msg {
             BankMsg::Send { to_address, amount } => {
-                // see https://github.com/cosmos/cosmos-sdk/blob/v0.42.7/x/bank/keeper/send.go#L142-L147
-                let events = vec![Event::new("transfer")
-                    .add_attribute("recipient", &to_address)
-                    .add_attribute("sender", &sender)
-                    .add_attribute("amount", coins_to_string(&amount))];
-                self.send(
-                    &mut bank_storage,
-                    sender,
-                    Addr::unchecked(to_address),
-                    amount,
-                )?;
-                Ok(AppResponse { events, data: None })
+                // see https://github.com/cosmos/cosmos-sdk/blob/v0.50.6/x/bank/keeper/msg_server.go#L56-L58
+                self.ensure_not_blocked(&to_address)?;
+                let mut events = vec![];
+                if self.auto_funding {
+                    let minted = self.fund_shortfall(&mut bank_storage, &sender, &amount)?;
+                    if !minted.is_empty() {
+                        events.push(
+                            Event::new("auto_funding")
+                                .add_attribute("account", &sender)
+                                .add_attribute("amount", coins_to_string(&minted)),
+                        );
+                    }
+                }
+                let transfers =
+                    self.apply_send_restrictions(&sender, Addr::unchecked(to_address), amount)?;
+                // send restrictions may redirect funds to a blocked address
+                for (recipient, _) in &transfers {
+                    self.ensure_not_blocked(recipient.as_str())?;
+                }
+                for (recipient, amount) in transfers {
+                    // see https://github.com/cosmos/cosmos-sdk/blob/v0.42.7/x/bank/keeper/send.go#L142-L147
+                    events.push(
+                        Event::new("transfer")
+                            .add_attribute("recipient", &recipient)
+                            .add_attribute("sender", &sender)
+                            .add_attribute("amount", coins_to_string(&amount)),
+                    );
+                    self.send(&mut bank_storage, sender.clone(), recipient, amount)?;
+                }
+                Ok(AppResponse {
+                    events,
+                    ..Default::default()
+                })
             }
             BankMsg::Burn { amount } => {
                 // burn doesn't seem to emit any events
@@ -246,12 +565,9 @@ impl Module for BankKeeper {
                 let res = DenomMetadataResponse::new(meta);
                 to_json_binary(&res).map_err(Into::into)
             }
-            BankQuery::AllDenomMetadata { pagination: _ } => {
-                let mut metadata = vec![];
-                for key in DENOM_METADATA.keys(storage, None, None, Order::Ascending) {
-                    metadata.push(DENOM_METADATA.may_load(storage, key?)?.unwrap_or_default());
-                }
-                let res = AllDenomMetadataResponse::new(metadata, None);
+            BankQuery::AllDenomMetadata { pagination } => {
+                let (metadata, next_key) = self.get_all_denom_metadata(storage, pagination)?;
+                let res = AllDenomMetadataResponse::new(metadata, next_key);
                 to_json_binary(&res).map_err(Into::into)
             }
             other => unimplemented!("bank query: {other:?}"),
@@ -273,6 +589,25 @@ impl Module for BankKeeper {
                 self.mint(&mut bank_storage, to_address, amount)?;
                 Ok(AppResponse::default())
             }
+            BankSudo::Burn {
+                from_address,
+                amount,
+            } => {
+                let from_address = api.addr_validate(&from_address)?;
+                self.burn(&mut bank_storage, from_address, amount)?;
+                Ok(AppResponse::default())
+            }
+            BankSudo::MintMany { mints } => {
+                for (to_address, amount) in mints {
+                    let to_address = api.addr_validate(&to_address)?;
+                    self.mint(&mut bank_storage, to_address, amount)?;
+                }
+                Ok(AppResponse::default())
+            }
+            BankSudo::SetDenomMetadata { denom, metadata } => {
+                self.set_denom_metadata(storage, denom, metadata)?;
+                Ok(AppResponse::default())
+            }
         }
     }
 }
@@ -450,6 +785,140 @@ mod test {
         assert_eq!(vec![coin(15, "btc"), coin(70, "eth")], rich);
     }
 
+    #[test]
+    fn balances_are_sorted_by_denom() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let bank = BankKeeper::new();
+
+        let owner = api.addr_make("owner");
+        let rcpt = api.addr_make("receiver");
+        bank.init_balance(
+            &mut store,
+            &owner,
+            vec![
+                coin(5, "zeta"),
+                coin(0, "beta"),
+                coin(7, "alpha"),
+                coin(3, "zeta"),
+            ],
+        )
+        .unwrap();
+        assert_eq!(
+            vec![coin(7, "alpha"), coin(8, "zeta")],
+            query_balance(&bank, &api, &store, &owner)
+        );
+
+        // receiving coins in reverse order keeps the balance sorted
+        let block = mock_env().block;
+        let router = MockRouter::default();
+        let msg = BankMsg::Send {
+            to_address: rcpt.clone().into(),
+            amount: vec![coin(2, "zeta"), coin(1, "alpha")],
+        };
+        bank.execute(&api, &mut store, &router, &block, owner, msg)
+            .unwrap();
+        assert_eq!(
+            vec![coin(1, "alpha"), coin(2, "zeta")],
+            query_balance(&bank, &api, &store, &rcpt)
+        );
+    }
+
+    #[test]
+    fn init_and_read_balance_as_coins() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let bank = BankKeeper::new();
+
+        let owner = api.addr_make("owner");
+        let mut amount = Coins::default();
+        amount.add(coin(10, "eth")).unwrap();
+        amount.add(coin(20, "btc")).unwrap();
+        amount.add(coin(5, "eth")).unwrap();
+        bank.init_balance_coins(&mut store, &owner, amount).unwrap();
+
+        let balance = bank.balance(&store, &owner).unwrap();
+        assert_eq!(vec![coin(20, "btc"), coin(15, "eth")], balance.to_vec());
+        assert_eq!(15, balance.amount_of("eth").u128());
+        assert!(bank
+            .balance(&store, &api.addr_make("nobody"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn send_coins_with_auto_funding() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let router = MockRouter::default();
+
+        let owner = api.addr_make("owner");
+        let rcpt = api.addr_make("receiver");
+        bank_init_balance_helper(&mut store, &owner, vec![coin(20, "btc")]);
+
+        // sending more than owned mints only the missing amount
+        let bank = BankKeeper::new().with_auto_funding(true);
+        let msg = BankMsg::Send {
+            to_address: rcpt.clone().into(),
+            amount: vec![coin(30, "btc"), coin(5, "eth")],
+        };
+        let res = bank
+            .execute(&api, &mut store, &router, &block, owner.clone(), msg)
+            .unwrap();
+        assert_eq!(2, res.events.len());
+        assert_eq!("auto_funding", res.events[0].ty);
+        assert_eq!("10btc,5eth", res.events[0].attributes[1].value);
+        assert_eq!("transfer", res.events[1].ty);
+        assert_eq!(
+            Vec::<Coin>::new(),
+            query_balance(&bank, &api, &store, &owner)
+        );
+        assert_eq!(
+            vec![coin(30, "btc"), coin(5, "eth")],
+            query_balance(&bank, &api, &store, &rcpt)
+        );
+
+        // no warning event when the balance is sufficient
+        let msg = BankMsg::Send {
+            to_address: owner.into(),
+            amount: coins(5, "btc"),
+        };
+        let res = bank
+            .execute(&api, &mut store, &router, &block, rcpt, msg)
+            .unwrap();
+        assert_eq!(1, res.events.len());
+        assert_eq!("transfer", res.events[0].ty);
+    }
+
+    #[test]
+    fn send_coins_without_auto_funding_fails() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let router = MockRouter::default();
+
+        let owner = api.addr_make("owner");
+        let rcpt = api.addr_make("receiver");
+        bank_init_balance_helper(&mut store, &owner, vec![coin(20, "btc")]);
+
+        let bank = BankKeeper::new().with_auto_funding(false);
+        let msg = BankMsg::Send {
+            to_address: rcpt.into(),
+            amount: coins(30, "btc"),
+        };
+        let err = bank
+            .execute(&api, &mut store, &router, &block, owner, msg)
+            .unwrap_err();
+        assert!(matches!(err.downcast().unwrap(), StdError::Overflow { .. }));
+    }
+
+    fn bank_init_balance_helper(store: &mut MockStorage, account: &Addr, amount: Vec<Coin>) {
+        BankKeeper::new()
+            .init_balance(store, account, amount)
+            .unwrap();
+    }
+
     #[test]
     fn burn_coins() {
         let api = MockApi::default();
@@ -495,6 +964,37 @@ mod test {
         assert!(matches!(err.downcast().unwrap(), StdError::Overflow { .. }));
     }
 
+    #[test]
+    fn burn_sudo_should_burn_coins_of_any_account() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let router = MockRouter::default();
+
+        let owner = api.addr_make("owner");
+        let bank = BankKeeper::new();
+        bank.init_balance(&mut store, &owner, coins(20, "btc"))
+            .unwrap();
+
+        let msg = BankSudo::Burn {
+            from_address: owner.to_string(),
+            amount: coins(5, "btc"),
+        };
+        bank.sudo(&api, &mut store, &router, &block, msg).unwrap();
+        let rich = query_balance(&bank, &api, &store, &owner);
+        assert_eq!(coins(15, "btc"), rich);
+
+        // cannot burn too much
+        let msg = BankSudo::Burn {
+            from_address: owner.to_string(),
+            amount: coins(20, "btc"),
+        };
+        let err = bank
+            .sudo(&api, &mut store, &router, &block, msg)
+            .unwrap_err();
+        assert!(matches!(err.downcast().unwrap(), StdError::Overflow { .. }));
+    }
+
     #[test]
     fn set_get_denom_metadata_should_work() {
         let api = MockApi::default();
@@ -559,6 +1059,142 @@ mod test {
         assert_eq!(res.metadata[1].name, denom_eth_name);
     }
 
+    #[test]
+    fn set_denom_metadata_via_sudo_should_work() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        let router = MockRouter::default();
+        let bank = BankKeeper::new();
+        let msg = BankSudo::SetDenomMetadata {
+            denom: "eth".to_string(),
+            metadata: DenomMetadata {
+                name: "Ether".to_string(),
+                ..Default::default()
+            },
+        };
+        bank.sudo(&api, &mut store, &router, &block, msg).unwrap();
+        let req = BankQuery::DenomMetadata {
+            denom: "eth".to_string(),
+        };
+        let raw = bank.query(&api, &store, &querier, &block, req).unwrap();
+        let res: DenomMetadataResponse = from_json(raw).unwrap();
+        assert_eq!(res.metadata.name, "Ether");
+    }
+
+    #[test]
+    fn paginate_all_denom_metadata_should_work() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        let bank = BankKeeper::new();
+        for denom in ["atom", "btc", "eth", "osmo", "ucosm"] {
+            bank.set_denom_metadata(
+                &mut store,
+                denom.to_string(),
+                DenomMetadata {
+                    name: denom.to_string(),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+        }
+        let query_page = |key: Option<Binary>, limit: u32, reverse: bool| {
+            let req = BankQuery::AllDenomMetadata {
+                pagination: Some(PageRequest {
+                    key,
+                    limit,
+                    reverse,
+                }),
+            };
+            let raw = bank.query(&api, &store, &querier, &block, req).unwrap();
+            let res: AllDenomMetadataResponse = from_json(raw).unwrap();
+            let names: Vec<String> = res.metadata.into_iter().map(|m| m.name).collect();
+            (names, res.next_key)
+        };
+
+        // iterate forward in pages of two items
+        let (names, next_key) = query_page(None, 2, false);
+        assert_eq!(names, ["atom", "btc"]);
+        assert_eq!(next_key, Some(Binary::from(b"eth")));
+        let (names, next_key) = query_page(next_key, 2, false);
+        assert_eq!(names, ["eth", "osmo"]);
+        let (names, next_key) = query_page(next_key, 2, false);
+        assert_eq!(names, ["ucosm"]);
+        assert_eq!(next_key, None);
+
+        // iterate backward
+        let (names, next_key) = query_page(None, 3, true);
+        assert_eq!(names, ["ucosm", "osmo", "eth"]);
+        let (names, next_key) = query_page(next_key, 3, true);
+        assert_eq!(names, ["btc", "atom"]);
+        assert_eq!(next_key, None);
+
+        // zero limit returns all items
+        let (names, next_key) = query_page(None, 0, false);
+        assert_eq!(5, names.len());
+        assert_eq!(next_key, None);
+    }
+
+    #[test]
+    fn paginate_total_supply_should_work() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let bank = BankKeeper::new();
+        let alice = api.addr_make("alice");
+        let bob = api.addr_make("bob");
+        bank.init_balance(
+            &mut store,
+            &alice,
+            vec![coin(10, "atom"), coin(20, "btc"), coin(30, "eth")],
+        )
+        .unwrap();
+        bank.init_balance(&mut store, &bob, vec![coin(5, "btc"), coin(1, "osmo")])
+            .unwrap();
+        let page = |key: Option<&[u8]>, limit: u32, reverse: bool| {
+            bank.total_supply(
+                &store,
+                Some(PageRequest {
+                    key: key.map(Binary::from),
+                    limit,
+                    reverse,
+                }),
+            )
+            .unwrap()
+        };
+
+        // supply of all denominations is summed over all accounts
+        let (supply, next_key) = bank.total_supply(&store, None).unwrap();
+        assert_eq!(
+            supply,
+            [
+                coin(10, "atom"),
+                coin(25, "btc"),
+                coin(30, "eth"),
+                coin(1, "osmo")
+            ]
+        );
+        assert_eq!(next_key, None);
+
+        // iterate forward in pages of three items
+        let (supply, next_key) = page(None, 3, false);
+        assert_eq!(supply, [coin(10, "atom"), coin(25, "btc"), coin(30, "eth")]);
+        assert_eq!(next_key, Some(Binary::from(b"osmo")));
+        let (supply, next_key) = page(Some(b"osmo"), 3, false);
+        assert_eq!(supply, [coin(1, "osmo")]);
+        assert_eq!(next_key, None);
+
+        // iterate backward
+        let (supply, next_key) = page(None, 2, true);
+        assert_eq!(supply, [coin(1, "osmo"), coin(30, "eth")]);
+        assert_eq!(next_key, Some(Binary::from(b"btc")));
+        let (supply, next_key) = page(Some(b"btc"), 2, true);
+        assert_eq!(supply, [coin(25, "btc"), coin(10, "atom")]);
+        assert_eq!(next_key, None);
+    }
+
     #[test]
     fn fail_on_zero_values() {
         let api = MockApi::default();
@@ -633,4 +1269,170 @@ mod test {
         bank.sudo(&api, &mut store, &router, &block, msg)
             .unwrap_err();
     }
+
+    #[test]
+    fn mint_many_via_sudo_should_work() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let router = MockRouter::default();
+
+        let alice = api.addr_make("alice");
+        let bob = api.addr_make("bob");
+        let bank = BankKeeper::new();
+
+        let msg = BankSudo::MintMany {
+            mints: vec![
+                (alice.to_string(), coins(100, "atom")),
+                (bob.to_string(), vec![coin(200, "atom"), coin(300, "eth")]),
+                (alice.to_string(), coins(50, "atom")),
+            ],
+        };
+        bank.sudo(&api, &mut store, &router, &block, msg).unwrap();
+        assert_eq!(
+            coins(150, "atom"),
+            bank.balance(&store, &alice).unwrap().into_vec()
+        );
+        assert_eq!(
+            vec![coin(200, "atom"), coin(300, "eth")],
+            bank.balance(&store, &bob).unwrap().into_vec()
+        );
+
+        // minting fails when any of the addresses is invalid
+        let msg = BankSudo::MintMany {
+            mints: vec![
+                (alice.to_string(), coins(100, "atom")),
+                ("invalid".to_string(), coins(100, "atom")),
+            ],
+        };
+        bank.sudo(&api, &mut store, &router, &block, msg)
+            .unwrap_err();
+    }
+
+    #[test]
+    fn send_to_blocked_address_should_fail() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let router = MockRouter::default();
+
+        let owner = api.addr_make("owner");
+        let rcpt = api.addr_make("recipient");
+        let blocked = api.addr_make("distribution");
+        let bank = BankKeeper::new().with_blocked_addresses([blocked.clone()]);
+        bank.init_balance(&mut store, &owner, coins(100, "atom"))
+            .unwrap();
+
+        let msg = BankMsg::Send {
+            to_address: blocked.to_string(),
+            amount: coins(10, "atom"),
+        };
+        let err = bank
+            .execute(&api, &mut store, &router, &block, owner.clone(), msg)
+            .unwrap_err();
+        assert_eq!(
+            format!("{} is not allowed to receive funds: unauthorized", blocked),
+            err.to_string()
+        );
+
+        // other addresses still can receive funds
+        let msg = BankMsg::Send {
+            to_address: rcpt.to_string(),
+            amount: coins(10, "atom"),
+        };
+        bank.execute(&api, &mut store, &router, &block, owner, msg)
+            .unwrap();
+        assert_eq!(
+            coins(10, "atom"),
+            bank.balance(&store, &rcpt).unwrap().into_vec()
+        );
+    }
+
+    #[test]
+    fn send_restrictions_should_work() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let router = MockRouter::default();
+
+        let owner = api.addr_make("owner");
+        let rcpt = api.addr_make("recipient");
+        let treasury = api.addr_make("treasury");
+        let bank = BankKeeper::new()
+            .with_send_restriction("frozen", |_, _, _| bail!("frozen"))
+            .with_send_restriction("taxed", {
+                let treasury = treasury.clone();
+                move |_, _, _| Ok(treasury.clone())
+            });
+        bank.init_balance(
+            &mut store,
+            &owner,
+            vec![coin(100, "atom"), coin(100, "frozen"), coin(100, "taxed")],
+        )
+        .unwrap();
+
+        // restricted denom can not be sent
+        let msg = BankMsg::Send {
+            to_address: rcpt.to_string(),
+            amount: vec![coin(10, "atom"), coin(10, "frozen")],
+        };
+        bank.execute(&api, &mut store, &router, &block, owner.clone(), msg)
+            .unwrap_err();
+
+        // redirected denom goes to other recipient
+        let msg = BankMsg::Send {
+            to_address: rcpt.to_string(),
+            amount: vec![coin(10, "atom"), coin(20, "taxed")],
+        };
+        let res = bank
+            .execute(&api, &mut store, &router, &block, owner, msg)
+            .unwrap();
+        assert_eq!(2, res.events.len());
+        assert_eq!(
+            coins(10, "atom"),
+            bank.balance(&store, &rcpt).unwrap().into_vec()
+        );
+        assert_eq!(
+            coins(20, "taxed"),
+            bank.balance(&store, &treasury).unwrap().into_vec()
+        );
+    }
+
+    #[test]
+    fn send_redirected_to_blocked_address_should_fail() {
+        let api = MockApi::default();
+        let mut store = MockStorage::new();
+        let block = mock_env().block;
+        let router = MockRouter::default();
+
+        let owner = api.addr_make("owner");
+        let rcpt = api.addr_make("recipient");
+        let blocked = api.addr_make("distribution");
+        let bank = BankKeeper::new()
+            .with_blocked_addresses([blocked.clone()])
+            .with_send_restriction("taxed", {
+                let blocked = blocked.clone();
+                move |_, _, _| Ok(blocked.clone())
+            });
+        bank.init_balance(
+            &mut store,
+            &owner,
+            vec![coin(100, "atom"), coin(100, "taxed")],
+        )
+        .unwrap();
+
+        let msg = BankMsg::Send {
+            to_address: rcpt.to_string(),
+            amount: vec![coin(10, "atom"), coin(20, "taxed")],
+        };
+        let err = bank
+            .execute(&api, &mut store, &router, &block, owner.clone(), msg)
+            .unwrap_err();
+        assert_eq!(
+            format!("{} is not allowed to receive funds: unauthorized", blocked),
+            err.to_string()
+        );
+        assert!(bank.balance(&store, &rcpt).unwrap().is_empty());
+        assert!(bank.balance(&store, &blocked).unwrap().is_empty());
+    }
 }
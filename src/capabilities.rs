@@ -0,0 +1,114 @@
+//! # Capabilities advertised by the simulated chain
+
+use cosmwasm_std::{Api, CanonicalAddr};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Path of the query returning capabilities of the chain, handled by the router
+/// for both `Stargate` and `Grpc` queries, so contracts under test can query it.
+///
+/// The request is an empty Protobuf message, the response is [QueryCapabilitiesResponse].
+pub const CAPABILITIES_QUERY_PATH: &str = "/cw_multi_test.capabilities.v1.Query/Capabilities";
+
+/// Request of the [CAPABILITIES_QUERY_PATH] query.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryCapabilitiesRequest {}
+
+/// Response of the [CAPABILITIES_QUERY_PATH] query, see [Capabilities] for details.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryCapabilitiesResponse {
+    /// CosmWasm capabilities supported by the chain.
+    #[prost(string, repeated, tag = "1")]
+    pub cosmwasm_features: Vec<String>,
+    /// Names of enabled modules.
+    #[prost(string, repeated, tag = "2")]
+    pub modules: Vec<String>,
+    /// Bech32 prefix of addresses.
+    #[prost(string, tag = "3")]
+    pub bech32_prefix: String,
+    /// Flag indicating if the IBC module accepts messages.
+    #[prost(bool, tag = "4")]
+    pub ibc_enabled: bool,
+}
+
+/// Capabilities advertised by the simulated chain.
+///
+/// Allows adaptive contracts and deployment tools to branch in tests
+/// the same way they would do on a live blockchain, see [App::capabilities](crate::App::capabilities)
+/// and [CAPABILITIES_QUERY_PATH].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Capabilities {
+    /// CosmWasm capabilities supported by the chain, like `iterator`, `staking` or `cosmwasm_2_0`,
+    /// derived from the enabled features of this crate.
+    pub cosmwasm_features: Vec<String>,
+    /// Names of enabled modules, like `bank`, `wasm` or `staking`.
+    /// Modules always failing, like [FailingModule](crate::FailingModule), are not listed.
+    pub modules: Vec<String>,
+    /// Bech32 prefix of addresses, empty when addresses are not Bech32 encoded.
+    pub bech32_prefix: String,
+    /// Flag indicating if the IBC module accepts messages.
+    pub ibc_enabled: bool,
+}
+
+impl Capabilities {
+    /// Creates capabilities of the chain using specified API and names of enabled modules.
+    pub(crate) fn new(api: &dyn Api, modules: Vec<String>) -> Self {
+        Self {
+            cosmwasm_features: cosmwasm_features(),
+            ibc_enabled: modules.iter().any(|module| module == "ibc"),
+            modules,
+            bech32_prefix: bech32_prefix(api),
+        }
+    }
+
+    /// Returns `true` when the chain supports specified CosmWasm capability.
+    pub fn has_feature(&self, feature: &str) -> bool {
+        self.cosmwasm_features.iter().any(|f| f == feature)
+    }
+
+    /// Returns `true` when the module with specified name is enabled.
+    pub fn has_module(&self, module: &str) -> bool {
+        self.modules.iter().any(|m| m == module)
+    }
+}
+
+impl From<Capabilities> for QueryCapabilitiesResponse {
+    fn from(capabilities: Capabilities) -> Self {
+        Self {
+            cosmwasm_features: capabilities.cosmwasm_features,
+            modules: capabilities.modules,
+            bech32_prefix: capabilities.bech32_prefix,
+            ibc_enabled: capabilities.ibc_enabled,
+        }
+    }
+}
+
+/// Returns CosmWasm capabilities enabled in `cosmwasm-std` for this build.
+fn cosmwasm_features() -> Vec<String> {
+    // always enabled in the `cosmwasm-std` dependency
+    let mut features = vec!["iterator", "staking", "stargate"];
+    features.extend(
+        [
+            (cfg!(feature = "cosmwasm_1_1"), "cosmwasm_1_1"),
+            (cfg!(feature = "cosmwasm_1_2"), "cosmwasm_1_2"),
+            (cfg!(feature = "cosmwasm_1_3"), "cosmwasm_1_3"),
+            (cfg!(feature = "cosmwasm_1_4"), "cosmwasm_1_4"),
+            (cfg!(feature = "cosmwasm_2_0"), "cosmwasm_2_0"),
+        ]
+        .into_iter()
+        .filter_map(|(enabled, feature)| enabled.then_some(feature)),
+    );
+    features.into_iter().map(String::from).collect()
+}
+
+/// Returns the Bech32 prefix of addresses generated by specified API.
+fn bech32_prefix(api: &dyn Api) -> String {
+    api.addr_humanize(&CanonicalAddr::from([0; 32]))
+        .ok()
+        .and_then(|addr| {
+            addr.as_str()
+                .rsplit_once('1')
+                .map(|(prefix, _)| prefix.to_string())
+        })
+        .unwrap_or_default()
+}
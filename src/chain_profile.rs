@@ -0,0 +1,72 @@
+//! # Profiles of well-known chains
+
+use crate::StakingInfo;
+use cosmwasm_std::testing::MockApi;
+
+/// Settings describing a chain, applied to all modules at once
+/// with [AppBuilder::with_chain_profile](crate::AppBuilder::with_chain_profile).
+///
+/// Built-in profiles are provided for some well-known chains,
+/// custom profiles can be created by filling the fields directly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChainProfile {
+    /// Identifier of the chain, used in the block info.
+    pub chain_id: &'static str,
+    /// Bech32 prefix of addresses.
+    pub bech32_prefix: &'static str,
+    /// Denomination of the staking token.
+    pub bonded_denom: &'static str,
+}
+
+impl ChainProfile {
+    /// Profile of the Cosmos Hub.
+    pub const fn cosmos_hub() -> Self {
+        Self::native("cosmoshub-4", "cosmos", "uatom")
+    }
+
+    /// Profile of Juno.
+    pub const fn juno() -> Self {
+        Self::native("juno-1", "juno", "ujuno")
+    }
+
+    /// Profile of Neutron.
+    pub const fn neutron() -> Self {
+        Self::native("neutron-1", "neutron", "untrn")
+    }
+
+    /// Profile of Osmosis.
+    pub const fn osmosis() -> Self {
+        Self::native("osmosis-1", "osmo", "uosmo")
+    }
+
+    /// Profile of Terra.
+    pub const fn terra() -> Self {
+        Self::native("phoenix-1", "terra", "uluna")
+    }
+
+    /// Creates a profile of the chain with specified native staking token.
+    const fn native(
+        chain_id: &'static str,
+        bech32_prefix: &'static str,
+        bonded_denom: &'static str,
+    ) -> Self {
+        Self {
+            chain_id,
+            bech32_prefix,
+            bonded_denom,
+        }
+    }
+
+    /// Returns [MockApi] generating addresses with the prefix of this chain.
+    pub fn api(&self) -> MockApi {
+        MockApi::default().with_prefix(self.bech32_prefix)
+    }
+
+    /// Returns default staking settings using the bonded denomination of this chain.
+    pub fn staking_info(&self) -> StakingInfo {
+        StakingInfo {
+            bonded_denom: self.bonded_denom.to_string(),
+            ..Default::default()
+        }
+    }
+}
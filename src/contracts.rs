@@ -2,8 +2,10 @@
 
 use crate::error::{anyhow, bail, AnyError, AnyResult};
 use cosmwasm_std::{
-    from_json, Binary, CosmosMsg, CustomMsg, CustomQuery, Deps, DepsMut, Empty, Env, MessageInfo,
-    QuerierWrapper, Reply, Response, SubMsg,
+    from_json, Binary, Checksum, CosmosMsg, CustomMsg, CustomQuery, Deps, DepsMut, Empty, Env,
+    IbcBasicResponse, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcChannelOpenResponse, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
+    IbcReceiveResponse, MessageInfo, QuerierWrapper, Reply, Response, SubMsg,
 };
 use serde::de::DeserializeOwned;
 use std::fmt::{Debug, Display};
@@ -33,6 +35,46 @@ where
 
     /// Evaluates contract's `migrate` entry-point.
     fn migrate(&self, deps: DepsMut<Q>, env: Env, msg: Vec<u8>) -> AnyResult<Response<C>>;
+
+    /// Returns the checksum of contract's wasm code, when known.
+    /// When `None`, the checksum is generated by the [ChecksumGenerator](crate::ChecksumGenerator)
+    /// while storing the contract's code. The default implementation returns `None`.
+    fn checksum(&self) -> Option<Checksum> { None }
+
+    /// Returns `true` when the contract exports IBC entry-points.
+    /// Such contracts get an IBC port assigned, reported in the contract info.
+    /// The default implementation returns `false`.
+    fn has_ibc_entry_points(&self) -> bool { false }
+
+    /// Evaluates contract's `ibc_channel_open` entry-point.
+    fn ibc_channel_open(&self, _deps: DepsMut<Q>, _env: Env, _msg: IbcChannelOpenMsg) -> AnyResult<IbcChannelOpenResponse> {
+        bail!("ibc_channel_open is not implemented for contract")
+    }
+
+    /// Evaluates contract's `ibc_channel_connect` entry-point.
+    fn ibc_channel_connect(&self, _deps: DepsMut<Q>, _env: Env, _msg: IbcChannelConnectMsg) -> AnyResult<IbcBasicResponse<C>> {
+        bail!("ibc_channel_connect is not implemented for contract")
+    }
+
+    /// Evaluates contract's `ibc_channel_close` entry-point.
+    fn ibc_channel_close(&self, _deps: DepsMut<Q>, _env: Env, _msg: IbcChannelCloseMsg) -> AnyResult<IbcBasicResponse<C>> {
+        bail!("ibc_channel_close is not implemented for contract")
+    }
+
+    /// Evaluates contract's `ibc_packet_receive` entry-point.
+    fn ibc_packet_receive(&self, _deps: DepsMut<Q>, _env: Env, _msg: IbcPacketReceiveMsg) -> AnyResult<IbcReceiveResponse<C>> {
+        bail!("ibc_packet_receive is not implemented for contract")
+    }
+
+    /// Evaluates contract's `ibc_packet_ack` entry-point.
+    fn ibc_packet_ack(&self, _deps: DepsMut<Q>, _env: Env, _msg: IbcPacketAckMsg) -> AnyResult<IbcBasicResponse<C>> {
+        bail!("ibc_packet_ack is not implemented for contract")
+    }
+
+    /// Evaluates contract's `ibc_packet_timeout` entry-point.
+    fn ibc_packet_timeout(&self, _deps: DepsMut<Q>, _env: Env, _msg: IbcPacketTimeoutMsg) -> AnyResult<IbcBasicResponse<C>> {
+        bail!("ibc_packet_timeout is not implemented for contract")
+    }
 }
 
 #[rustfmt::skip]
@@ -44,16 +86,43 @@ mod closures {
     pub type PermissionedFn<T, C, E, Q> = fn(deps: DepsMut<Q>, env: Env, msg: T) -> Result<Response<C>, E>;
     pub type ReplyFn<C, E, Q> = fn(deps: DepsMut<Q>, env: Env, msg: Reply) -> Result<Response<C>, E>;
     pub type QueryFn<T, E, Q> = fn(deps: Deps<Q>, env: Env, msg: T) -> Result<Binary, E>;
+    pub type IbcFn<T, R, E, Q> = fn(deps: DepsMut<Q>, env: Env, msg: T) -> Result<R, E>;
 
     // closure types
     pub type ContractClosure<T, C, E, Q> = Box<dyn Fn(DepsMut<Q>, Env, MessageInfo, T) -> Result<Response<C>, E>>;
     pub type PermissionedClosure<T, C, E, Q> = Box<dyn Fn(DepsMut<Q>, Env, T) -> Result<Response<C>, E>>;
     pub type ReplyClosure<C, E, Q> = Box<dyn Fn(DepsMut<Q>, Env, Reply) -> Result<Response<C>, E>>;
     pub type QueryClosure<T, E, Q> = Box<dyn Fn(Deps<Q>, Env, T) -> Result<Binary, E>>;
+    pub type IbcClosure<T, R, Q> = Box<dyn Fn(DepsMut<Q>, Env, T) -> AnyResult<R>>;
 }
 
 use closures::*;
 
+/// IBC entry-points of the contract wrapped in [ContractWrapper].
+struct IbcEntryPoints<C, Q>
+where
+    C: CustomMsg,
+    Q: CustomQuery,
+{
+    channel_open_fn: IbcClosure<IbcChannelOpenMsg, IbcChannelOpenResponse, Q>,
+    channel_connect_fn: IbcClosure<IbcChannelConnectMsg, IbcBasicResponse<C>, Q>,
+    channel_close_fn: IbcClosure<IbcChannelCloseMsg, IbcBasicResponse<C>, Q>,
+    packet_receive_fn: IbcClosure<IbcPacketReceiveMsg, IbcReceiveResponse<C>, Q>,
+    packet_ack_fn: IbcClosure<IbcPacketAckMsg, IbcBasicResponse<C>, Q>,
+    packet_timeout_fn: IbcClosure<IbcPacketTimeoutMsg, IbcBasicResponse<C>, Q>,
+}
+
+/// Converts IBC entry-point function into closure returning [AnyResult].
+fn ibc_closure<T, R, E, Q>(ibc_fn: IbcFn<T, R, E, Q>) -> IbcClosure<T, R, Q>
+where
+    T: 'static,
+    R: 'static,
+    E: Display + Debug + Send + Sync + 'static,
+    Q: CustomQuery + 'static,
+{
+    Box::new(move |deps, env, msg| ibc_fn(deps, env, msg).map_err(|err: E| anyhow!(err)))
+}
+
 /// This structure wraps the [Contract] trait implementor
 /// and provides generic access to the contract's entry-points.
 ///
@@ -156,6 +225,8 @@ pub struct ContractWrapper<
     sudo_fn: Option<PermissionedClosure<T4, C, E4, Q>>,
     reply_fn: Option<ReplyClosure<C, E5, Q>>,
     migrate_fn: Option<PermissionedClosure<T6, C, E6, Q>>,
+    ibc: Option<IbcEntryPoints<C, Q>>,
+    checksum: Option<Checksum>,
 }
 
 impl<T1, T2, T3, E1, E2, E3, C, Q> ContractWrapper<T1, T2, T3, E1, E2, E3, C, Q>
@@ -182,6 +253,8 @@ where
             sudo_fn: None,
             reply_fn: None,
             migrate_fn: None,
+            ibc: None,
+            checksum: None,
         }
     }
 
@@ -199,6 +272,8 @@ where
             sudo_fn: None,
             reply_fn: None,
             migrate_fn: None,
+            ibc: None,
+            checksum: None,
         }
     }
 }
@@ -237,6 +312,8 @@ where
             sudo_fn: Some(Box::new(sudo_fn)),
             reply_fn: self.reply_fn,
             migrate_fn: self.migrate_fn,
+            ibc: self.ibc,
+            checksum: self.checksum,
         }
     }
 
@@ -256,6 +333,8 @@ where
             sudo_fn: Some(customize_permissioned_fn(sudo_fn)),
             reply_fn: self.reply_fn,
             migrate_fn: self.migrate_fn,
+            ibc: self.ibc,
+            checksum: self.checksum,
         }
     }
 
@@ -274,6 +353,8 @@ where
             sudo_fn: self.sudo_fn,
             reply_fn: Some(Box::new(reply_fn)),
             migrate_fn: self.migrate_fn,
+            ibc: self.ibc,
+            checksum: self.checksum,
         }
     }
 
@@ -292,9 +373,58 @@ where
             sudo_fn: self.sudo_fn,
             reply_fn: Some(customize_permissioned_fn(reply_fn)),
             migrate_fn: self.migrate_fn,
+            ibc: self.ibc,
+            checksum: self.checksum,
         }
     }
 
+    /// Populates [ContractWrapper] with the checksum of contract's wasm code,
+    /// reported in code info instead of the generated checksum.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use cosmwasm_std::{Checksum, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, Binary};
+    /// # use cw_multi_test::ContractWrapper;
+    /// # fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> { Ok(Response::new()) }
+    /// # fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> { Ok(Response::new()) }
+    /// # fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> { Ok(Binary::default()) }
+    /// # let wasm_code = b"wasm byte code";
+    /// let contract = ContractWrapper::new(execute, instantiate, query)
+    ///     .with_checksum(Checksum::generate(wasm_code));
+    /// ```
+    pub fn with_checksum(mut self, checksum: Checksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Populates [ContractWrapper] with contract's IBC entry-points.
+    ///
+    /// Contracts with IBC entry-points get an IBC port assigned,
+    /// reported in the contract info as `wasm.<contract address>`.
+    pub fn with_ibc<E>(
+        mut self,
+        channel_open_fn: IbcFn<IbcChannelOpenMsg, IbcChannelOpenResponse, E, Q>,
+        channel_connect_fn: IbcFn<IbcChannelConnectMsg, IbcBasicResponse<C>, E, Q>,
+        channel_close_fn: IbcFn<IbcChannelCloseMsg, IbcBasicResponse<C>, E, Q>,
+        packet_receive_fn: IbcFn<IbcPacketReceiveMsg, IbcReceiveResponse<C>, E, Q>,
+        packet_ack_fn: IbcFn<IbcPacketAckMsg, IbcBasicResponse<C>, E, Q>,
+        packet_timeout_fn: IbcFn<IbcPacketTimeoutMsg, IbcBasicResponse<C>, E, Q>,
+    ) -> Self
+    where
+        E: Display + Debug + Send + Sync + 'static,
+    {
+        self.ibc = Some(IbcEntryPoints {
+            channel_open_fn: ibc_closure(channel_open_fn),
+            channel_connect_fn: ibc_closure(channel_connect_fn),
+            channel_close_fn: ibc_closure(channel_close_fn),
+            packet_receive_fn: ibc_closure(packet_receive_fn),
+            packet_ack_fn: ibc_closure(packet_ack_fn),
+            packet_timeout_fn: ibc_closure(packet_timeout_fn),
+        });
+        self
+    }
+
     /// Populates [ContractWrapper] with contract's `migrate` entry-point and custom message type.
     pub fn with_migrate<T6A, E6A>(
         self,
@@ -311,6 +441,8 @@ where
             sudo_fn: self.sudo_fn,
             reply_fn: self.reply_fn,
             migrate_fn: Some(Box::new(migrate_fn)),
+            ibc: self.ibc,
+            checksum: self.checksum,
         }
     }
 
@@ -330,6 +462,8 @@ where
             sudo_fn: self.sudo_fn,
             reply_fn: self.reply_fn,
             migrate_fn: Some(customize_permissioned_fn(migrate_fn)),
+            ibc: self.ibc,
+            checksum: self.checksum,
         }
     }
 }
@@ -530,4 +664,92 @@ where
             None => bail!("migrate is not implemented for contract"),
         }
     }
+
+    /// Returns the checksum set with [with_checksum](ContractWrapper::with_checksum).
+    fn checksum(&self) -> Option<Checksum> {
+        self.checksum
+    }
+
+    /// Returns `true` when IBC entry-points were set with [with_ibc](ContractWrapper::with_ibc).
+    fn has_ibc_entry_points(&self) -> bool {
+        self.ibc.is_some()
+    }
+
+    /// Calls `ibc_channel_open` entry-point set with [with_ibc](ContractWrapper::with_ibc).
+    fn ibc_channel_open(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        msg: IbcChannelOpenMsg,
+    ) -> AnyResult<IbcChannelOpenResponse> {
+        match &self.ibc {
+            Some(ibc) => (ibc.channel_open_fn)(deps, env, msg),
+            None => bail!("ibc_channel_open is not implemented for contract"),
+        }
+    }
+
+    /// Calls `ibc_channel_connect` entry-point set with [with_ibc](ContractWrapper::with_ibc).
+    fn ibc_channel_connect(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        msg: IbcChannelConnectMsg,
+    ) -> AnyResult<IbcBasicResponse<C>> {
+        match &self.ibc {
+            Some(ibc) => (ibc.channel_connect_fn)(deps, env, msg),
+            None => bail!("ibc_channel_connect is not implemented for contract"),
+        }
+    }
+
+    /// Calls `ibc_channel_close` entry-point set with [with_ibc](ContractWrapper::with_ibc).
+    fn ibc_channel_close(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        msg: IbcChannelCloseMsg,
+    ) -> AnyResult<IbcBasicResponse<C>> {
+        match &self.ibc {
+            Some(ibc) => (ibc.channel_close_fn)(deps, env, msg),
+            None => bail!("ibc_channel_close is not implemented for contract"),
+        }
+    }
+
+    /// Calls `ibc_packet_receive` entry-point set with [with_ibc](ContractWrapper::with_ibc).
+    fn ibc_packet_receive(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        msg: IbcPacketReceiveMsg,
+    ) -> AnyResult<IbcReceiveResponse<C>> {
+        match &self.ibc {
+            Some(ibc) => (ibc.packet_receive_fn)(deps, env, msg),
+            None => bail!("ibc_packet_receive is not implemented for contract"),
+        }
+    }
+
+    /// Calls `ibc_packet_ack` entry-point set with [with_ibc](ContractWrapper::with_ibc).
+    fn ibc_packet_ack(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        msg: IbcPacketAckMsg,
+    ) -> AnyResult<IbcBasicResponse<C>> {
+        match &self.ibc {
+            Some(ibc) => (ibc.packet_ack_fn)(deps, env, msg),
+            None => bail!("ibc_packet_ack is not implemented for contract"),
+        }
+    }
+
+    /// Calls `ibc_packet_timeout` entry-point set with [with_ibc](ContractWrapper::with_ibc).
+    fn ibc_packet_timeout(
+        &self,
+        deps: DepsMut<Q>,
+        env: Env,
+        msg: IbcPacketTimeoutMsg,
+    ) -> AnyResult<IbcBasicResponse<C>> {
+        match &self.ibc {
+            Some(ibc) => (ibc.packet_timeout_fn)(deps, env, msg),
+            None => bail!("ibc_packet_timeout is not implemented for contract"),
+        }
+    }
 }
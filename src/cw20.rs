@@ -0,0 +1,377 @@
+//! # Built-in cw20 token contract
+//!
+//! Minimal implementation of the [cw20](https://github.com/CosmWasm/cw-plus/blob/main/packages/cw20/README.md)
+//! fungible token, compatible on the message level with `cw20-base`, so tests that only
+//! need some token do not have to depend on and wrap the `cw20-base` contract.
+//!
+//! Supported are transfers, sends to contracts, burning and minting of tokens,
+//! as well as balance, token info and minter queries. Allowances are not supported.
+//!
+//! Available with the `cw20` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use cosmwasm_std::Uint128;
+//! use cw_multi_test::cw20::{Cw20Coin, Cw20ExecuteMsg};
+//! use cw_multi_test::{App, Executor};
+//!
+//! let mut app = App::default();
+//!
+//! let owner = app.api().addr_make("owner");
+//! let alice = app.api().addr_make("alice");
+//!
+//! let initial_balances = vec![Cw20Coin {
+//!     address: owner.to_string(),
+//!     amount: Uint128::new(100),
+//! }];
+//! let token = app.create_cw20(&owner, "TOKEN", initial_balances).unwrap();
+//!
+//! let msg = Cw20ExecuteMsg::Transfer {
+//!     recipient: alice.to_string(),
+//!     amount: Uint128::new(30),
+//! };
+//! app.execute_contract(owner.clone(), token.clone(), &msg, &[]).unwrap();
+//!
+//! assert_eq!(Uint128::new(70), app.cw20_balance(&token, &owner).unwrap());
+//! assert_eq!(Uint128::new(30), app.cw20_balance(&token, &alice).unwrap());
+//! ```
+
+use crate::{Contract, ContractWrapper};
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, CosmosMsg, CustomMsg, CustomQuery, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+/// Token information, saved on instantiation.
+const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
+
+/// Token balances of addresses.
+const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
+
+/// Token information, saved under the same key as in `cw20-base`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct TokenInfo {
+    name: String,
+    symbol: String,
+    decimals: u8,
+    total_supply: Uint128,
+    mint: Option<MinterData>,
+}
+
+/// Minter of the token, saved as part of the token information.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct MinterData {
+    minter: Addr,
+    cap: Option<Uint128>,
+}
+
+/// Initial balance of an address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Cw20Coin {
+    /// Address holding the tokens.
+    pub address: String,
+    /// Number of tokens.
+    pub amount: Uint128,
+}
+
+/// Message instantiating the token contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct InstantiateMsg {
+    /// Name of the token.
+    pub name: String,
+    /// Symbol of the token.
+    pub symbol: String,
+    /// Number of decimal places of the token.
+    pub decimals: u8,
+    /// Balances of addresses after instantiation.
+    pub initial_balances: Vec<Cw20Coin>,
+    /// Minter of the token, no tokens can be minted when `None`.
+    pub mint: Option<MinterResponse>,
+}
+
+/// Messages executed by the token contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20ExecuteMsg {
+    /// Transfers tokens from the sender to the recipient.
+    Transfer {
+        /// Address receiving the tokens.
+        recipient: String,
+        /// Number of transferred tokens.
+        amount: Uint128,
+    },
+    /// Burns tokens of the sender, reducing the total supply.
+    Burn {
+        /// Number of burned tokens.
+        amount: Uint128,
+    },
+    /// Transfers tokens from the sender to the contract
+    /// and calls the contract with [Cw20ReceiveMsg].
+    Send {
+        /// Address of the contract receiving the tokens.
+        contract: String,
+        /// Number of transferred tokens.
+        amount: Uint128,
+        /// Message passed to the receiving contract.
+        msg: Binary,
+    },
+    /// Mints new tokens to the recipient, allowed only for the minter.
+    Mint {
+        /// Address receiving the tokens.
+        recipient: String,
+        /// Number of minted tokens.
+        amount: Uint128,
+    },
+}
+
+/// Message passed to the contract receiving tokens with [Cw20ExecuteMsg::Send].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Cw20ReceiveMsg {
+    /// Address of the sender of the tokens.
+    pub sender: String,
+    /// Number of received tokens.
+    pub amount: Uint128,
+    /// Message passed by the sender.
+    pub msg: Binary,
+}
+
+/// Wrapper serializing [Cw20ReceiveMsg] as `{"receive": {...}}`.
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum ReceiverExecuteMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+/// Queries handled by the token contract.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20QueryMsg {
+    /// Returns the balance of the address as [BalanceResponse].
+    Balance {
+        /// Queried address.
+        address: String,
+    },
+    /// Returns the token information as [TokenInfoResponse].
+    TokenInfo {},
+    /// Returns the minter of the token as `Option<MinterResponse>`.
+    Minter {},
+}
+
+/// Response to [Cw20QueryMsg::Balance].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct BalanceResponse {
+    /// Balance of the queried address.
+    pub balance: Uint128,
+}
+
+/// Response to [Cw20QueryMsg::TokenInfo].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TokenInfoResponse {
+    /// Name of the token.
+    pub name: String,
+    /// Symbol of the token.
+    pub symbol: String,
+    /// Number of decimal places of the token.
+    pub decimals: u8,
+    /// Total number of tokens.
+    pub total_supply: Uint128,
+}
+
+/// Minter of the token, response to [Cw20QueryMsg::Minter].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct MinterResponse {
+    /// Address allowed to mint tokens.
+    pub minter: String,
+    /// Maximum total supply, unlimited when `None`.
+    pub cap: Option<Uint128>,
+}
+
+/// Returns the token contract, ready to be stored with [App::store_code](crate::App::store_code).
+pub fn contract<C, Q>() -> Box<dyn Contract<C, Q>>
+where
+    C: CustomMsg + 'static,
+    Q: CustomQuery + DeserializeOwned + 'static,
+{
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
+fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InstantiateMsg,
+) -> StdResult<Response> {
+    let mut total_supply = Uint128::zero();
+    for coin in msg.initial_balances {
+        let address = deps.api.addr_validate(&coin.address)?;
+        add_balance(deps.storage, &address, coin.amount)?;
+        total_supply = total_supply.checked_add(coin.amount)?;
+    }
+    let mint = match msg.mint {
+        Some(minter) => {
+            if minter.cap.is_some_and(|cap| total_supply > cap) {
+                return Err(StdError::generic_err("Initial supply greater than cap"));
+            }
+            Some(MinterData {
+                minter: deps.api.addr_validate(&minter.minter)?,
+                cap: minter.cap,
+            })
+        }
+        None => None,
+    };
+    let token_info = TokenInfo {
+        name: msg.name,
+        symbol: msg.symbol,
+        decimals: msg.decimals,
+        total_supply,
+        mint,
+    };
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+    Ok(Response::default())
+}
+
+fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: Cw20ExecuteMsg,
+) -> StdResult<Response> {
+    match msg {
+        Cw20ExecuteMsg::Transfer { recipient, amount } => {
+            let recipient_addr = deps.api.addr_validate(&recipient)?;
+            sub_balance(deps.storage, &info.sender, amount)?;
+            add_balance(deps.storage, &recipient_addr, amount)?;
+            Ok(Response::new()
+                .add_attribute("action", "transfer")
+                .add_attribute("from", info.sender)
+                .add_attribute("to", recipient)
+                .add_attribute("amount", amount))
+        }
+        Cw20ExecuteMsg::Burn { amount } => {
+            sub_balance(deps.storage, &info.sender, amount)?;
+            TOKEN_INFO.update(deps.storage, |mut token_info| -> StdResult<_> {
+                token_info.total_supply = token_info.total_supply.checked_sub(amount)?;
+                Ok(token_info)
+            })?;
+            Ok(Response::new()
+                .add_attribute("action", "burn")
+                .add_attribute("from", info.sender)
+                .add_attribute("amount", amount))
+        }
+        Cw20ExecuteMsg::Send {
+            contract,
+            amount,
+            msg,
+        } => {
+            let contract_addr = deps.api.addr_validate(&contract)?;
+            sub_balance(deps.storage, &info.sender, amount)?;
+            add_balance(deps.storage, &contract_addr, amount)?;
+            let receive_msg = ReceiverExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: info.sender.to_string(),
+                amount,
+                msg,
+            });
+            let message: CosmosMsg = WasmMsg::Execute {
+                contract_addr: contract,
+                msg: to_json_binary(&receive_msg)?,
+                funds: vec![],
+            }
+            .into();
+            Ok(Response::new()
+                .add_message(message)
+                .add_attribute("action", "send")
+                .add_attribute("from", info.sender)
+                .add_attribute("to", contract_addr)
+                .add_attribute("amount", amount))
+        }
+        Cw20ExecuteMsg::Mint { recipient, amount } => {
+            let mut token_info = TOKEN_INFO.load(deps.storage)?;
+            let Some(mint) = &token_info.mint else {
+                return Err(StdError::generic_err("Unauthorized"));
+            };
+            if mint.minter != info.sender {
+                return Err(StdError::generic_err("Unauthorized"));
+            }
+            token_info.total_supply = token_info.total_supply.checked_add(amount)?;
+            if mint.cap.is_some_and(|cap| token_info.total_supply > cap) {
+                return Err(StdError::generic_err("Minting cannot exceed the cap"));
+            }
+            TOKEN_INFO.save(deps.storage, &token_info)?;
+            let recipient_addr = deps.api.addr_validate(&recipient)?;
+            add_balance(deps.storage, &recipient_addr, amount)?;
+            Ok(Response::new()
+                .add_attribute("action", "mint")
+                .add_attribute("to", recipient)
+                .add_attribute("amount", amount))
+        }
+    }
+}
+
+fn query(deps: Deps, _env: Env, msg: Cw20QueryMsg) -> StdResult<Binary> {
+    match msg {
+        Cw20QueryMsg::Balance { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            let balance = BALANCES
+                .may_load(deps.storage, &address)?
+                .unwrap_or_default();
+            to_json_binary(&BalanceResponse { balance })
+        }
+        Cw20QueryMsg::TokenInfo {} => {
+            let token_info = TOKEN_INFO.load(deps.storage)?;
+            to_json_binary(&TokenInfoResponse {
+                name: token_info.name,
+                symbol: token_info.symbol,
+                decimals: token_info.decimals,
+                total_supply: token_info.total_supply,
+            })
+        }
+        Cw20QueryMsg::Minter {} => {
+            let token_info = TOKEN_INFO.load(deps.storage)?;
+            let minter = token_info.mint.map(|mint| MinterResponse {
+                minter: mint.minter.to_string(),
+                cap: mint.cap,
+            });
+            to_json_binary(&minter)
+        }
+    }
+}
+
+/// Adds tokens to the balance of the address.
+fn add_balance(storage: &mut dyn Storage, address: &Addr, amount: Uint128) -> StdResult<()> {
+    BALANCES.update(storage, address, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_add(amount)?)
+    })?;
+    Ok(())
+}
+
+/// Subtracts tokens from the balance of the address, fails when the balance is too low.
+fn sub_balance(storage: &mut dyn Storage, address: &Addr, amount: Uint128) -> StdResult<()> {
+    BALANCES.update(storage, address, |balance| -> StdResult<_> {
+        Ok(balance.unwrap_or_default().checked_sub(amount)?)
+    })?;
+    Ok(())
+}
+
+/// Message instantiating a token with specified name and initial balances,
+/// used by [App::create_cw20](crate::App::create_cw20).
+pub(crate) fn instantiate_msg(
+    owner: &Addr,
+    name: &str,
+    initial_balances: Vec<Cw20Coin>,
+) -> InstantiateMsg {
+    InstantiateMsg {
+        name: name.to_string(),
+        symbol: name.to_string(),
+        decimals: 6,
+        initial_balances,
+        mint: Some(MinterResponse {
+            minter: owner.to_string(),
+            cap: None,
+        }),
+    }
+}
@@ -1,7 +1,7 @@
 //! # Error definitions
 
 pub use anyhow::{anyhow, bail, Context as AnyContext, Error as AnyError, Result as AnyResult};
-use cosmwasm_std::{WasmMsg, WasmQuery};
+use cosmwasm_std::{Addr, WasmMsg, WasmQuery};
 use thiserror::Error;
 
 /// An enumeration of errors reported across the **CosmWasm MultiTest** library.
@@ -47,9 +47,23 @@ pub enum Error {
     #[error("no more code identifiers available")]
     NoMoreCodeIdAvailable,
 
+    /// Error variant for reporting that the gas limit was exceeded, holds the gas used and the gas limit.
+    #[error("out of gas: gas used {0}, gas limit {1}")]
+    OutOfGas(u64, u64),
+
     /// Error variant for reporting duplicated contract addresses.
     #[error("Contract with this address already exists: {0}")]
     DuplicatedContractAddress(String),
+
+    /// Error variant for reporting storage keys or values exceeding the length limit,
+    /// holds the length and the limit.
+    #[error("Region length too big. Got {0}, limit {1}")]
+    RegionLengthTooBig(usize, usize),
+
+    /// Error variant for reporting that a contract iterated over more storage items
+    /// than allowed in a single call, holds the limit.
+    #[error("Iterated over more than {0} storage items")]
+    TooManyIteratedItems(u64),
 }
 
 impl Error {
@@ -103,8 +117,130 @@ impl Error {
         Self::NoMoreCodeIdAvailable
     }
 
+    /// Creates an instance of the [Error](Self) for exceeded gas limit.
+    pub fn out_of_gas(gas_used: u64, gas_limit: u64) -> Self {
+        Self::OutOfGas(gas_used, gas_limit)
+    }
+
     /// Creates an instance of the [Error](Self) for duplicated contract addresses.
     pub fn duplicated_contract_address(address: impl Into<String>) -> Self {
         Self::DuplicatedContractAddress(address.into())
     }
+
+    /// Creates an instance of the [Error](Self) for storage keys or values exceeding the length limit.
+    pub fn region_length_too_big(length: usize, limit: usize) -> Self {
+        Self::RegionLengthTooBig(length, limit)
+    }
+
+    /// Creates an instance of the [Error](Self) for exceeded limit of iterated storage items.
+    pub fn too_many_iterated_items(limit: u64) -> Self {
+        Self::TooManyIteratedItems(limit)
+    }
+}
+
+/// Structured context attached to errors returned while processing messages.
+///
+/// The original error (e.g. the typed error returned by a contract) is preserved,
+/// so both the original error and this context can be retrieved by downcasting:
+///
+/// ```
+/// use cosmwasm_std::{to_json_binary, Empty, WasmMsg};
+/// use cw_multi_test::error::AppError;
+/// use cw_multi_test::{App, Executor};
+///
+/// let mut app = App::default();
+/// let sender = app.api().addr_make("sender");
+/// let contract_addr = app.api().addr_make("contract");
+///
+/// let msg = WasmMsg::Execute {
+///     contract_addr: contract_addr.to_string(),
+///     msg: to_json_binary(&Empty {}).unwrap(),
+///     funds: vec![],
+/// };
+/// let err = app.execute(sender, msg.into()).unwrap_err();
+///
+/// let AppError::WasmMsg { contract, .. } = err.downcast_ref().unwrap();
+/// assert_eq!(Some(&contract_addr), contract.as_ref());
+/// ```
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum AppError {
+    /// Error of processing a [WasmMsg].
+    #[error("Error executing WasmMsg:\n  sender: {sender}\n  {msg:?}")]
+    WasmMsg {
+        /// Sender of the message.
+        sender: Addr,
+        /// Address of the called or instantiated contract, not known when the message
+        /// failed before the address of the instantiated contract was generated.
+        contract: Option<Addr>,
+        /// The failed message.
+        msg: WasmMsg,
+        /// Index of the failed message among messages sent together, i.e. messages
+        /// of a transaction or sub-messages of the response of the calling contract.
+        msg_index: Option<usize>,
+        /// Context of the failed message sent by the called contract, when the error
+        /// originated in a nested call, so the whole chain of calls can be inspected.
+        nested: Option<Box<AppError>>,
+    },
+}
+
+impl AppError {
+    /// Creates an instance of the [AppError](Self) for a failed [WasmMsg].
+    /// The address of the instantiated contract is taken from `instantiated`,
+    /// for other messages it is taken from the message itself.
+    pub(crate) fn wasm_msg(
+        sender: Addr,
+        msg: WasmMsg,
+        instantiated: Option<Addr>,
+        source: &AnyError,
+    ) -> Self {
+        let contract = match &msg {
+            WasmMsg::Execute { contract_addr, .. }
+            | WasmMsg::Migrate { contract_addr, .. }
+            | WasmMsg::UpdateAdmin { contract_addr, .. }
+            | WasmMsg::ClearAdmin { contract_addr } => Some(Addr::unchecked(contract_addr)),
+            _ => instantiated,
+        };
+        Self::WasmMsg {
+            sender,
+            contract,
+            msg,
+            msg_index: None,
+            nested: source.downcast_ref::<AppError>().cloned().map(Box::new),
+        }
+    }
+
+    /// Records the index of the failed message in the context attached to specified error,
+    /// when the message was sent together with other messages.
+    pub(crate) fn set_msg_index(err: &mut AnyError, index: usize) {
+        if let Some(Self::WasmMsg { msg_index, .. }) = err.downcast_mut::<AppError>() {
+            msg_index.get_or_insert(index);
+        }
+    }
+
+    /// Returns indices of failed messages, from the outermost message to the message
+    /// where the error originated, so the failed message can be located
+    /// in the tree of messages. Messages without known index are skipped.
+    pub fn msg_indices(&self) -> Vec<usize> {
+        let mut indices = vec![];
+        let mut current = Some(self);
+        while let Some(Self::WasmMsg {
+            msg_index, nested, ..
+        }) = current
+        {
+            indices.extend(*msg_index);
+            current = nested.as_deref();
+        }
+        indices
+    }
+
+    /// Returns the context of the innermost failed call, where the error originated.
+    pub fn origin(&self) -> &AppError {
+        match self {
+            Self::WasmMsg {
+                nested: Some(nested),
+                ..
+            } => nested.origin(),
+            _ => self,
+        }
+    }
 }
@@ -0,0 +1,56 @@
+//! # Log of events emitted by committed executions
+
+use cosmwasm_std::Event;
+
+/// Log of events emitted by committed transactions, sudo calls and end blockers,
+/// see [App::events_since](crate::App::events_since).
+///
+/// Events are recorded only when the log is enabled. Cursors are absolute positions
+/// in the log, so they remain valid after the recorded events are cleared.
+#[derive(Clone, Default)]
+pub(crate) struct EventLog {
+    /// Flag indicating if events are recorded.
+    enabled: bool,
+    /// Number of events removed from the beginning of the log.
+    offset: usize,
+    /// Recorded events, in emission order.
+    events: Vec<Event>,
+}
+
+impl EventLog {
+    /// Enables or disables recording events.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Appends specified events to the log, when recording is enabled.
+    pub fn record(&mut self, events: &[Event]) {
+        if self.enabled {
+            self.events.extend_from_slice(events);
+        }
+    }
+
+    /// Returns the cursor pointing past the last recorded event.
+    pub fn cursor(&self) -> usize {
+        self.offset + self.events.len()
+    }
+
+    /// Returns events recorded since specified cursor, cleared events are skipped.
+    pub fn since(&self, cursor: usize) -> &[Event] {
+        self.events
+            .get(cursor.saturating_sub(self.offset)..)
+            .unwrap_or_default()
+    }
+
+    /// Removes all recorded events, the cursor keeps its value.
+    pub fn clear(&mut self) {
+        self.offset += self.events.len();
+        self.events.clear();
+    }
+
+    /// Removes events recorded after specified cursor.
+    pub fn truncate(&mut self, cursor: usize) {
+        self.events.truncate(cursor.saturating_sub(self.offset));
+        self.offset = self.offset.min(cursor);
+    }
+}
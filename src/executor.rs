@@ -1,20 +1,40 @@
 use crate::error::AnyResult;
+use anyhow::bail;
 use cosmwasm_std::{
-    to_json_binary, Addr, Attribute, BankMsg, Binary, Coin, CosmosMsg, CustomMsg, Event,
-    SubMsgResponse, WasmMsg,
+    from_json, to_json_binary, Addr, AnyMsg, Attribute, BankMsg, Binary, Coin, CosmosMsg,
+    CustomMsg, Event, SubMsgResponse, WasmMsg,
 };
-use cw_utils::{parse_execute_response_data, parse_instantiate_response_data};
-use serde::Serialize;
+use cw_utils::{
+    parse_execute_response_data, parse_instantiate_response_data, MsgInstantiateContractResponse,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
+/// Metadata assigned by [App](crate::App) to every top-level transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxMetadata {
+    /// Deterministic pseudo hash of the transaction, as uppercase hexadecimal string.
+    pub hash: String,
+    /// Height of the block the transaction was executed in.
+    pub height: u64,
+    /// Index of the transaction within the block.
+    pub index: u32,
+    /// Gas consumed by the transaction, zero when gas is not metered.
+    pub gas_used: u64,
+}
+
 /// A subset of data returned as a response of a contract entry point,
 /// such as `instantiate`, `execute` or `migrate`.
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct AppResponse {
     /// Response events.
     pub events: Vec<Event>,
     /// Response data.
     pub data: Option<Binary>,
+    /// Metadata of the top-level transaction this response belongs to.
+    /// Only responses returned directly from [App](crate::App) have this field populated.
+    pub tx: Option<TxMetadata>,
 }
 
 impl AppResponse {
@@ -27,6 +47,69 @@ impl AppResponse {
         &self.events[idx].attributes[1..]
     }
 
+    /// Returns the first `wasm` event having an attribute with specified key and value.
+    pub fn wasm_event(&self, key: &str, value: &str) -> AnyResult<&Event> {
+        match self.events.iter().find(|ev| {
+            ev.ty == "wasm"
+                && ev
+                    .attributes
+                    .iter()
+                    .any(|at| at.key == key && at.value == value)
+        }) {
+            Some(event) => Ok(event),
+            None => bail!("No wasm event with attribute {key}={value} found"),
+        }
+    }
+
+    /// Returns the value of the first attribute with specified key,
+    /// found in the first event of specified type.
+    pub fn attribute_value(&self, ty: &str, key: &str) -> Option<&str> {
+        self.events
+            .iter()
+            .filter(|ev| ev.ty == ty)
+            .flat_map(|ev| ev.attributes.iter())
+            .find(|at| at.key == key)
+            .map(|at| at.value.as_str())
+    }
+
+    /// Returns all custom attributes emitted by the contract with specified address
+    /// in all `wasm` events, skipping the `_contract_address` attribute.
+    pub fn contract_attrs(&self, contract_addr: &Addr) -> Vec<&Attribute> {
+        self.events
+            .iter()
+            .filter(|ev| {
+                ev.ty == "wasm"
+                    && ev.attributes.iter().any(|at| {
+                        at.key == "_contract_address" && at.value == contract_addr.as_str()
+                    })
+            })
+            .flat_map(|ev| ev.attributes.iter())
+            .filter(|at| at.key != "_contract_address")
+            .collect()
+    }
+
+    /// Decodes the data of this response as the JSON-encoded value.
+    pub fn data_as<T: DeserializeOwned>(&self) -> AnyResult<T> {
+        let Some(data) = &self.data else {
+            bail!("Response has no data");
+        };
+        Ok(from_json(data)?)
+    }
+
+    /// Decodes the data of the response to the `WasmMsg::Instantiate`
+    /// or `WasmMsg::Instantiate2` message executed with [Executor::execute].
+    pub fn instantiate_response(&self) -> AnyResult<MsgInstantiateContractResponse> {
+        let data = self.data.clone().unwrap_or_default();
+        Ok(parse_instantiate_response_data(data.as_slice())?)
+    }
+
+    /// Decodes the data returned by the contract from the response
+    /// to the `WasmMsg::Execute` message executed with [Executor::execute].
+    pub fn execute_response_data(&self) -> AnyResult<Option<Binary>> {
+        let data = self.data.clone().unwrap_or_default();
+        Ok(parse_execute_response_data(data.as_slice())?.data)
+    }
+
     /// Checks if there is an Event that is a super-set of this.
     ///
     /// It has the same type, and all compared attributes are included in it as well.
@@ -61,6 +144,7 @@ impl From<SubMsgResponse> for AppResponse {
             #[allow(deprecated)]
             data: reply.data,
             events: reply.events,
+            tx: None,
         }
     }
 }
@@ -79,6 +163,32 @@ where
     /// but all are persisted on success.
     fn execute(&mut self, sender: Addr, msg: CosmosMsg<C>) -> AnyResult<AppResponse>;
 
+    /// Processes (executes) a batch of `CosmosMsg`, like a single transaction
+    /// broadcast by off-chain tooling, and returns one response per message.
+    ///
+    /// [App](crate::App) executes the whole batch atomically, so no state changes
+    /// are persisted if any of the messages fails. The default implementation
+    /// executes messages one by one using [execute](Self::execute)
+    /// and stops at the first failure.
+    fn execute_cosmos_msgs(
+        &mut self,
+        sender: Addr,
+        msgs: Vec<CosmosMsg<C>>,
+    ) -> AnyResult<Vec<AppResponse>> {
+        msgs.into_iter()
+            .map(|msg| self.execute(sender.clone(), msg))
+            .collect()
+    }
+
+    /// Processes (executes) a batch of pre-encoded `Any` messages,
+    /// that are handled by the stargate module.
+    /// This is just a helper function around [execute_cosmos_msgs()](Self::execute_cosmos_msgs)
+    /// with `CosmosMsg::Any` messages.
+    fn execute_any_msgs(&mut self, sender: Addr, msgs: Vec<AnyMsg>) -> AnyResult<Vec<AppResponse>> {
+        let msgs = msgs.into_iter().map(CosmosMsg::Any).collect();
+        self.execute_cosmos_msgs(sender, msgs)
+    }
+
     /// Create a contract and get the new address.
     /// This is just a helper around execute()
     fn instantiate_contract<T: Serialize, U: Into<String>>(
@@ -0,0 +1,173 @@
+//! # Gas metering for contracts executed by [WasmKeeper](crate::WasmKeeper)
+
+use cosmwasm_std::{Order, Record, Storage};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Gas costs charged for calling contracts and accessing contract storage.
+///
+/// Default values follow the flat costs used by `wasmd` and Cosmos SDK:
+/// each call to a contract entry-point costs `60_000` gas, reading from storage costs `1000`,
+/// writing `2000`, removing `1000` and each item returned while iterating costs `30` gas.
+/// Per-byte costs are not charged.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GasCosts {
+    /// Gas charged for calling contract's `instantiate` entry-point.
+    pub instantiate: u64,
+    /// Gas charged for calling contract's `execute` entry-point.
+    pub execute: u64,
+    /// Gas charged for calling contract's `query` entry-point.
+    pub query: u64,
+    /// Gas charged for calling contract's `migrate` entry-point.
+    pub migrate: u64,
+    /// Gas charged for calling contract's `sudo` entry-point.
+    pub sudo: u64,
+    /// Gas charged for calling contract's `reply` entry-point.
+    pub reply: u64,
+    /// Gas charged for every read from contract storage.
+    pub storage_read: u64,
+    /// Gas charged for every write to contract storage.
+    pub storage_write: u64,
+    /// Gas charged for every removal from contract storage.
+    pub storage_remove: u64,
+    /// Gas charged for every item returned while iterating over contract storage.
+    pub storage_iter_next: u64,
+}
+
+impl Default for GasCosts {
+    /// Returns default gas costs, see [GasCosts] for details.
+    fn default() -> Self {
+        Self {
+            instantiate: 60_000,
+            execute: 60_000,
+            query: 60_000,
+            migrate: 60_000,
+            sudo: 60_000,
+            reply: 60_000,
+            storage_read: 1000,
+            storage_write: 2000,
+            storage_remove: 1000,
+            storage_iter_next: 30,
+        }
+    }
+}
+
+/// Gas meter accumulating gas consumed by all contract calls.
+pub(crate) struct GasMeter {
+    /// Gas costs charged by this meter.
+    costs: GasCosts,
+    /// Total gas consumed since the meter was created.
+    consumed: AtomicU64,
+    /// Value of the consumed gas when the limit was set.
+    limit_start: AtomicU64,
+    /// Maximum amount of gas consumed after the limit was set, `u64::MAX` when unlimited.
+    limit: AtomicU64,
+}
+
+impl GasMeter {
+    /// Creates a new gas meter charging specified costs.
+    pub fn new(costs: GasCosts) -> Self {
+        Self {
+            costs,
+            consumed: AtomicU64::new(0),
+            limit_start: AtomicU64::new(0),
+            limit: AtomicU64::new(u64::MAX),
+        }
+    }
+
+    /// Returns gas costs charged by this meter.
+    pub fn costs(&self) -> &GasCosts {
+        &self.costs
+    }
+
+    /// Adds specified amount of gas to the consumed gas.
+    pub fn consume(&self, amount: u64) {
+        self.consumed.fetch_add(amount, Ordering::Relaxed);
+    }
+
+    /// Returns total gas consumed since the meter was created.
+    pub fn consumed(&self) -> u64 {
+        self.consumed.load(Ordering::Relaxed)
+    }
+
+    /// Resets the consumed gas to specified value, discarding gas consumed since it was read.
+    pub fn restore_consumed(&self, consumed: u64) {
+        self.consumed.store(consumed, Ordering::Relaxed);
+    }
+
+    /// Limits the amount of gas that can be consumed from now on, `None` removes the limit.
+    pub fn set_limit(&self, limit: Option<u64>) {
+        self.limit_start.store(self.consumed(), Ordering::Relaxed);
+        self.limit
+            .store(limit.unwrap_or(u64::MAX), Ordering::Relaxed);
+    }
+
+    /// Limits the amount of gas that can be consumed from now on, within the current limit.
+    /// Returns the current limit, to be restored with [restore_limit](Self::restore_limit).
+    pub fn push_limit(&self, limit: u64) -> (u64, u64) {
+        let saved = (
+            self.limit_start.load(Ordering::Relaxed),
+            self.limit.load(Ordering::Relaxed),
+        );
+        let consumed = self.consumed();
+        let remaining = saved.1.saturating_sub(consumed - saved.0);
+        self.limit_start.store(consumed, Ordering::Relaxed);
+        self.limit.store(limit.min(remaining), Ordering::Relaxed);
+        saved
+    }
+
+    /// Restores the limit returned by [push_limit](Self::push_limit).
+    pub fn restore_limit(&self, (limit_start, limit): (u64, u64)) {
+        self.limit_start.store(limit_start, Ordering::Relaxed);
+        self.limit.store(limit, Ordering::Relaxed);
+    }
+
+    /// Returns the gas consumed since the limit was set and the limit,
+    /// when the consumed gas exceeds the limit.
+    pub fn exceeded(&self) -> Option<(u64, u64)> {
+        let used = self.consumed() - self.limit_start.load(Ordering::Relaxed);
+        let limit = self.limit.load(Ordering::Relaxed);
+        (used > limit).then_some((used, limit))
+    }
+}
+
+/// Storage charging gas for every access to the wrapped storage.
+pub(crate) struct MeteredStorage<'a> {
+    storage: Box<dyn Storage + 'a>,
+    meter: &'a GasMeter,
+}
+
+impl<'a> MeteredStorage<'a> {
+    /// Wraps the storage, all accesses will be charged using specified meter.
+    pub fn new(storage: Box<dyn Storage + 'a>, meter: &'a GasMeter) -> Self {
+        Self { storage, meter }
+    }
+}
+
+impl<'a> Storage for MeteredStorage<'a> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.meter.consume(self.meter.costs().storage_read);
+        self.storage.get(key)
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        let meter = self.meter;
+        Box::new(self.storage.range(start, end, order).inspect(move |_| {
+            meter.consume(meter.costs().storage_iter_next);
+        }))
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.meter.consume(self.meter.costs().storage_write);
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.meter.consume(self.meter.costs().storage_remove);
+        self.storage.remove(key)
+    }
+}
@@ -1,17 +1,505 @@
-use crate::{AcceptingModule, FailingModule, Module};
-use cosmwasm_std::{Empty, GovMsg};
+use crate::addresses::module_address;
+use crate::app::CosmosRouter;
+use crate::error::{anyhow, bail, AnyResult};
+use crate::prefixed_storage::{prefixed, prefixed_read};
+use crate::transactions::transactional;
+use crate::{AcceptingModule, AppResponse, FailingModule, Module};
+use cosmwasm_std::{
+    from_json, to_json_binary, to_json_vec, Addr, Api, Binary, BlockInfo, CosmosMsg, CustomMsg,
+    CustomQuery, Decimal, Empty, Event, GovMsg, Order, Querier, StdResult, Storage, Timestamp,
+    Uint128, VoteOption,
+};
+use cw_storage_plus::{Item, Map};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
 /// Handles governance-related operations within the test environment.
 /// This trait is essential for testing contracts that interact with governance mechanisms,
 /// simulating proposals, voting, and other governance activities.
-pub trait Gov: Module<ExecT = GovMsg, QueryT = Empty, SudoT = Empty> {}
+pub trait Gov: Module<ExecT = GovMsg, QueryT = Empty, SudoT = Empty> {
+    /// Runs governance privileged actions, like submitting and tallying proposals,
+    /// see [GovSudo] for details.
+    ///
+    /// The default implementation always returns an error.
+    fn sudo_gov<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        msg: GovSudo,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let _ = (api, storage, router, block);
+        bail!("Unexpected gov sudo msg {:?}", msg)
+    }
+}
 /// A type alias for a module that accepts governance-related interactions.
 /// It's used in scenarios where you need to test how your contract interacts
 /// with governance processes and messages.
 pub type GovAcceptingModule = AcceptingModule<GovMsg, Empty, Empty>;
 
-impl Gov for GovAcceptingModule {}
+impl Gov for GovAcceptingModule {
+    /// Runs any governance privileged action, always returns a default response.
+    fn sudo_gov<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: GovSudo,
+    ) -> AnyResult<AppResponse> {
+        Ok(AppResponse::default())
+    }
+}
 /// This type alias represents a module designed to fail in response to governance operations.
 /// It's useful for testing how contracts behave when governance actions do not proceed as expected.
 pub type GovFailingModule = FailingModule<GovMsg, Empty, Empty>;
 
 impl Gov for GovFailingModule {}
+
+/// Proposals stored by the governance keeper.
+const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+
+/// Identifier of the most recently submitted proposal.
+const LAST_PROPOSAL_ID: Item<u64> = Item::new("last_proposal_id");
+
+/// Votes cast on proposals, every vote is a list of weighted options.
+const VOTES: Map<(u64, &Addr), Vec<(VoteOption, Decimal)>> = Map::new("votes");
+
+/// Voting power of accounts, accounts without voting power can vote, but their votes do not count.
+const VOTING_POWER: Map<&Addr, Uint128> = Map::new("voting_power");
+
+/// Default storage namespace for governance module.
+const NAMESPACE_GOV: &[u8] = b"gov";
+
+/// Governance privileged actions.
+#[derive(Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub enum GovSudo {
+    /// Submits a new proposal, the proposal enters the voting period immediately.
+    /// The identifier of the new proposal is returned as JSON encoded `data` of the response.
+    SubmitProposal {
+        /// Address of the account submitting the proposal.
+        proposer: String,
+        /// Title of the proposal.
+        title: String,
+        /// Messages executed with the governance module address as sender when the proposal passes.
+        messages: Vec<CosmosMsg>,
+    },
+    /// Sets the voting power of an account.
+    SetVotingPower {
+        /// Address of the voter.
+        voter: String,
+        /// New voting power of the voter.
+        power: Uint128,
+    },
+    /// Ends the voting period of the proposal and tallies the votes.
+    /// Messages of the passed proposal are executed, when any of them fails,
+    /// all their changes are reverted and the proposal is marked as failed,
+    /// the error is saved in [Proposal::failure_reason].
+    Tally {
+        /// Identifier of the proposal.
+        proposal_id: u64,
+    },
+}
+
+/// Status of the governance proposal.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    /// The proposal accepts votes.
+    VotingPeriod,
+    /// The proposal passed and all its messages were successfully executed.
+    Passed,
+    /// The proposal did not pass.
+    Rejected,
+    /// The proposal passed, but executing its messages failed.
+    Failed,
+}
+
+/// Governance proposal.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct Proposal {
+    /// Identifier of the proposal.
+    pub id: u64,
+    /// Address of the account that submitted the proposal.
+    pub proposer: Addr,
+    /// Title of the proposal.
+    pub title: String,
+    /// Messages executed when the proposal passes.
+    pub messages: Vec<CosmosMsg>,
+    /// Current status of the proposal.
+    pub status: ProposalStatus,
+    /// Time the proposal was submitted.
+    pub submit_time: Timestamp,
+    /// Error returned by the proposal messages, set when the proposal [failed](ProposalStatus::Failed).
+    pub failure_reason: Option<String>,
+}
+
+/// Voting power that voted for each option.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct TallyResult {
+    /// Voting power that voted `yes`.
+    pub yes: Uint128,
+    /// Voting power that voted `no`.
+    pub no: Uint128,
+    /// Voting power that voted `abstain`.
+    pub abstain: Uint128,
+    /// Voting power that voted `no with veto`.
+    pub no_with_veto: Uint128,
+}
+
+impl TallyResult {
+    /// Returns the voting power that voted for any option.
+    pub fn total(&self) -> Uint128 {
+        self.yes + self.no + self.abstain + self.no_with_veto
+    }
+}
+
+/// Parameters used for tallying votes, see [GovKeeper::with_tally_params].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TallyParams {
+    /// Minimum part of the total voting power that must vote for the proposal to be valid.
+    pub quorum: Decimal,
+    /// Minimum part of `yes` votes (excluding `abstain`) for the proposal to pass.
+    pub threshold: Decimal,
+    /// Minimum part of `no with veto` votes (excluding `abstain`) for the proposal to be vetoed.
+    pub veto_threshold: Decimal,
+}
+
+impl Default for TallyParams {
+    /// Returns default tally parameters used by Cosmos SDK.
+    fn default() -> Self {
+        Self {
+            quorum: Decimal::permille(334),
+            threshold: Decimal::percent(50),
+            veto_threshold: Decimal::permille(334),
+        }
+    }
+}
+
+/// A structure representing a default governance keeper.
+///
+/// Proposals are submitted and tallied using [GovSudo] messages, voting is done
+/// with [GovMsg] messages sent by voters, either users or contracts.
+/// Messages of the passed proposals are executed with the governance module address as sender,
+/// so contracts gated on governance authority can be tested end to end.
+pub struct GovKeeper {
+    /// Module address of the governance keeper, derived from the module name when `None`.
+    module_addr: Option<Addr>,
+    /// Parameters used for tallying votes.
+    tally_params: TallyParams,
+}
+
+impl Default for GovKeeper {
+    /// Creates a new governance keeper with default settings.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GovKeeper {
+    /// Creates a new governance keeper with default module address and tally parameters.
+    pub fn new() -> Self {
+        Self {
+            module_addr: None,
+            tally_params: TallyParams::default(),
+        }
+    }
+
+    /// Sets the address of the governance module, used as the sender of proposal messages.
+    ///
+    /// By default, the address of the `gov` module account is used,
+    /// the same as returned by [App::module_address](crate::App::module_address).
+    pub fn with_module_addr(mut self, module_addr: Addr) -> Self {
+        self.module_addr = Some(module_addr);
+        self
+    }
+
+    /// Sets the parameters used for tallying votes.
+    pub fn with_tally_params(mut self, tally_params: TallyParams) -> Self {
+        self.tally_params = tally_params;
+        self
+    }
+
+    /// Returns the address of the governance module.
+    pub fn module_addr(&self, api: &dyn Api) -> AnyResult<Addr> {
+        match &self.module_addr {
+            Some(module_addr) => Ok(module_addr.clone()),
+            None => module_address(api, "gov"),
+        }
+    }
+
+    /// Returns the proposal with specified identifier.
+    pub fn proposal(&self, storage: &dyn Storage, proposal_id: u64) -> AnyResult<Proposal> {
+        let gov_storage = prefixed_read(storage, NAMESPACE_GOV);
+        PROPOSALS
+            .may_load(&gov_storage, proposal_id)?
+            .ok_or_else(|| anyhow!("proposal {} does not exist", proposal_id))
+    }
+
+    /// Returns the current tally of votes cast on the proposal with specified identifier.
+    pub fn tally(&self, storage: &dyn Storage, proposal_id: u64) -> AnyResult<TallyResult> {
+        let gov_storage = prefixed_read(storage, NAMESPACE_GOV);
+        let mut result = TallyResult::default();
+        for item in VOTES
+            .prefix(proposal_id)
+            .range(&gov_storage, None, None, Order::Ascending)
+        {
+            let (voter, options) = item?;
+            let power = VOTING_POWER
+                .may_load(&gov_storage, &voter)?
+                .unwrap_or_default();
+            for (option, weight) in options {
+                let power = power.mul_floor(weight);
+                match option {
+                    VoteOption::Yes => result.yes += power,
+                    VoteOption::No => result.no += power,
+                    VoteOption::Abstain => result.abstain += power,
+                    VoteOption::NoWithVeto => result.no_with_veto += power,
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the sum of voting powers of all accounts.
+    fn total_voting_power(&self, gov_storage: &dyn Storage) -> AnyResult<Uint128> {
+        Ok(VOTING_POWER
+            .range(gov_storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, power)| power))
+            .sum::<StdResult<Uint128>>()?)
+    }
+
+    /// Returns `true` when the proposal with specified tally passes.
+    fn passes(&self, tally: &TallyResult, total_power: Uint128) -> bool {
+        let params = &self.tally_params;
+        if total_power.is_zero() || Decimal::from_ratio(tally.total(), total_power) < params.quorum
+        {
+            return false;
+        }
+        let non_abstain = tally.total() - tally.abstain;
+        if non_abstain.is_zero() {
+            return false;
+        }
+        if Decimal::from_ratio(tally.no_with_veto, non_abstain) > params.veto_threshold {
+            return false;
+        }
+        Decimal::from_ratio(tally.yes, non_abstain) > params.threshold
+    }
+
+    /// Records the vote of the voter on the proposal in voting period.
+    fn vote(
+        &self,
+        storage: &mut dyn Storage,
+        voter: &Addr,
+        proposal_id: u64,
+        options: Vec<(VoteOption, Decimal)>,
+    ) -> AnyResult<AppResponse> {
+        if self.proposal(storage, proposal_id)?.status != ProposalStatus::VotingPeriod {
+            bail!("proposal {} is not in voting period", proposal_id);
+        }
+        if options.iter().map(|(_, weight)| *weight).sum::<Decimal>() != Decimal::one() {
+            bail!("total weight of vote options must be 1");
+        }
+        let option = options
+            .iter()
+            .map(|(option, weight)| format!("option:{:?} weight:{}", option, weight))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mut gov_storage = prefixed(storage, NAMESPACE_GOV);
+        VOTES.save(&mut gov_storage, (proposal_id, voter), &options)?;
+        Ok(AppResponse {
+            events: vec![Event::new("proposal_vote")
+                .add_attribute("voter", voter)
+                .add_attribute("option", option)
+                .add_attribute("proposal_id", proposal_id.to_string())],
+            ..Default::default()
+        })
+    }
+}
+
+impl Module for GovKeeper {
+    type ExecT = GovMsg;
+    type QueryT = Empty;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        sender: Addr,
+        msg: GovMsg,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg {
+            GovMsg::Vote {
+                proposal_id,
+                option,
+            } => self.vote(
+                storage,
+                &sender,
+                proposal_id,
+                vec![(option, Decimal::one())],
+            ),
+            GovMsg::VoteWeighted {
+                proposal_id,
+                options,
+            } => {
+                let options = options
+                    .into_iter()
+                    .map(|option| (option.option, option.weight))
+                    .collect();
+                self.vote(storage, &sender, proposal_id, options)
+            }
+        }
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: Empty,
+    ) -> AnyResult<Binary> {
+        bail!("Unexpected gov query: {:?}", request)
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        msg: Empty,
+    ) -> AnyResult<AppResponse> {
+        bail!("Unexpected sudo msg {:?}", msg)
+    }
+}
+
+impl Gov for GovKeeper {
+    fn sudo_gov<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        msg: GovSudo,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        match msg {
+            GovSudo::SubmitProposal {
+                proposer,
+                title,
+                messages,
+            } => {
+                let proposer = api.addr_validate(&proposer)?;
+                let mut gov_storage = prefixed(storage, NAMESPACE_GOV);
+                let id = LAST_PROPOSAL_ID.may_load(&gov_storage)?.unwrap_or_default() + 1;
+                LAST_PROPOSAL_ID.save(&mut gov_storage, &id)?;
+                let proposal = Proposal {
+                    id,
+                    proposer,
+                    title,
+                    messages,
+                    status: ProposalStatus::VotingPeriod,
+                    submit_time: block.time,
+                    failure_reason: None,
+                };
+                PROPOSALS.save(&mut gov_storage, id, &proposal)?;
+                Ok(AppResponse {
+                    events: vec![
+                        Event::new("submit_proposal").add_attribute("proposal_id", id.to_string())
+                    ],
+                    data: Some(to_json_binary(&id)?),
+                    ..Default::default()
+                })
+            }
+            GovSudo::SetVotingPower { voter, power } => {
+                let voter = api.addr_validate(&voter)?;
+                let mut gov_storage = prefixed(storage, NAMESPACE_GOV);
+                if power.is_zero() {
+                    VOTING_POWER.remove(&mut gov_storage, &voter);
+                } else {
+                    VOTING_POWER.save(&mut gov_storage, &voter, &power)?;
+                }
+                Ok(AppResponse::default())
+            }
+            GovSudo::Tally { proposal_id } => {
+                let mut proposal = self.proposal(storage, proposal_id)?;
+                if proposal.status != ProposalStatus::VotingPeriod {
+                    bail!("proposal {} is not in voting period", proposal_id);
+                }
+                let tally = self.tally(storage, proposal_id)?;
+                let total_power =
+                    self.total_voting_power(&prefixed_read(storage, NAMESPACE_GOV))?;
+                let module_addr = self.module_addr(api)?;
+                let mut events = vec![];
+                proposal.status = if self.passes(&tally, total_power) {
+                    // execute all messages atomically, reverting all changes when any of them fails
+                    let res = transactional(storage, |write_cache, _| {
+                        proposal
+                            .messages
+                            .iter()
+                            .try_fold(vec![], |mut events, msg| {
+                                // proposal messages are stored without custom messages
+                                let msg: CosmosMsg<ExecC> = from_json(to_json_vec(msg)?)?;
+                                let res = router.execute(
+                                    api,
+                                    write_cache,
+                                    block,
+                                    module_addr.clone(),
+                                    msg,
+                                )?;
+                                events.extend(res.events);
+                                AnyResult::Ok(events)
+                            })
+                    });
+                    match res {
+                        Ok(msg_events) => {
+                            events.extend(msg_events);
+                            ProposalStatus::Passed
+                        }
+                        Err(err) => {
+                            proposal.failure_reason = Some(err.to_string());
+                            ProposalStatus::Failed
+                        }
+                    }
+                } else {
+                    ProposalStatus::Rejected
+                };
+                let result = match proposal.status {
+                    ProposalStatus::Passed => "proposal_passed",
+                    ProposalStatus::Failed => "proposal_failed",
+                    _ => "proposal_rejected",
+                };
+                let mut event = Event::new("active_proposal")
+                    .add_attribute("proposal_id", proposal_id.to_string())
+                    .add_attribute("proposal_result", result);
+                if let Some(reason) = &proposal.failure_reason {
+                    event = event.add_attribute("proposal_failure_reason", reason);
+                }
+                events.push(event);
+                let mut gov_storage = prefixed(storage, NAMESPACE_GOV);
+                PROPOSALS.save(&mut gov_storage, proposal_id, &proposal)?;
+                Ok(AppResponse {
+                    events,
+                    ..Default::default()
+                })
+            }
+        }
+    }
+}
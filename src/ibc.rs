@@ -1,5 +1,6 @@
-use crate::{AcceptingModule, FailingModule, Module};
-use cosmwasm_std::{Empty, IbcMsg, IbcQuery};
+use crate::error::AnyResult;
+use crate::{AcceptingModule, AppResponse, FailingModule, Module};
+use cosmwasm_std::{Binary, Empty, Event, IbcMsg, IbcQuery};
 ///Manages Inter-Blockchain Communication (IBC) functionalities.
 ///This trait is critical for testing contracts that involve cross-chain interactions,
 ///reflecting the interconnected nature of the Cosmos ecosystem.
@@ -10,6 +11,43 @@ pub trait Ibc: Module<ExecT = IbcMsg, QueryT = IbcQuery, SudoT = Empty> {}
 pub type IbcAcceptingModule = AcceptingModule<IbcMsg, IbcQuery, Empty>;
 
 impl Ibc for IbcAcceptingModule {}
+
+impl IbcAcceptingModule {
+    /// Registers a handler producing acknowledgement bytes and events for every
+    /// IBC message accepted by this module.
+    ///
+    /// The acknowledgement is returned as the `data` of the response,
+    /// so it is passed to the contract in the reply to a submessage.
+    /// When the handler returns an error, the message is rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::{to_json_binary, Event, IbcMsg};
+    /// use cw_multi_test::IbcAcceptingModule;
+    ///
+    /// let ibc = IbcAcceptingModule::new().with_ack_handler(|msg| match msg {
+    ///     IbcMsg::SendPacket { channel_id, .. } => Ok((
+    ///         to_json_binary(&"ack")?,
+    ///         vec![Event::new("packet_ack").add_attribute("channel", channel_id)],
+    ///     )),
+    ///     _ => Ok((Default::default(), vec![])),
+    /// });
+    /// ```
+    pub fn with_ack_handler<F>(self, handler: F) -> Self
+    where
+        F: Fn(&IbcMsg) -> AnyResult<(Binary, Vec<Event>)> + Send + Sync + 'static,
+    {
+        self.with_exec_handler(move |msg| {
+            let (ack, events) = handler(msg)?;
+            Ok(AppResponse {
+                events,
+                data: Some(ack),
+                ..Default::default()
+            })
+        })
+    }
+}
 /// Use this to test how your contract deals with problematic IBC scenarios.
 /// It's a module that deliberately fails in handling IBC messages, allowing you
 /// to check how your contract behaves in less-than-ideal cross-chain communication situations.
@@ -124,44 +124,88 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![deny(rustdoc::missing_crate_level_docs)]
 
+mod address_book;
 mod addresses;
 mod api;
 mod app;
 mod app_builder;
+mod assertions;
 mod bank;
+mod capabilities;
+mod chain_profile;
 mod checksums;
 mod contracts;
 pub mod custom_handler;
+#[cfg(feature = "cw20")]
+pub mod cw20;
 pub mod error;
+mod event_log;
 mod executor;
+mod gas;
 mod gov;
 mod ibc;
 mod module;
 mod prefixed_storage;
+mod profiler;
+mod query_handle;
+mod randomness;
+mod snapshot;
 mod staking;
 mod stargate;
+mod storage_limits;
 mod test_helpers;
 mod tests;
+pub mod token_factory;
+mod trace;
 mod transactions;
 mod wasm;
 
+pub use crate::address_book::AddressBook;
 pub use crate::addresses::{
-    AddressGenerator, IntoAddr, IntoBech32, IntoBech32m, SimpleAddressGenerator,
+    AccountGenerator, AddressGenerator, IntoAddr, IntoBech32, IntoBech32m, ModuleAccountGenerator,
+    NamedAccountGenerator, SaltedAddressGenerator, SequentialAccountGenerator,
+    SimpleAddressGenerator,
 };
 pub use crate::api::{MockApiBech32, MockApiBech32m};
 pub use crate::app::{
-    custom_app, next_block, no_init, App, BasicApp, CosmosRouter, Router, SudoMsg,
+    custom_app, next_block, no_init, App, BasicApp, BlockParams, CosmosRouter, Router, SudoMsg,
 };
 pub use crate::app_builder::{AppBuilder, BasicAppBuilder};
+pub use crate::assertions::{
+    assert_same_storage, assert_same_storage_filtered, storage_diff, StorageDiff,
+};
 pub use crate::bank::{Bank, BankKeeper, BankSudo};
+pub use crate::capabilities::{
+    Capabilities, QueryCapabilitiesRequest, QueryCapabilitiesResponse, CAPABILITIES_QUERY_PATH,
+};
+pub use crate::chain_profile::ChainProfile;
 pub use crate::checksums::ChecksumGenerator;
 pub use crate::contracts::{Contract, ContractWrapper};
-pub use crate::executor::{AppResponse, Executor};
-pub use crate::gov::{Gov, GovAcceptingModule, GovFailingModule};
+pub use crate::executor::{AppResponse, Executor, TxMetadata};
+pub use crate::gas::GasCosts;
+pub use crate::gov::{
+    Gov, GovAcceptingModule, GovFailingModule, GovKeeper, GovSudo, Proposal, ProposalStatus,
+    TallyParams, TallyResult,
+};
 pub use crate::ibc::{Ibc, IbcAcceptingModule, IbcFailingModule};
 pub use crate::module::{AcceptingModule, FailingModule, Module};
+pub use crate::profiler::{EntryPointProfile, ProfilingReport};
+pub use crate::query_handle::QueryHandle;
+pub use crate::randomness::{
+    QueryRandomnessRequest, QueryRandomnessResponse, RANDOMNESS_QUERY_PATH,
+};
+pub use crate::snapshot::AppSnapshot;
 pub use crate::staking::{
     Distribution, DistributionKeeper, StakeKeeper, Staking, StakingInfo, StakingSudo,
 };
-pub use crate::stargate::{Stargate, StargateAccepting, StargateFailing};
-pub use crate::wasm::{ContractData, Wasm, WasmKeeper, WasmSudo};
+pub use crate::stargate::{
+    Stargate, StargateAccepting, StargateFailing, StargateKeeper, StargateMsgResult,
+};
+pub use crate::storage_limits::StorageLimits;
+pub use crate::trace::{CallTrace, Trace};
+pub use crate::transactions::StorageWrite;
+pub use crate::wasm::{
+    ContractCodeHistoryEntry, ContractCodeHistoryOperation, ContractData, MsgPinCodes,
+    MsgUnpinCodes, Wasm, WasmAdminSudo, WasmKeeper, WasmSudo, MSG_PIN_CODES_TYPE_URL,
+    MSG_UNPIN_CODES_TYPE_URL,
+};
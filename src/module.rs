@@ -1,7 +1,9 @@
 use crate::app::CosmosRouter;
 use crate::error::{bail, AnyResult};
 use crate::AppResponse;
-use cosmwasm_std::{Addr, Api, Binary, BlockInfo, CustomMsg, CustomQuery, Querier, Storage};
+use cosmwasm_std::{
+    from_json, Addr, Api, Binary, BlockInfo, CustomMsg, CustomQuery, Querier, Storage,
+};
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 use std::marker::PhantomData;
@@ -62,6 +64,32 @@ pub trait Module {
     where
         ExecC: CustomMsg + DeserializeOwned + 'static,
         QueryC: CustomQuery + DeserializeOwned + 'static;
+
+    /// Runs privileged actions sent to the custom module through
+    /// [SudoMsg::Custom](crate::SudoMsg::Custom), encoded as JSON.
+    ///
+    /// The default implementation decodes the message and passes it to [sudo](Self::sudo).
+    fn sudo_custom<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        msg: Binary,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+        Self::SudoT: DeserializeOwned,
+    {
+        self.sudo(api, storage, router, block, from_json(msg)?)
+    }
+
+    /// Returns `true` when this module processes messages,
+    /// reported as enabled in [Capabilities](crate::Capabilities) of the chain.
+    fn is_enabled(&self) -> bool {
+        true
+    }
 }
 /// # Always failing module
 ///
@@ -93,6 +121,11 @@ where
     type QueryT = QueryT;
     type SudoT = SudoT;
 
+    /// Always returns `false`, the failing module is reported as disabled.
+    fn is_enabled(&self) -> bool {
+        false
+    }
+
     /// Runs any [ExecT](Self::ExecT) message, always returns an error.
     fn execute<ExecC, QueryC>(
         &self,
@@ -134,12 +167,31 @@ where
 ///
 /// This struct represents a module in the Cosmos ecosystem designed to
 /// always accept all processed messages, queries and privileged actions.
-pub struct AcceptingModule<ExecT, QueryT, SudoT>(PhantomData<(ExecT, QueryT, SudoT)>);
+pub struct AcceptingModule<ExecT, QueryT, SudoT> {
+    /// Optional handler producing responses for executed messages.
+    exec_handler: Option<ExecHandler<ExecT>>,
+    _marker: PhantomData<(ExecT, QueryT, SudoT)>,
+}
+
+/// Handler producing a response for the message executed by [AcceptingModule].
+type ExecHandler<ExecT> = Box<dyn Fn(&ExecT) -> AnyResult<AppResponse> + Send + Sync>;
 
 impl<ExecT, QueryT, SudoT> AcceptingModule<ExecT, QueryT, SudoT> {
     /// Creates an instance of an accepting module.
     pub fn new() -> Self {
-        Self(PhantomData)
+        Self {
+            exec_handler: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets the handler producing responses for executed messages.
+    pub(crate) fn with_exec_handler<F>(mut self, handler: F) -> Self
+    where
+        F: Fn(&ExecT) -> AnyResult<AppResponse> + Send + Sync + 'static,
+    {
+        self.exec_handler = Some(Box::new(handler));
+        self
     }
 }
 
@@ -160,7 +212,8 @@ where
     type QueryT = QueryT;
     type SudoT = SudoT;
 
-    /// Runs any [ExecT](Self::ExecT) message, always returns a default response.
+    /// Runs any [ExecT](Self::ExecT) message, returns a default response
+    /// or the response produced by the registered handler.
     fn execute<ExecC, QueryC>(
         &self,
         _api: &dyn Api,
@@ -168,9 +221,12 @@ where
         _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
         _block: &BlockInfo,
         _sender: Addr,
-        _msg: Self::ExecT,
+        msg: Self::ExecT,
     ) -> AnyResult<AppResponse> {
-        Ok(AppResponse::default())
+        match &self.exec_handler {
+            Some(handler) => handler(&msg),
+            None => Ok(AppResponse::default()),
+        }
     }
 
     /// Runs any [QueryT](Self::QueryT) message, always returns a default (empty) binary.
@@ -196,4 +252,16 @@ where
     ) -> AnyResult<AppResponse> {
         Ok(AppResponse::default())
     }
+
+    /// Runs any privileged action encoded as JSON, always returns a default response.
+    fn sudo_custom<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Binary,
+    ) -> AnyResult<AppResponse> {
+        Ok(AppResponse::default())
+    }
 }
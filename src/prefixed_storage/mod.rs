@@ -59,6 +59,15 @@ impl<'a> PrefixedStorage<'a> {
             prefix: to_length_prefixed_nested(namespaces),
         }
     }
+
+    /// Returns a mutable prefixed storage with specified prefix used as is,
+    /// without encoding its length.
+    pub fn raw(storage: &'a mut dyn Storage, prefix: &[u8]) -> Self {
+        PrefixedStorage {
+            storage,
+            prefix: prefix.to_vec(),
+        }
+    }
 }
 
 impl<'a> Storage for PrefixedStorage<'a> {
@@ -110,6 +119,15 @@ impl<'a> ReadonlyPrefixedStorage<'a> {
             prefix: to_length_prefixed_nested(namespaces),
         }
     }
+
+    /// Returns a read-only prefixed storage with specified prefix used as is,
+    /// without encoding its length.
+    pub fn raw(storage: &'a dyn Storage, prefix: &[u8]) -> Self {
+        ReadonlyPrefixedStorage {
+            storage,
+            prefix: prefix.to_vec(),
+        }
+    }
 }
 
 impl<'a> Storage for ReadonlyPrefixedStorage<'a> {
@@ -0,0 +1,114 @@
+//! # Profiling of contract calls executed by [WasmKeeper](crate::WasmKeeper)
+
+use cosmwasm_std::Addr;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Statistics of contract calls accumulated since profiling was enabled,
+/// see [App::profiling_report](crate::App::profiling_report).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProfilingReport {
+    /// Statistics of every called entry-point, sorted by contract address and entry-point name.
+    pub entries: Vec<EntryPointProfile>,
+}
+
+impl ProfilingReport {
+    /// Returns statistics of all entry-points of the contract with specified address.
+    pub fn contract(&self, contract: &Addr) -> Vec<&EntryPointProfile> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.contract == *contract)
+            .collect()
+    }
+
+    /// Returns statistics of entry-points sorted by the total gas consumed, the hottest first.
+    pub fn by_total_gas(&self) -> Vec<&EntryPointProfile> {
+        let mut entries: Vec<&EntryPointProfile> = self.entries.iter().collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.total_gas));
+        entries
+    }
+}
+
+impl fmt::Display for ProfilingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{} {}: calls={} total_gas={} max_gas={} total_time={:?} max_time={:?}",
+                entry.contract,
+                entry.entry_point,
+                entry.calls,
+                entry.total_gas,
+                entry.max_gas,
+                entry.total_time,
+                entry.max_time
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Statistics of calls of a single contract's entry-point.
+///
+/// Gas and time of a call include nested calls made while processing
+/// the messages returned by the entry-point.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EntryPointProfile {
+    /// Address of the called contract.
+    pub contract: Addr,
+    /// Name of the called entry-point, like `instantiate`, `execute` or `reply`.
+    pub entry_point: String,
+    /// Number of calls, including failed ones.
+    pub calls: u64,
+    /// Gas consumed by all calls. Zero when gas is not metered.
+    pub total_gas: u64,
+    /// Gas consumed by the most expensive call. Zero when gas is not metered.
+    pub max_gas: u64,
+    /// Wall time of all calls.
+    pub total_time: Duration,
+    /// Wall time of the slowest call.
+    pub max_time: Duration,
+}
+
+/// Profiler accumulating statistics of contract calls.
+#[derive(Default)]
+pub(crate) struct Profiler {
+    entries: Mutex<BTreeMap<(Addr, String), EntryPointProfile>>,
+}
+
+impl Profiler {
+    /// Records a completed call of the contract's entry-point.
+    pub fn record(&self, contract: &Addr, entry_point: &str, gas_used: u64, elapsed: Duration) {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries
+            .entry((contract.clone(), entry_point.to_string()))
+            .or_insert_with(|| EntryPointProfile {
+                contract: contract.clone(),
+                entry_point: entry_point.to_string(),
+                calls: 0,
+                total_gas: 0,
+                max_gas: 0,
+                total_time: Duration::ZERO,
+                max_time: Duration::ZERO,
+            });
+        entry.calls += 1;
+        entry.total_gas += gas_used;
+        entry.max_gas = entry.max_gas.max(gas_used);
+        entry.total_time += elapsed;
+        entry.max_time = entry.max_time.max(elapsed);
+    }
+
+    /// Returns the report of all recorded calls.
+    pub fn report(&self) -> ProfilingReport {
+        ProfilingReport {
+            entries: self.entries.lock().unwrap().values().cloned().collect(),
+        }
+    }
+
+    /// Removes all recorded statistics.
+    pub fn reset(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
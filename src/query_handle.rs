@@ -0,0 +1,222 @@
+//! # Read-only handle for querying application state from other threads
+
+use crate::app::{CosmosRouter, RouterQuerier};
+use crate::prefixed_storage::prefixed_multilevel_read;
+use cosmwasm_std::{
+    Api, BlockInfo, CustomMsg, CustomQuery, Order, QuerierWrapper, Record, Storage,
+};
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+use std::ops::Bound;
+use std::sync::{Arc, RwLock};
+
+/// Read-only copy of all key-value pairs held in application's storage.
+#[derive(Clone, Default)]
+struct SnapshotStorage {
+    records: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl SnapshotStorage {
+    /// Brings the records up to date with the provided storage,
+    /// only added, changed and removed records are copied.
+    fn update(&mut self, storage: &dyn Storage) {
+        let mut changes: Vec<(Vec<u8>, Option<Vec<u8>>)> = vec![];
+        let mut records = self.records.iter().peekable();
+        for (key, value) in storage.range(None, None, Order::Ascending) {
+            while let Some((removed, _)) = records.next_if(|(k, _)| **k < key) {
+                changes.push((removed.clone(), None));
+            }
+            match records.next_if(|(k, _)| **k == key) {
+                Some((_, current)) if *current == value => {}
+                _ => changes.push((key, Some(value))),
+            }
+        }
+        changes.extend(records.map(|(removed, _)| (removed.clone(), None)));
+        for (key, value) in changes {
+            match value {
+                Some(value) => self.records.insert(key, value),
+                None => self.records.remove(&key),
+            };
+        }
+    }
+}
+
+impl Storage for SnapshotStorage {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.records.get(key).cloned()
+    }
+
+    fn range<'a>(
+        &'a self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'a> {
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Box::new(std::iter::empty());
+            }
+        }
+        let start = start.map_or(Bound::Unbounded, |key| Bound::Included(key.to_vec()));
+        let end = end.map_or(Bound::Unbounded, |key| Bound::Excluded(key.to_vec()));
+        let iter = self
+            .records
+            .range((start, end))
+            .map(|(key, value)| (key.clone(), value.clone()));
+        match order {
+            Order::Ascending => Box::new(iter),
+            Order::Descending => Box::new(iter.rev()),
+        }
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.records.insert(key.to_vec(), value.to_vec());
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.records.remove(key);
+    }
+}
+
+/// Consistent copy of the application state taken after a committed change.
+#[derive(Clone)]
+struct Snapshot {
+    /// Copy of all key-value pairs held in application's storage.
+    storage: SnapshotStorage,
+    /// Block info at the moment the snapshot was taken.
+    block: BlockInfo,
+}
+
+/// Cloneable, read-only view of the [App](crate::App) state.
+///
+/// The handle is backed by a consistent snapshot of the application's storage,
+/// that is refreshed by the [App](crate::App) after every successfully executed transaction,
+/// sudo call and block update. The handle is [Send] and [Sync], so it can be moved
+/// to other threads, e.g. to poll the state while the main thread executes messages.
+///
+/// The snapshot is shared copy-on-write: readers keep using the snapshot they started with,
+/// and refreshing copies only the records changed since the previous refresh.
+/// Refreshing is skipped while the application holds the only handle.
+///
+/// # Example
+///
+/// ```
+/// use cw_multi_test::App;
+///
+/// let mut app = App::default();
+/// let handle = app.query_handle();
+///
+/// let height = std::thread::spawn(move || handle.block_info().height)
+///     .join()
+///     .unwrap();
+///
+/// assert_eq!(app.block_info().height, height);
+/// ```
+#[derive(Clone)]
+pub struct QueryHandle {
+    snapshot: Arc<RwLock<Arc<Snapshot>>>,
+}
+
+impl QueryHandle {
+    /// Creates a new handle with the snapshot of the provided storage and block.
+    pub(crate) fn new(storage: &dyn Storage, block: &BlockInfo) -> Self {
+        let mut snapshot = Snapshot {
+            storage: SnapshotStorage::default(),
+            block: block.clone(),
+        };
+        snapshot.storage.update(storage);
+        Self {
+            snapshot: Arc::new(RwLock::new(Arc::new(snapshot))),
+        }
+    }
+
+    /// Returns `true` when any clone of this handle is held outside of the application.
+    pub(crate) fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.snapshot) > 1
+    }
+
+    /// Refreshes the snapshot shared by all clones of this handle.
+    pub(crate) fn publish(&self, storage: &dyn Storage, block: &BlockInfo) {
+        let mut current = self.snapshot.write().unwrap();
+        // the snapshot is copied only when some reader still uses it
+        let snapshot = Arc::make_mut(&mut current);
+        snapshot.storage.update(storage);
+        snapshot.block = block.clone();
+    }
+
+    /// Returns the current snapshot.
+    fn current(&self) -> Arc<Snapshot> {
+        self.snapshot.read().unwrap().clone()
+    }
+
+    /// Returns a copy of the block info at the moment the current snapshot was taken.
+    pub fn block_info(&self) -> BlockInfo {
+        self.current().block.clone()
+    }
+
+    /// Returns the value stored under specified key in the current snapshot.
+    pub fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.current().storage.get(key)
+    }
+
+    /// Runs the provided closure with **read-only** access to the current snapshot.
+    ///
+    /// The snapshot does not change while the closure is executed,
+    /// so all reads made inside the closure are consistent.
+    pub fn read<F, T>(&self, read_fn: F) -> T
+    where
+        F: FnOnce(&dyn Storage, &BlockInfo) -> T,
+    {
+        let snapshot = self.current();
+        read_fn(&snapshot.storage, &snapshot.block)
+    }
+
+    /// Runs the provided closure with **read-only**, prefixed, multilevel storage
+    /// with specified namespaces, taken from the current snapshot.
+    pub fn read_prefixed<F, T>(&self, namespaces: &[&[u8]], read_fn: F) -> T
+    where
+        F: FnOnce(&dyn Storage) -> T,
+    {
+        let snapshot = self.current();
+        read_fn(&prefixed_multilevel_read(&snapshot.storage, namespaces))
+    }
+
+    /// Runs the provided closure with a querier answering all queries from the current snapshot,
+    /// like [App::wrap](crate::App::wrap) does for the current state,
+    /// e.g. `query_wasm_smart` or `query_balance`.
+    ///
+    /// Queries are processed by the router of the application, returned from
+    /// [App::router](crate::App::router), which holds contracts' code that is not [Send],
+    /// so this function is called on the thread owning the application.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cosmwasm_std::coin;
+    /// use cw_multi_test::App;
+    ///
+    /// let mut app = App::default();
+    /// let handle = app.query_handle();
+    /// let user = app.api().addr_make("user");
+    ///
+    /// let balance = handle.with_querier(app.router(), app.api(), |querier| {
+    ///     querier.query_balance(&user, "uatom").unwrap()
+    /// });
+    /// assert_eq!(coin(0, "uatom"), balance);
+    /// ```
+    pub fn with_querier<ExecC, QueryC, F, T>(
+        &self,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        api: &dyn Api,
+        query_fn: F,
+    ) -> T
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+        F: FnOnce(QuerierWrapper<QueryC>) -> T,
+    {
+        let snapshot = self.current();
+        let querier = RouterQuerier::new(router, api, &snapshot.storage, &snapshot.block);
+        query_fn(QuerierWrapper::new(&querier))
+    }
+}
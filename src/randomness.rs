@@ -0,0 +1,68 @@
+//! # Deterministic per-block randomness
+
+use crate::error::AnyResult;
+use anyhow::bail;
+use cosmwasm_std::{Binary, BlockInfo, Storage};
+use cw_storage_plus::Item;
+use sha2::{Digest, Sha256};
+
+/// Path of the query returning the randomness of the current block,
+/// handled by [StargateKeeper](crate::StargateKeeper).
+///
+/// The request is an empty Protobuf message, the response is [QueryRandomnessResponse].
+pub const RANDOMNESS_QUERY_PATH: &str = "/cw_multi_test.randomness.v1.Query/Randomness";
+
+/// Seed of the randomness, set with [AppBuilder::with_random_seed](crate::AppBuilder::with_random_seed)
+/// or [App::set_random_seed](crate::App::set_random_seed).
+const RANDOMNESS_SEED: Item<Binary> = Item::new("randomness_seed");
+
+/// Request of the [RANDOMNESS_QUERY_PATH] query.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryRandomnessRequest {}
+
+/// Response of the [RANDOMNESS_QUERY_PATH] query.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct QueryRandomnessResponse {
+    /// 32 bytes of randomness of the current block.
+    #[prost(bytes = "vec", tag = "1")]
+    pub randomness: Vec<u8>,
+}
+
+/// Saves the randomness seed in the storage.
+pub(crate) fn save_seed(storage: &mut dyn Storage, seed: &Binary) -> AnyResult<()> {
+    Ok(RANDOMNESS_SEED.save(storage, seed)?)
+}
+
+/// Returns the randomness of specified block, derived from the seed saved in the storage,
+/// the chain identifier, the block height and the block time.
+///
+/// The same seed always produces the same values for the same blocks,
+/// and every new block gets a new value.
+pub(crate) fn block_randomness(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+) -> AnyResult<Option<Binary>> {
+    let Some(seed) = RANDOMNESS_SEED.may_load(storage)? else {
+        return Ok(None);
+    };
+    let randomness = Sha256::new()
+        .chain_update(seed.as_slice())
+        .chain_update(block.chain_id.as_bytes())
+        .chain_update(block.height.to_be_bytes())
+        .chain_update(block.time.nanos().to_be_bytes())
+        .finalize();
+    Ok(Some(randomness.to_vec().into()))
+}
+
+/// Handles the [RANDOMNESS_QUERY_PATH] query.
+pub(crate) fn query_randomness(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+) -> AnyResult<QueryRandomnessResponse> {
+    let Some(randomness) = block_randomness(storage, block)? else {
+        bail!("Randomness seed is not set");
+    };
+    Ok(QueryRandomnessResponse {
+        randomness: randomness.to_vec(),
+    })
+}
@@ -0,0 +1,93 @@
+//! # Snapshots of the application state
+
+use crate::error::AnyResult;
+use crate::executor::AppResponse;
+use cosmwasm_std::{from_json, to_json_vec, Binary, BlockInfo, Order, Storage};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Snapshot of the application state, created with [App::snapshot](crate::App::snapshot)
+/// and restored with [App::restore](crate::App::restore).
+///
+/// The snapshot holds a copy of the whole application storage, i.e. the state of all modules
+/// (contracts, balances, staking, etc.), the block info at the moment it was taken,
+/// and the state kept by the application itself: executed transactions, the index
+/// and the gas consumed in the current block, results of end blockers, scheduled chain upgrades
+/// and the event log cursor.
+/// Contract codes are not part of the snapshot, they are kept by the [WasmKeeper](crate::WasmKeeper).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppSnapshot {
+    /// Block info at the moment the snapshot was taken.
+    block: BlockInfo,
+    /// All key-value pairs from the application storage, sorted by key.
+    storage: Vec<(Binary, Binary)>,
+    /// State kept by the application itself.
+    #[serde(default)]
+    pub(crate) app_state: AppState,
+}
+
+/// State kept by the application outside the storage, saved in [AppSnapshot].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct AppState {
+    /// Responses of executed transactions, indexed by transaction hash.
+    pub transactions: BTreeMap<String, Vec<AppResponse>>,
+    /// Index of the next transaction in the current block.
+    pub tx_index: u32,
+    /// Gas consumed by transactions executed in the current block.
+    pub block_gas_used: u64,
+    /// Results of end blockers executed at the end of the last block.
+    pub end_block_results: Vec<Result<AppResponse, String>>,
+    /// Chain identifiers scheduled to be set when the block height is reached.
+    pub chain_upgrades: BTreeMap<u64, String>,
+    /// Cursor of the event log.
+    pub event_cursor: usize,
+}
+
+impl AppSnapshot {
+    /// Copies the whole content of the storage, the block info and the application state.
+    pub(crate) fn new(storage: &dyn Storage, block: &BlockInfo, app_state: AppState) -> Self {
+        Self {
+            block: block.clone(),
+            storage: storage
+                .range(None, None, Order::Ascending)
+                .map(|(key, value)| (Binary::from(key), Binary::from(value)))
+                .collect(),
+            app_state,
+        }
+    }
+
+    /// Returns the block info saved in this snapshot.
+    pub fn block(&self) -> &BlockInfo {
+        &self.block
+    }
+
+    /// Saves this snapshot as JSON to the file at specified path.
+    ///
+    /// Together with [load](Self::load) allows to persist a fully set up application state
+    /// between test runs, so expensive setup does not have to be repeated.
+    pub fn save(&self, path: impl AsRef<Path>) -> AnyResult<()> {
+        fs::write(path, to_json_vec(self)?)?;
+        Ok(())
+    }
+
+    /// Loads a snapshot previously saved with [save](Self::save) from the file at specified path.
+    pub fn load(path: impl AsRef<Path>) -> AnyResult<Self> {
+        Ok(from_json(fs::read(path)?)?)
+    }
+
+    /// Replaces the whole content of the storage with the content saved in this snapshot.
+    pub(crate) fn restore_storage(&self, storage: &mut dyn Storage) {
+        let keys: Vec<Vec<u8>> = storage
+            .range(None, None, Order::Ascending)
+            .map(|(key, _)| key)
+            .collect();
+        for key in keys {
+            storage.remove(&key);
+        }
+        for (key, value) in &self.storage {
+            storage.set(key, value);
+        }
+    }
+}
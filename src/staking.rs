@@ -9,6 +9,13 @@ use cosmwasm_std::{
     Delegation, DelegationResponse, DistributionMsg, Empty, Event, FullDelegation, Querier,
     StakingMsg, StakingQuery, Storage, Timestamp, Uint128, Validator, ValidatorResponse,
 };
+#[cfg(feature = "cosmwasm_1_4")]
+use cosmwasm_std::{
+    DecCoin, Decimal256, DelegationRewardsResponse, DelegationTotalRewardsResponse,
+    DelegatorReward, DelegatorValidatorsResponse, Order,
+};
+#[cfg(feature = "cosmwasm_1_3")]
+use cosmwasm_std::{DelegatorWithdrawAddressResponse, DistributionQuery};
 use cw_storage_plus::{Deque, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -63,6 +70,9 @@ struct ValidatorInfo {
     stake: Uint128,
     /// The block time when this validator's rewards were last update. This is needed for rewards calculation.
     last_rewards_calculation: Timestamp,
+    /// Jailed validators are not part of the active set and do not earn rewards.
+    #[serde(default)]
+    jailed: bool,
 }
 
 impl ValidatorInfo {
@@ -71,6 +81,16 @@ impl ValidatorInfo {
             stakers: BTreeSet::new(),
             stake: Uint128::zero(),
             last_rewards_calculation: block_time,
+            jailed: false,
+        }
+    }
+
+    /// Returns the stake earning rewards, jailed validators earn no rewards.
+    pub fn rewarded_stake(&self) -> Uint128 {
+        if self.jailed {
+            Uint128::zero()
+        } else {
+            self.stake
         }
     }
 }
@@ -108,12 +128,22 @@ pub const NAMESPACE_DISTRIBUTION: &[u8] = b"distribution";
 #[derive(Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub enum StakingSudo {
     /// Slashes the given percentage of the validator's stake.
+    /// Pending unbondings from the validator are slashed too.
     /// For now, you cannot slash retrospectively in tests.
     Slash {
         /// Validator's address.
         validator: String,
         /// Percentage of the validator's stake.
         percentage: Decimal,
+        /// Flag indicating if the validator should be jailed.
+        /// Jailed validators are not returned by [StakingQuery::AllValidators]
+        /// and do not earn rewards until unjailed.
+        jail: bool,
+    },
+    /// Unjails previously jailed validator.
+    Unjail {
+        /// Validator's address.
+        validator: String,
     },
 }
 
@@ -134,10 +164,36 @@ pub trait Staking: Module<ExecT = StakingMsg, QueryT = StakingQuery, SudoT = Sta
     ) -> AnyResult<AppResponse> {
         Ok(AppResponse::default())
     }
+
+    /// Sets the staking parameters, like the bonded denomination.
+    /// Called when the application is built with a [ChainProfile](crate::ChainProfile).
+    /// If you're implementing a dummy staking module, this can be a no-op.
+    fn setup_staking_info(
+        &self,
+        _storage: &mut dyn Storage,
+        _staking_info: StakingInfo,
+    ) -> AnyResult<()> {
+        Ok(())
+    }
 }
 
 /// A trait defining a behavior of the distribution keeper.
-pub trait Distribution: Module<ExecT = DistributionMsg, QueryT = Empty, SudoT = Empty> {}
+pub trait Distribution: Module<ExecT = DistributionMsg, QueryT = Empty, SudoT = Empty> {
+    /// Processes distribution queries.
+    ///
+    /// The default implementation does not support any distribution query.
+    #[cfg(feature = "cosmwasm_1_3")]
+    fn query_distribution(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: DistributionQuery,
+    ) -> AnyResult<Binary> {
+        bail!("Unsupported distribution query: {:?}", request)
+    }
+}
 
 /// A structure representing a default stake keeper.
 pub struct StakeKeeper {
@@ -244,7 +300,7 @@ impl StakeKeeper {
             validator_info.last_rewards_calculation,
             staking_info.apr,
             validator.commission,
-            validator_info.stake,
+            validator_info.rewarded_stake(),
         );
 
         // calculate the delegator's share of those
@@ -305,7 +361,7 @@ impl StakeKeeper {
             validator_info.last_rewards_calculation,
             staking_info.apr,
             validator_obj.commission,
-            validator_info.stake,
+            validator_info.rewarded_stake(),
         );
 
         // update validator info
@@ -469,6 +525,7 @@ impl StakeKeeper {
         block: &BlockInfo,
         validator: &Addr,
         percentage: Decimal,
+        jail: bool,
     ) -> AnyResult<()> {
         // calculate rewards before slashing
         Self::update_rewards(api, staking_storage, block, validator)?;
@@ -516,6 +573,28 @@ impl StakeKeeper {
             });
         UNBONDING_QUEUE.save(staking_storage, &unbonding_queue)?;
 
+        if jail {
+            validator_info.jailed = true;
+        }
+        VALIDATOR_INFO.save(staking_storage, validator, &validator_info)?;
+        Ok(())
+    }
+
+    /// Unjails the validator, from now on the validator earns rewards again.
+    fn unjail(
+        &self,
+        api: &dyn Api,
+        staking_storage: &mut dyn Storage,
+        block: &BlockInfo,
+        validator: &Addr,
+    ) -> AnyResult<()> {
+        // move the rewards calculation time forward, no rewards are earned while jailed
+        Self::update_rewards(api, staking_storage, block, validator)?;
+        let mut validator_info = VALIDATOR_INFO.load(staking_storage, validator)?;
+        if !validator_info.jailed {
+            bail!("validator {} is not jailed", validator);
+        }
+        validator_info.jailed = false;
         VALIDATOR_INFO.save(staking_storage, validator, &validator_info)?;
         Ok(())
     }
@@ -619,6 +698,14 @@ impl Staking for StakeKeeper {
     ) -> AnyResult<AppResponse> {
         self.process_queue(api, storage, router, block)
     }
+
+    fn setup_staking_info(
+        &self,
+        storage: &mut dyn Storage,
+        staking_info: StakingInfo,
+    ) -> AnyResult<()> {
+        self.setup(storage, staking_info)
+    }
 }
 
 impl Module for StakeKeeper {
@@ -670,7 +757,10 @@ impl Module for StakeKeeper {
                     }
                     .into(),
                 )?;
-                Ok(AppResponse { events, data: None })
+                Ok(AppResponse {
+                    events,
+                    ..Default::default()
+                })
             }
             StakingMsg::Undelegate { validator, amount } => {
                 let validator = api.addr_validate(&validator)?;
@@ -706,7 +796,10 @@ impl Module for StakeKeeper {
                     payout_at: block.time.plus_seconds(staking_info.unbonding_time),
                 });
                 UNBONDING_QUEUE.save(&mut staking_storage, &unbonding_queue)?;
-                Ok(AppResponse { events, data: None })
+                Ok(AppResponse {
+                    events,
+                    ..Default::default()
+                })
             }
             StakingMsg::Redelegate {
                 src_validator,
@@ -738,7 +831,10 @@ impl Module for StakeKeeper {
                     amount,
                 )?;
 
-                Ok(AppResponse { events, data: None })
+                Ok(AppResponse {
+                    events,
+                    ..Default::default()
+                })
             }
             m => bail!("Unsupported staking message: {:?}", m),
         }
@@ -832,9 +928,20 @@ impl Module for StakeKeeper {
                 let res = to_json_binary(&full_delegation_response)?;
                 Ok(res)
             }
-            StakingQuery::AllValidators {} => Ok(to_json_binary(&AllValidatorsResponse::new(
-                self.get_validators(&staking_storage)?,
-            ))?),
+            StakingQuery::AllValidators {} => {
+                // jailed validators are not part of the active set
+                let mut validators = vec![];
+                for validator in self.get_validators(&staking_storage)? {
+                    let validator_addr = Addr::unchecked(&validator.address);
+                    if !VALIDATOR_INFO
+                        .load(&staking_storage, &validator_addr)?
+                        .jailed
+                    {
+                        validators.push(validator);
+                    }
+                }
+                Ok(to_json_binary(&AllValidatorsResponse::new(validators))?)
+            }
             StakingQuery::Validator { address } => Ok(to_json_binary(&ValidatorResponse::new(
                 self.get_validator(&staking_storage, &Addr::unchecked(address))?,
             ))?),
@@ -854,12 +961,35 @@ impl Module for StakeKeeper {
             StakingSudo::Slash {
                 validator,
                 percentage,
+                jail,
             } => {
                 let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
                 let validator = api.addr_validate(&validator)?;
                 self.validate_percentage(percentage)?;
-                self.slash(api, &mut staking_storage, block, &validator, percentage)?;
-                Ok(AppResponse::default())
+                self.slash(
+                    api,
+                    &mut staking_storage,
+                    block,
+                    &validator,
+                    percentage,
+                    jail,
+                )?;
+                Ok(AppResponse {
+                    events: vec![Event::new("slash")
+                        .add_attribute("address", &validator)
+                        .add_attribute("fraction", percentage.to_string())
+                        .add_attribute("jailed", jail.to_string())],
+                    ..Default::default()
+                })
+            }
+            StakingSudo::Unjail { validator } => {
+                let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
+                let validator = api.addr_validate(&validator)?;
+                self.unjail(api, &mut staking_storage, block, &validator)?;
+                Ok(AppResponse {
+                    events: vec![Event::new("unjail").add_attribute("address", validator)],
+                    ..Default::default()
+                })
             }
         }
     }
@@ -911,6 +1041,47 @@ impl DistributionKeeper {
         })
     }
 
+    /// Returns pending rewards of the delegator for every validator the delegator has stake with.
+    #[cfg(feature = "cosmwasm_1_4")]
+    fn get_delegator_rewards(
+        storage: &dyn Storage,
+        block: &BlockInfo,
+        delegator: &Addr,
+    ) -> AnyResult<Vec<(Addr, Coin)>> {
+        let staking_storage = prefixed_read(storage, NAMESPACE_STAKING);
+        STAKES
+            .prefix(delegator)
+            .range(&staking_storage, None, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((_, shares)) if shares.stake.is_zero()))
+            .map(|item| {
+                let (validator, shares) = item?;
+                let validator_obj = VALIDATOR_MAP.load(&staking_storage, &validator)?;
+                let validator_info = VALIDATOR_INFO.load(&staking_storage, &validator)?;
+                let reward = StakeKeeper::get_rewards_internal(
+                    &staking_storage,
+                    block,
+                    &shares,
+                    &validator_obj,
+                    &validator_info,
+                )?;
+                Ok((validator, reward))
+            })
+            .collect()
+    }
+
+    /// Converts a reward into decimal coins, zero rewards are omitted.
+    #[cfg(feature = "cosmwasm_1_4")]
+    fn to_dec_coins(reward: &Coin) -> Vec<DecCoin> {
+        if reward.amount.is_zero() {
+            vec![]
+        } else {
+            vec![DecCoin::new(
+                Decimal256::from_ratio(reward.amount, 1u128),
+                &reward.denom,
+            )]
+        }
+    }
+
     /// Sets (changes) the [withdraw address] of the delegator.
     ///
     /// [withdraw address]: https://docs.cosmos.network/main/modules/distribution#msgsetwithdrawaddress
@@ -932,7 +1103,85 @@ impl DistributionKeeper {
     }
 }
 
-impl Distribution for DistributionKeeper {}
+impl Distribution for DistributionKeeper {
+    #[cfg(feature = "cosmwasm_1_3")]
+    #[cfg_attr(not(feature = "cosmwasm_1_4"), allow(unused_variables))]
+    fn query_distribution(
+        &self,
+        api: &dyn Api,
+        storage: &dyn Storage,
+        _querier: &dyn Querier,
+        block: &BlockInfo,
+        request: DistributionQuery,
+    ) -> AnyResult<Binary> {
+        match request {
+            DistributionQuery::DelegatorWithdrawAddress { delegator_address } => {
+                let delegator = api.addr_validate(&delegator_address)?;
+                let distribution_storage = prefixed_read(storage, NAMESPACE_DISTRIBUTION);
+                let withdraw_address =
+                    Self::get_withdraw_address(&distribution_storage, &delegator)?;
+                Ok(to_json_binary(&DelegatorWithdrawAddressResponse::new(
+                    withdraw_address,
+                ))?)
+            }
+            #[cfg(feature = "cosmwasm_1_4")]
+            DistributionQuery::DelegationRewards {
+                delegator_address,
+                validator_address,
+            } => {
+                let delegator = api.addr_validate(&delegator_address)?;
+                let rewards = Self::get_delegator_rewards(storage, block, &delegator)?;
+                let Some((_, reward)) = rewards
+                    .iter()
+                    .find(|(validator, _)| validator.as_str() == validator_address)
+                else {
+                    bail!(
+                        "no delegation for ({}, {}) tuple",
+                        delegator_address,
+                        validator_address
+                    )
+                };
+                Ok(to_json_binary(&DelegationRewardsResponse::new(
+                    Self::to_dec_coins(reward),
+                ))?)
+            }
+            #[cfg(feature = "cosmwasm_1_4")]
+            DistributionQuery::DelegationTotalRewards { delegator_address } => {
+                let delegator = api.addr_validate(&delegator_address)?;
+                let rewards = Self::get_delegator_rewards(storage, block, &delegator)?;
+                let total = rewards
+                    .iter()
+                    .map(|(_, reward)| reward.amount)
+                    .sum::<Uint128>();
+                let total = match rewards.first() {
+                    Some((_, reward)) => Self::to_dec_coins(&coin(total.u128(), &reward.denom)),
+                    None => vec![],
+                };
+                let rewards = rewards
+                    .iter()
+                    .map(|(validator, reward)| {
+                        DelegatorReward::new(validator.to_string(), Self::to_dec_coins(reward))
+                    })
+                    .collect();
+                Ok(to_json_binary(&DelegationTotalRewardsResponse::new(
+                    rewards, total,
+                ))?)
+            }
+            #[cfg(feature = "cosmwasm_1_4")]
+            DistributionQuery::DelegatorValidators { delegator_address } => {
+                let delegator = api.addr_validate(&delegator_address)?;
+                let validators = Self::get_delegator_rewards(storage, block, &delegator)?
+                    .into_iter()
+                    .map(|(validator, _)| validator.into_string())
+                    .collect();
+                Ok(to_json_binary(&DelegatorValidatorsResponse::new(
+                    validators,
+                ))?)
+            }
+            q => bail!("Unsupported distribution query: {:?}", q),
+        }
+    }
+}
 
 impl Module for DistributionKeeper {
     type ExecT = DistributionMsg;
@@ -980,7 +1229,10 @@ impl Module for DistributionKeeper {
                         "amount",
                         format!("{}{}", rewards, staking_info.bonded_denom),
                     )];
-                Ok(AppResponse { events, data: None })
+                Ok(AppResponse {
+                    events,
+                    ..Default::default()
+                })
             }
             DistributionMsg::SetWithdrawAddress { address } => {
                 let address = api.addr_validate(&address)?;
@@ -988,10 +1240,10 @@ impl Module for DistributionKeeper {
                 let storage = &mut prefixed(storage, NAMESPACE_DISTRIBUTION);
                 Self::set_withdraw_address(storage, &sender, &address)?;
                 Ok(AppResponse {
-                    data: None,
                     // https://github.com/cosmos/cosmos-sdk/blob/4f6f6c00021f4b5ee486bbb71ae2071a8ceb47c9/x/distribution/keeper/keeper.go#L74
                     events: vec![Event::new("set_withdraw_address")
                         .add_attribute("withdraw_address", address)],
+                    ..Default::default()
                 })
             }
             m => bail!("Unsupported distribution message: {:?}", m),
@@ -1190,6 +1442,7 @@ mod test {
                 StakingSudo::Slash {
                     validator: validator_addr.to_string(),
                     percentage: Decimal::percent(50),
+                    jail: false,
                 },
             )
             .unwrap();
@@ -1215,6 +1468,7 @@ mod test {
                 StakingSudo::Slash {
                     validator: validator_addr.to_string(),
                     percentage: Decimal::percent(100),
+                    jail: false,
                 },
             )
             .unwrap();
@@ -1902,6 +2156,7 @@ mod test {
                     StakingSudo::Slash {
                         validator: non_existing_validator.to_string(),
                         percentage: Decimal::percent(50),
+                        jail: false,
                     },
                 )
                 .unwrap_err();
@@ -2440,6 +2695,7 @@ mod test {
                     StakingSudo::Slash {
                         validator: validator.to_string(),
                         percentage: Decimal::percent(50),
+                        jail: false,
                     },
                 )
                 .unwrap();
@@ -2479,6 +2735,99 @@ mod test {
             assert_eq!(balance.amount.u128(), 55);
         }
 
+        #[test]
+        fn jailed_validator_earns_no_rewards() {
+            let (mut test_env, validator) =
+                TestEnv::wrap(setup_test_env(Decimal::percent(10), Decimal::zero()));
+            let delegator = test_env.api.addr_make("delegator");
+            test_env
+                .router
+                .bank
+                .init_balance(&mut test_env.store, &delegator, vec![coin(100, "TOKEN")])
+                .unwrap();
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Delegate {
+                    validator: validator.to_string(),
+                    amount: coin(100, "TOKEN"),
+                },
+            )
+            .unwrap();
+
+            // slash and jail the validator
+            let sudo = |test_env: &mut TestEnv, msg: StakingSudo| {
+                test_env.router.staking.sudo(
+                    &test_env.api,
+                    &mut test_env.store,
+                    &test_env.router,
+                    &test_env.block,
+                    msg,
+                )
+            };
+            let res = sudo(
+                &mut test_env,
+                StakingSudo::Slash {
+                    validator: validator.to_string(),
+                    percentage: Decimal::percent(50),
+                    jail: true,
+                },
+            )
+            .unwrap();
+            assert_eq!("true", res.events[0].attributes[2].value);
+
+            // jailed validator is not in the active set, but delegations are kept
+            let response: AllValidatorsResponse =
+                query_stake(&test_env, StakingQuery::AllValidators {}).unwrap();
+            assert!(response.validators.is_empty());
+            let response: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(coin(50, "TOKEN"), response.delegations[0].amount);
+
+            // no rewards while jailed
+            const YEAR: u64 = 60 * 60 * 24 * 365;
+            test_env.block.time = test_env.block.time.plus_seconds(YEAR);
+            let rewards = test_env
+                .router
+                .staking
+                .get_rewards(&test_env.store, &test_env.block, &delegator, &validator)
+                .unwrap()
+                .unwrap();
+            assert!(rewards.amount.is_zero());
+
+            // unjailed validator earns rewards again
+            sudo(
+                &mut test_env,
+                StakingSudo::Unjail {
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+            sudo(
+                &mut test_env,
+                StakingSudo::Unjail {
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap_err();
+            let response: AllValidatorsResponse =
+                query_stake(&test_env, StakingQuery::AllValidators {}).unwrap();
+            assert_eq!(1, response.validators.len());
+            test_env.block.time = test_env.block.time.plus_seconds(YEAR);
+            let rewards = test_env
+                .router
+                .staking
+                .get_rewards(&test_env.store, &test_env.block, &delegator, &validator)
+                .unwrap()
+                .unwrap();
+            assert_eq!(5, rewards.amount.u128()); // 10% of 50
+        }
+
         #[test]
         fn rewards_initial_wait() {
             let (mut test_env, validator) =
@@ -2524,5 +2873,101 @@ mod test {
                 vec![coin(10, "TOKEN")] // 10% of 100
             );
         }
+
+        #[test]
+        #[cfg(feature = "cosmwasm_1_4")]
+        fn distribution_queries() {
+            let (mut test_env, validator1) =
+                TestEnv::wrap(setup_test_env(Decimal::percent(10), Decimal::percent(10)));
+            let delegator = test_env.api.addr_make("delegator");
+            let reward_receiver = test_env.api.addr_make("rewardreceiver");
+            let validator2 = test_env.api.addr_make("validator2");
+
+            test_env
+                .router
+                .bank
+                .init_balance(&mut test_env.store, &delegator, vec![coin(1000, "TOKEN")])
+                .unwrap();
+            test_env
+                .router
+                .staking
+                .add_validator(
+                    &test_env.api,
+                    &mut test_env.store,
+                    &test_env.block,
+                    Validator::new(
+                        validator2.to_string(),
+                        Decimal::zero(),
+                        Decimal::percent(20),
+                        Decimal::percent(1),
+                    ),
+                )
+                .unwrap();
+
+            // delegate to both validators
+            for validator in [&validator1, &validator2] {
+                execute_stake(
+                    &mut test_env,
+                    delegator.clone(),
+                    StakingMsg::Delegate {
+                        validator: validator.to_string(),
+                        amount: coin(100, "TOKEN"),
+                    },
+                )
+                .unwrap();
+            }
+            execute_distr(
+                &mut test_env,
+                delegator.clone(),
+                DistributionMsg::SetWithdrawAddress {
+                    address: reward_receiver.to_string(),
+                },
+            )
+            .unwrap();
+
+            // wait a year
+            test_env.block.time = test_env.block.time.plus_seconds(60 * 60 * 24 * 365);
+
+            let querier = test_env
+                .router
+                .querier(&test_env.api, &test_env.store, &test_env.block);
+            let querier = QuerierWrapper::<Empty>::new(&querier);
+
+            assert_eq!(
+                reward_receiver,
+                querier
+                    .query_delegator_withdraw_address(&delegator)
+                    .unwrap()
+            );
+            let mut validators = vec![validator1.to_string(), validator2.to_string()];
+            validators.sort();
+            assert_eq!(
+                validators,
+                querier.query_delegator_validators(&delegator).unwrap()
+            );
+            // one year, 10% apr, 10% commission, 100 tokens staked
+            assert_eq!(
+                vec![DecCoin::new(Decimal256::from_ratio(9u128, 1u128), "TOKEN")],
+                querier
+                    .query_delegation_rewards(&delegator, &validator1)
+                    .unwrap()
+            );
+            let total = querier.query_delegation_total_rewards(&delegator).unwrap();
+            assert_eq!(2, total.rewards.len());
+            assert_eq!(
+                vec![DecCoin::new(Decimal256::from_ratio(19u128, 1u128), "TOKEN")],
+                total.total
+            );
+
+            // querying rewards of a non-existing delegation fails
+            let stranger = test_env.api.addr_make("stranger");
+            querier
+                .query_delegation_rewards(&stranger, &validator1)
+                .unwrap_err();
+            assert!(querier
+                .query_delegator_validators(&stranger)
+                .unwrap()
+                .is_empty());
+        }
     }
 }
@@ -1,13 +1,19 @@
 //! # Handler for `CosmosMsg::Stargate`, `CosmosMsg::Any`, `QueryRequest::Stargate` and `QueryRequest::Grpc` messages
 
+use crate::bank;
 use crate::error::AnyResult;
-use crate::{AppResponse, CosmosRouter};
+use crate::randomness::{query_randomness, QueryRandomnessRequest, RANDOMNESS_QUERY_PATH};
+use crate::token_factory::ProtoCoin;
+use crate::{AppResponse, CosmosRouter, SudoMsg};
 use anyhow::bail;
 use cosmwasm_std::{
-    to_json_binary, Addr, AnyMsg, Api, Binary, BlockInfo, CustomMsg, CustomQuery, Empty, GrpcQuery,
-    Querier, Storage,
+    from_json, to_json_binary, to_json_vec, Addr, AnyMsg, Api, BankMsg, Binary, BlockInfo, Coin,
+    Coins, CosmosMsg, CustomMsg, CustomQuery, DistributionMsg, Empty, Event, GrpcQuery,
+    PageRequest, Querier, StakingMsg, Storage, Uint128,
 };
+use prost::Message;
 use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
 
 /// Interface of handlers for processing `Stargate`/`Any` message variants
 /// and `Stargate`/`Grpc` queries.
@@ -76,12 +82,22 @@ pub trait Stargate {
     ) -> AnyResult<Binary> {
         bail!("Unexpected grpc query: request={:?}", request)
     }
+
+    /// Returns `true` when this handler processes messages,
+    /// reported as enabled in [Capabilities](crate::Capabilities) of the chain.
+    fn is_enabled(&self) -> bool {
+        true
+    }
 }
 
 /// Always failing handler for `Stargate`/`Any` message variants and `Stargate`/`Grpc` queries.
 pub struct StargateFailing;
 
-impl Stargate for StargateFailing {}
+impl Stargate for StargateFailing {
+    fn is_enabled(&self) -> bool {
+        false
+    }
+}
 
 /// Always accepting handler for `Stargate`/`Any` message variants and `Stargate`/`Grpc` queries.
 pub struct StargateAccepting;
@@ -143,3 +159,591 @@ impl Stargate for StargateAccepting {
         Ok(Binary::default())
     }
 }
+
+/// Result of processing a stargate message by a handler registered in [StargateKeeper].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct StargateMsgResult {
+    /// Messages executed on behalf of the sender after the handler returns,
+    /// like `BankMsg::Send` for `MsgSend`.
+    pub messages: Vec<CosmosMsg>,
+    /// Privileged actions executed after the messages,
+    /// like minting tokens of a token factory denomination.
+    pub sudo_messages: Vec<SudoMsg>,
+    /// Events emitted by the handler.
+    pub events: Vec<Event>,
+    /// Data returned by the handler.
+    pub data: Option<Binary>,
+}
+
+impl StargateMsgResult {
+    /// Creates a result executing specified message on behalf of the sender.
+    pub fn message(msg: impl Into<CosmosMsg>) -> Self {
+        Self {
+            messages: vec![msg.into()],
+            ..Default::default()
+        }
+    }
+}
+
+/// Type-erased handler of a stargate message, the value is Protobuf encoded.
+type StargateMsgHandler = Box<
+    dyn Fn(&dyn Api, &mut dyn Storage, &BlockInfo, &Addr, &[u8]) -> AnyResult<StargateMsgResult>,
+>;
+
+/// Type-erased handler of a stargate query, the request and the response are Protobuf encoded.
+type StargateQueryHandler =
+    Box<dyn Fn(&dyn Storage, &dyn Querier, &BlockInfo, &[u8]) -> AnyResult<Binary>>;
+
+/// Handler for `Stargate`/`Any` message variants and `Stargate`/`Grpc` queries,
+/// dispatching messages to handlers registered per type URL
+/// and queries to handlers registered per query path.
+///
+/// Messages are decoded with [prost] into the type expected by the handler.
+/// Messages returned by the handler are executed on behalf of the sender,
+/// so handlers may be used to route stargate messages to bank, staking or any other module.
+/// By default, [StargateKeeper] handles these messages:
+/// - `/cosmos.bank.v1beta1.MsgSend` as `BankMsg::Send`,
+/// - `/cosmos.bank.v1beta1.MsgMultiSend` as `BankMsg::Send` for every output,
+/// - `/cosmos.staking.v1beta1.MsgDelegate` as `StakingMsg::Delegate`,
+/// - `/cosmos.staking.v1beta1.MsgUndelegate` as `StakingMsg::Undelegate`,
+/// - `/cosmos.staking.v1beta1.MsgBeginRedelegate` as `StakingMsg::Redelegate`,
+/// - `/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward` as `DistributionMsg::WithdrawDelegatorReward`.
+///
+/// Messages with other type URLs are rejected.
+///
+/// Query requests are decoded and responses are encoded with [prost],
+/// for both `QueryRequest::Stargate` and `QueryRequest::Grpc` queries.
+/// By default, [StargateKeeper] handles the [RANDOMNESS_QUERY_PATH] query
+/// and `/cosmos.bank.v1beta1.Query/TotalSupply` query returning the supply of all denominations.
+/// Queries with paths without registered handlers are rejected.
+///
+/// Messages and queries of the `x/tokenfactory` module are handled after
+/// calling [with_token_factory](StargateKeeper::with_token_factory),
+/// see [token_factory](crate::token_factory) module for details.
+///
+/// # Example
+///
+/// ```
+/// use cosmwasm_std::{AnyMsg, Binary, CosmosMsg, Event};
+/// use cw_multi_test::{no_init, AppBuilder, Executor, StargateKeeper, StargateMsgResult};
+///
+/// #[derive(Clone, PartialEq, prost::Message)]
+/// struct MsgSwap {
+///     #[prost(string, tag = "1")]
+///     sender: String,
+/// }
+///
+/// let stargate_keeper = StargateKeeper::new().with_msg_handler(
+///     "/osmosis.gamm.v1beta1.MsgSwapExactAmountIn",
+///     |_storage, _block, _sender, msg: MsgSwap| {
+///         Ok(StargateMsgResult {
+///             events: vec![Event::new("swap").add_attribute("sender", msg.sender)],
+///             ..Default::default()
+///         })
+///     },
+/// );
+///
+/// let mut app = AppBuilder::default().with_stargate(stargate_keeper).build(no_init);
+///
+/// let sender = app.api().addr_make("sender");
+/// let msg = CosmosMsg::Any(AnyMsg {
+///     type_url: "/osmosis.gamm.v1beta1.MsgSwapExactAmountIn".to_string(),
+///     value: Binary::from(prost::Message::encode_to_vec(&MsgSwap { sender: sender.to_string() })),
+/// });
+/// let res = app.execute(sender, msg).unwrap();
+/// assert!(res.has_event(&Event::new("swap").add_attribute("sender", app.api().addr_make("sender"))));
+/// ```
+pub struct StargateKeeper {
+    /// Registered message handlers, keyed by type URL.
+    msg_handlers: BTreeMap<String, StargateMsgHandler>,
+    /// Registered query handlers, keyed by query path.
+    query_handlers: BTreeMap<String, StargateQueryHandler>,
+}
+
+impl Default for StargateKeeper {
+    /// Creates a keeper with default message handlers, see [StargateKeeper] for details.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StargateKeeper {
+    /// Creates a keeper with default message handlers, see [StargateKeeper] for details.
+    pub fn new() -> Self {
+        Self {
+            msg_handlers: BTreeMap::new(),
+            query_handlers: BTreeMap::new(),
+        }
+        .with_msg_handler(
+            "/cosmos.bank.v1beta1.MsgSend",
+            |_, _, sender, msg: MsgSend| {
+                check_sender(sender, &msg.from_address)?;
+                Ok(StargateMsgResult::message(BankMsg::Send {
+                    to_address: msg.to_address,
+                    amount: proto_coins(msg.amount)?,
+                }))
+            },
+        )
+        .with_msg_handler(
+            "/cosmos.bank.v1beta1.MsgMultiSend",
+            |_, _, sender, msg: MsgMultiSend| {
+                let [input] = msg.inputs.as_slice() else {
+                    bail!("multi send must have exactly one input");
+                };
+                check_sender(sender, &input.address)?;
+                let mut total = Coins::default();
+                let mut messages = vec![];
+                for output in msg.outputs {
+                    let amount = proto_coins(output.coins)?;
+                    for coin in &amount {
+                        total.add(coin.clone())?;
+                    }
+                    messages.push(
+                        BankMsg::Send {
+                            to_address: output.address,
+                            amount,
+                        }
+                        .into(),
+                    );
+                }
+                if total != Coins::try_from(proto_coins(input.coins.clone())?)? {
+                    bail!("sum of inputs and outputs of multi send do not match");
+                }
+                Ok(StargateMsgResult {
+                    messages,
+                    ..Default::default()
+                })
+            },
+        )
+        .with_msg_handler(
+            "/cosmos.staking.v1beta1.MsgDelegate",
+            |_, _, sender, msg: MsgDelegate| {
+                check_sender(sender, &msg.delegator_address)?;
+                Ok(StargateMsgResult::message(StakingMsg::Delegate {
+                    validator: msg.validator_address,
+                    amount: proto_coin(msg.amount)?,
+                }))
+            },
+        )
+        .with_msg_handler(
+            "/cosmos.staking.v1beta1.MsgUndelegate",
+            |_, _, sender, msg: MsgDelegate| {
+                check_sender(sender, &msg.delegator_address)?;
+                Ok(StargateMsgResult::message(StakingMsg::Undelegate {
+                    validator: msg.validator_address,
+                    amount: proto_coin(msg.amount)?,
+                }))
+            },
+        )
+        .with_msg_handler(
+            "/cosmos.staking.v1beta1.MsgBeginRedelegate",
+            |_, _, sender, msg: MsgBeginRedelegate| {
+                check_sender(sender, &msg.delegator_address)?;
+                Ok(StargateMsgResult::message(StakingMsg::Redelegate {
+                    src_validator: msg.validator_src_address,
+                    dst_validator: msg.validator_dst_address,
+                    amount: proto_coin(msg.amount)?,
+                }))
+            },
+        )
+        .with_msg_handler(
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward",
+            |_, _, sender, msg: MsgWithdrawDelegatorReward| {
+                check_sender(sender, &msg.delegator_address)?;
+                Ok(StargateMsgResult::message(
+                    DistributionMsg::WithdrawDelegatorReward {
+                        validator: msg.validator_address,
+                    },
+                ))
+            },
+        )
+        .with_query_handler(
+            "/cosmos.bank.v1beta1.Query/TotalSupply",
+            |storage, _, _, req: QueryTotalSupplyRequest| {
+                let pagination = req.pagination.map(|page| PageRequest {
+                    key: (!page.key.is_empty()).then(|| Binary::from(page.key)),
+                    limit: page.limit as u32,
+                    reverse: page.reverse,
+                });
+                let (supply, next_key) = bank::total_supply(storage, pagination)?;
+                Ok(QueryTotalSupplyResponse {
+                    supply: supply
+                        .into_iter()
+                        .map(|coin| ProtoCoin {
+                            denom: coin.denom,
+                            amount: coin.amount.to_string(),
+                        })
+                        .collect(),
+                    pagination: Some(PageResponse {
+                        next_key: next_key.map(Into::into).unwrap_or_default(),
+                        total: 0,
+                    }),
+                })
+            },
+        )
+        .with_query_handler(
+            RANDOMNESS_QUERY_PATH,
+            |storage, _, block, _: QueryRandomnessRequest| query_randomness(storage, block),
+        )
+    }
+
+    /// Registers a handler of messages with specified type URL, replacing the handler
+    /// registered before for the same type URL. The handler receives the application storage,
+    /// the current block, the sender and the decoded message.
+    pub fn with_msg_handler<M, F>(self, type_url: &str, handler: F) -> Self
+    where
+        M: Message + Default + 'static,
+        F: Fn(&mut dyn Storage, &BlockInfo, &Addr, M) -> AnyResult<StargateMsgResult> + 'static,
+    {
+        self.with_api_msg_handler(type_url, move |_, storage, block, sender, msg| {
+            handler(storage, block, sender, msg)
+        })
+    }
+
+    /// Registers a handler of messages with specified type URL,
+    /// like [with_msg_handler](Self::with_msg_handler) does,
+    /// the handler receives also the API, e.g. to validate addresses.
+    pub(crate) fn with_api_msg_handler<M, F>(mut self, type_url: &str, handler: F) -> Self
+    where
+        M: Message + Default + 'static,
+        F: Fn(&dyn Api, &mut dyn Storage, &BlockInfo, &Addr, M) -> AnyResult<StargateMsgResult>
+            + 'static,
+    {
+        self.msg_handlers.insert(
+            type_url.to_string(),
+            Box::new(move |api, storage, block, sender, value| {
+                let msg = M::decode(value)?;
+                handler(api, storage, block, sender, msg)
+            }),
+        );
+        self
+    }
+
+    /// Registers a handler of queries with specified path (like `/cosmos.bank.v1beta1.Query/Balance`),
+    /// replacing the handler registered before for the same path. The handler receives
+    /// the application storage, the querier, the current block and the decoded request.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::StargateKeeper;
+    ///
+    /// #[derive(Clone, PartialEq, prost::Message)]
+    /// struct QueryPriceRequest {
+    ///     #[prost(string, tag = "1")]
+    ///     denom: String,
+    /// }
+    ///
+    /// #[derive(Clone, PartialEq, prost::Message)]
+    /// struct QueryPriceResponse {
+    ///     #[prost(string, tag = "1")]
+    ///     price: String,
+    /// }
+    ///
+    /// let stargate_keeper = StargateKeeper::new().with_query_handler(
+    ///     "/oracle.v1.Query/Price",
+    ///     |_storage, _querier, _block, _request: QueryPriceRequest| {
+    ///         Ok(QueryPriceResponse { price: "1.5".to_string() })
+    ///     },
+    /// );
+    /// ```
+    pub fn with_query_handler<Q, R, F>(mut self, path: &str, handler: F) -> Self
+    where
+        Q: Message + Default + 'static,
+        R: Message + 'static,
+        F: Fn(&dyn Storage, &dyn Querier, &BlockInfo, Q) -> AnyResult<R> + 'static,
+    {
+        self.query_handlers.insert(
+            path.to_string(),
+            Box::new(move |storage, querier, block, data| {
+                let request = Q::decode(data)?;
+                let response = handler(storage, querier, block, request)?;
+                Ok(response.encode_to_vec().into())
+            }),
+        );
+        self
+    }
+
+    /// Processes the query with specified path and Protobuf encoded request.
+    fn query(
+        &self,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        block: &BlockInfo,
+        path: &str,
+        data: &[u8],
+    ) -> AnyResult<Binary> {
+        let Some(handler) = self.query_handlers.get(path) else {
+            bail!("Unexpected stargate query: path={}", path);
+        };
+        handler(storage, querier, block, data)
+    }
+
+    /// Processes the message with specified type URL and Protobuf encoded value.
+    fn execute_msg<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        type_url: &str,
+        value: &[u8],
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        let Some(handler) = self.msg_handlers.get(type_url) else {
+            bail!("Unexpected stargate message: type_url={}", type_url);
+        };
+        let StargateMsgResult {
+            messages,
+            sudo_messages,
+            mut events,
+            data,
+        } = handler(api, storage, block, &sender, value)?;
+        for msg in messages {
+            // standard messages are the same for all custom message types
+            let msg: CosmosMsg<ExecC> = from_json(to_json_vec(&msg)?)?;
+            let res = router.execute(api, storage, block, sender.clone(), msg)?;
+            events.extend(res.events);
+        }
+        for msg in sudo_messages {
+            let res = router.sudo(api, storage, block, msg)?;
+            events.extend(res.events);
+        }
+        Ok(AppResponse {
+            events,
+            data,
+            ..Default::default()
+        })
+    }
+}
+
+impl Stargate for StargateKeeper {
+    fn execute_stargate<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        type_url: String,
+        value: Binary,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        self.execute_msg(api, storage, router, block, sender, &type_url, &value)
+    }
+
+    fn execute_any<ExecC, QueryC>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: AnyMsg,
+    ) -> AnyResult<AppResponse>
+    where
+        ExecC: CustomMsg + DeserializeOwned + 'static,
+        QueryC: CustomQuery + DeserializeOwned + 'static,
+    {
+        self.execute_msg(
+            api,
+            storage,
+            router,
+            block,
+            sender,
+            &msg.type_url,
+            &msg.value,
+        )
+    }
+
+    fn query_stargate(
+        &self,
+        _api: &dyn Api,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        block: &BlockInfo,
+        path: String,
+        data: Binary,
+    ) -> AnyResult<Binary> {
+        self.query(storage, querier, block, &path, &data)
+    }
+
+    fn query_grpc(
+        &self,
+        _api: &dyn Api,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        block: &BlockInfo,
+        request: GrpcQuery,
+    ) -> AnyResult<Binary> {
+        self.query(storage, querier, block, &request.path, &request.data)
+    }
+}
+
+/// Fails when the signer of the message is not the sender.
+fn check_sender(sender: &Addr, signer: &str) -> AnyResult<()> {
+    if sender.as_str() != signer {
+        bail!("Message signer {} is not the sender {}", signer, sender);
+    }
+    Ok(())
+}
+
+/// Converts Protobuf encoded coin.
+fn proto_coin(coin: Option<ProtoCoin>) -> AnyResult<Coin> {
+    let Some(ProtoCoin { denom, amount }) = coin else {
+        bail!("Missing coin");
+    };
+    Ok(Coin::new(amount.parse::<Uint128>()?, denom))
+}
+
+/// Converts Protobuf encoded coins.
+fn proto_coins(coins: Vec<ProtoCoin>) -> AnyResult<Vec<Coin>> {
+    coins
+        .into_iter()
+        .map(|coin| proto_coin(Some(coin)))
+        .collect()
+}
+
+/// Protobuf encoded `cosmos.bank.v1beta1.MsgSend`.
+#[derive(Clone, PartialEq, Message)]
+struct MsgSend {
+    #[prost(string, tag = "1")]
+    from_address: String,
+    #[prost(string, tag = "2")]
+    to_address: String,
+    #[prost(message, repeated, tag = "3")]
+    amount: Vec<ProtoCoin>,
+}
+
+/// Protobuf encoded `cosmos.bank.v1beta1.MsgMultiSend`.
+#[derive(Clone, PartialEq, Message)]
+struct MsgMultiSend {
+    #[prost(message, repeated, tag = "1")]
+    inputs: Vec<BankIo>,
+    #[prost(message, repeated, tag = "2")]
+    outputs: Vec<BankIo>,
+}
+
+/// Input or output of `/cosmos.bank.v1beta1.MsgMultiSend`, both have the same encoding.
+#[derive(Clone, PartialEq, Message)]
+struct BankIo {
+    #[prost(string, tag = "1")]
+    address: String,
+    #[prost(message, repeated, tag = "2")]
+    coins: Vec<ProtoCoin>,
+}
+
+/// Protobuf encoded `cosmos.staking.v1beta1.MsgDelegate` and `MsgUndelegate`.
+#[derive(Clone, PartialEq, Message)]
+struct MsgDelegate {
+    #[prost(string, tag = "1")]
+    delegator_address: String,
+    #[prost(string, tag = "2")]
+    validator_address: String,
+    #[prost(message, optional, tag = "3")]
+    amount: Option<ProtoCoin>,
+}
+
+/// Protobuf encoded `cosmos.staking.v1beta1.MsgBeginRedelegate`.
+#[derive(Clone, PartialEq, Message)]
+struct MsgBeginRedelegate {
+    #[prost(string, tag = "1")]
+    delegator_address: String,
+    #[prost(string, tag = "2")]
+    validator_src_address: String,
+    #[prost(string, tag = "3")]
+    validator_dst_address: String,
+    #[prost(message, optional, tag = "4")]
+    amount: Option<ProtoCoin>,
+}
+
+/// Protobuf encoded `cosmos.base.query.v1beta1.PageRequest`.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct ProtoPageRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    pub key: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub offset: u64,
+    #[prost(uint64, tag = "3")]
+    pub limit: u64,
+    #[prost(bool, tag = "4")]
+    pub count_total: bool,
+    #[prost(bool, tag = "5")]
+    pub reverse: bool,
+}
+
+/// Protobuf encoded `cosmos.base.query.v1beta1.PageResponse`.
+#[derive(Clone, PartialEq, Message)]
+pub(crate) struct PageResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    pub next_key: Vec<u8>,
+    #[prost(uint64, tag = "2")]
+    pub total: u64,
+}
+
+/// Returns a page of items sorted by their keys, like Cosmos SDK paginated queries do.
+///
+/// The page starts at the key from the request (inclusive), the key of the next page
+/// is the key of the first item not included in the page. The limit equal to zero means no limit.
+pub(crate) fn paginate<T>(
+    items: Vec<(Vec<u8>, T)>,
+    pagination: Option<ProtoPageRequest>,
+) -> (Vec<T>, PageResponse) {
+    let request = pagination.unwrap_or_default();
+    let total = items.len() as u64;
+    let items: Box<dyn Iterator<Item = (Vec<u8>, T)>> = if request.reverse {
+        Box::new(items.into_iter().rev())
+    } else {
+        Box::new(items.into_iter())
+    };
+    let start = request.key;
+    let mut items = items.skip_while(|(key, _)| {
+        !start.is_empty()
+            && (if request.reverse {
+                *key > start
+            } else {
+                *key < start
+            })
+    });
+    let limit = if request.limit == 0 {
+        usize::MAX
+    } else {
+        request.limit as usize
+    };
+    let page = items.by_ref().take(limit).map(|(_, item)| item).collect();
+    let next_key = items.next().map(|(key, _)| key).unwrap_or_default();
+    let total = if request.count_total { total } else { 0 };
+    (page, PageResponse { next_key, total })
+}
+
+/// Protobuf encoded `cosmos.bank.v1beta1.QueryTotalSupplyRequest`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryTotalSupplyRequest {
+    #[prost(message, optional, tag = "1")]
+    pagination: Option<ProtoPageRequest>,
+}
+
+/// Protobuf encoded `cosmos.bank.v1beta1.QueryTotalSupplyResponse`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryTotalSupplyResponse {
+    #[prost(message, repeated, tag = "1")]
+    supply: Vec<ProtoCoin>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+/// Protobuf encoded `cosmos.distribution.v1beta1.MsgWithdrawDelegatorReward`.
+#[derive(Clone, PartialEq, Message)]
+struct MsgWithdrawDelegatorReward {
+    #[prost(string, tag = "1")]
+    delegator_address: String,
+    #[prost(string, tag = "2")]
+    validator_address: String,
+}
@@ -0,0 +1,114 @@
+//! # Limits of contract storage accesses made by [WasmKeeper](crate::WasmKeeper)
+
+use crate::error::Error;
+use cosmwasm_std::{Order, Record, Storage};
+use std::cell::{Cell, RefCell};
+
+/// Maximum length of a storage key accepted by `wasmd`, in bytes.
+const MAX_KEY_LENGTH: usize = 64 * 1024;
+
+/// Maximum length of a storage value accepted by `wasmd`, in bytes.
+const MAX_VALUE_LENGTH: usize = 128 * 1024;
+
+/// Limits of contract storage accesses, enforced with
+/// [WasmKeeper::with_storage_limits](crate::WasmKeeper::with_storage_limits).
+///
+/// Default values follow the limits of `wasmd`: keys up to 64 KiB, values up to 128 KiB
+/// and no limit of the number of items iterated over in a single contract call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageLimits {
+    /// Maximum length of a key written to the contract storage, in bytes.
+    pub max_key_length: usize,
+    /// Maximum length of a value written to the contract storage, in bytes.
+    pub max_value_length: usize,
+    /// Maximum number of items iterated over in a single contract call, unlimited when `None`.
+    pub max_iterated_items: Option<u64>,
+}
+
+impl Default for StorageLimits {
+    /// Returns default storage limits, see [StorageLimits] for details.
+    fn default() -> Self {
+        Self {
+            max_key_length: MAX_KEY_LENGTH,
+            max_value_length: MAX_VALUE_LENGTH,
+            max_iterated_items: None,
+        }
+    }
+}
+
+/// Storage recording the first access exceeding the limits.
+///
+/// Accesses exceeding the limits are not passed to the wrapped storage,
+/// the contract call is failed with the recorded error after it returns.
+pub(crate) struct LimitedStorage<'a> {
+    storage: Box<dyn Storage + 'a>,
+    limits: &'a StorageLimits,
+    violation: &'a RefCell<Option<Error>>,
+    iterated: Cell<u64>,
+}
+
+impl<'a> LimitedStorage<'a> {
+    /// Wraps the storage, the first access exceeding the limits is recorded in `violation`.
+    pub fn new(
+        storage: Box<dyn Storage + 'a>,
+        limits: &'a StorageLimits,
+        violation: &'a RefCell<Option<Error>>,
+    ) -> Self {
+        Self {
+            storage,
+            limits,
+            violation,
+            iterated: Cell::new(0),
+        }
+    }
+
+    /// Records the error, unless some error was already recorded.
+    fn violate(&self, error: Error) {
+        self.violation.borrow_mut().get_or_insert(error);
+    }
+}
+
+impl<'a> Storage for LimitedStorage<'a> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.storage.get(key)
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        Box::new(self.storage.range(start, end, order).take_while(move |_| {
+            let iterated = self.iterated.get() + 1;
+            self.iterated.set(iterated);
+            match self.limits.max_iterated_items {
+                Some(limit) if iterated > limit => {
+                    self.violate(Error::too_many_iterated_items(limit));
+                    false
+                }
+                _ => true,
+            }
+        }))
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        if key.len() > self.limits.max_key_length {
+            self.violate(Error::region_length_too_big(
+                key.len(),
+                self.limits.max_key_length,
+            ));
+        } else if value.len() > self.limits.max_value_length {
+            self.violate(Error::region_length_too_big(
+                value.len(),
+                self.limits.max_value_length,
+            ));
+        } else {
+            self.storage.set(key, value)
+        }
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.storage.remove(key)
+    }
+}
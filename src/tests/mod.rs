@@ -5,4 +5,7 @@ mod test_custom_handler;
 mod test_error;
 mod test_gov;
 mod test_ibc;
+mod test_profiler;
 mod test_stargate;
+mod test_token_factory;
+mod test_trace;
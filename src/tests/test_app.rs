@@ -5,16 +5,17 @@ use crate::test_helpers::{caller, echo, error, hackatom, payout, reflect, Custom
 use crate::transactions::{transactional, StorageTransaction};
 use crate::wasm::ContractData;
 use crate::{
-    custom_app, next_block, no_init, App, AppResponse, Bank, CosmosRouter, Distribution, Executor,
-    Module, Router, Staking, Wasm, WasmSudo,
+    custom_app, next_block, no_init, App, AppResponse, Bank, BasicApp, BasicAppBuilder,
+    CosmosRouter, Distribution, Executor, GasCosts, Module, Router, Staking, Wasm, WasmKeeper,
+    WasmSudo,
 };
 use crate::{AppBuilder, IntoAddr};
 use cosmwasm_std::testing::{mock_env, MockQuerier};
 use cosmwasm_std::{
     coin, coins, from_json, to_json_binary, Addr, AllBalanceResponse, Api, Attribute, BankMsg,
     BankQuery, Binary, BlockInfo, Coin, CosmosMsg, CustomMsg, CustomQuery, Empty, Event,
-    OverflowError, OverflowOperation, Querier, Reply, StdError, StdResult, Storage, SubMsg,
-    WasmMsg,
+    MsgResponse, OverflowError, OverflowOperation, Querier, Reply, StdError, StdResult, Storage,
+    SubMsg, WasmMsg,
 };
 use cw_storage_plus::Item;
 use cw_utils::parse_instantiate_response_data;
@@ -31,6 +32,7 @@ fn get_balance<BankT, ApiT, StorageT, CustomT, WasmT>(
 where
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
     ApiT: Api,
@@ -75,6 +77,7 @@ fn query_app<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>(
 where
     CustomT::ExecT: CustomMsg + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    CustomT::SudoT: DeserializeOwned,
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
     ApiT: Api,
@@ -684,6 +687,119 @@ fn reflect_sub_message_reply_works() {
     // TODO: check error?
 }
 
+#[test]
+fn sub_message_gas_limit_should_work() {
+    let owner = addr_make("owner");
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(GasCosts {
+        execute: 10,
+        storage_read: 1,
+        storage_write: 2,
+        ..Default::default()
+    });
+    let mut app = BasicAppBuilder::<CustomHelperMsg, Empty>::new_custom()
+        .with_wasm(wasm_keeper)
+        .build(no_init);
+    let reflect_id = app.store_code(reflect::contract());
+    let reflect_addr = app
+        .instantiate_contract(reflect_id, owner.clone(), &Empty {}, &[], "Reflect", None)
+        .unwrap();
+
+    // reflect calls itself within the gas limit of the submessage
+    let call_self = |gas_limit: u64| reflect::Message {
+        messages: vec![SubMsg::reply_on_error(
+            WasmMsg::Execute {
+                contract_addr: reflect_addr.to_string(),
+                msg: to_json_binary(&reflect::Message::default()).unwrap(),
+                funds: vec![],
+            },
+            1,
+        )
+        .with_gas_limit(gas_limit)],
+    };
+    let count = |app: &BasicApp<CustomHelperMsg>| {
+        let res: payout::CountResponse = app
+            .wrap()
+            .query_wasm_smart(&reflect_addr, &reflect::QueryMsg::Count {})
+            .unwrap();
+        res.count
+    };
+
+    // the nested call fits in the limit, both calls increment the counter
+    app.execute_contract(owner.clone(), reflect_addr.clone(), &call_self(100), &[])
+        .unwrap();
+    assert_eq!(2, count(&app));
+
+    // the nested call runs out of gas, the error is handled in reply
+    app.execute_contract(owner.clone(), reflect_addr.clone(), &call_self(5), &[])
+        .unwrap();
+    assert_eq!(3, count(&app));
+    let res: Reply = app
+        .wrap()
+        .query_wasm_smart(&reflect_addr, &reflect::QueryMsg::Reply { id: 1 })
+        .unwrap();
+    assert!(res.result.unwrap_err().contains("out of gas"));
+
+    // the nested call enters within the limit (10 gas), but its storage accesses
+    // exceed the limit (10 + 1 + 2 gas), so it fails and its changes are reverted
+    app.execute_contract(owner, reflect_addr.clone(), &call_self(11), &[])
+        .unwrap();
+    assert_eq!(4, count(&app));
+    let res: Reply = app
+        .wrap()
+        .query_wasm_smart(&reflect_addr, &reflect::QueryMsg::Reply { id: 1 })
+        .unwrap();
+    assert!(res.result.unwrap_err().contains("out of gas"));
+}
+
+#[test]
+fn reflect_sub_message_reply_should_have_payload_and_msg_responses() {
+    let owner = addr_make("owner");
+    let random = addr_make("random");
+
+    let mut app = custom_app::<CustomHelperMsg, Empty, _>(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, coins(100, "eth"))
+            .unwrap();
+    });
+    let reflect_id = app.store_code(reflect::contract());
+    let reflect_addr = app
+        .instantiate_contract(
+            reflect_id,
+            owner,
+            &Empty {},
+            &coins(40, "eth"),
+            "Reflect",
+            None,
+        )
+        .unwrap();
+
+    let msg = SubMsg::reply_on_success(
+        BankMsg::Send {
+            to_address: random.clone().into(),
+            amount: coins(7, "eth"),
+        },
+        123,
+    )
+    .with_payload(b"payload".to_vec());
+    let msgs = reflect::Message {
+        messages: vec![msg],
+    };
+    app.execute_contract(random, reflect_addr.clone(), &msgs, &[])
+        .unwrap();
+
+    let query = reflect::QueryMsg::Reply { id: 123 };
+    let res: Reply = app.wrap().query_wasm_smart(&reflect_addr, &query).unwrap();
+    assert_eq!(Binary::from(b"payload"), res.payload);
+    assert_eq!(
+        vec![MsgResponse {
+            type_url: "/cosmos.bank.v1beta1.MsgSendResponse".to_string(),
+            value: Binary::default(),
+        }],
+        res.result.unwrap().msg_responses
+    );
+}
+
 #[test]
 fn send_update_admin_works() {
     // The plan:
@@ -1847,6 +1963,7 @@ mod protobuf_wrapped_data {
 
 mod errors {
     use super::*;
+    use crate::error::AppError;
 
     #[test]
     fn simple_instantiation() {
@@ -1950,6 +2067,148 @@ mod errors {
         assert_eq!(err.chain().count(), 3);
     }
 
+    #[test]
+    fn nested_call_context() {
+        let mut app = App::default();
+
+        let owner = app.api().addr_make("owner");
+        let random_addr = app.api().addr_make("random");
+
+        let error_code_id = app.store_code(error::contract(true));
+        let caller_code_id = app.store_code(caller::contract());
+
+        let msg = Empty {};
+        let caller_addr = app
+            .instantiate_contract(caller_code_id, owner.clone(), &msg, &[], "caller", None)
+            .unwrap();
+        let error_addr = app
+            .instantiate_contract(error_code_id, owner, &msg, &[], "error", None)
+            .unwrap();
+
+        let msg = WasmMsg::Execute {
+            contract_addr: error_addr.to_string(),
+            msg: to_json_binary(&Empty {}).unwrap(),
+            funds: vec![],
+        };
+        let err = app
+            .execute_contract(random_addr.clone(), caller_addr.clone(), &msg, &[])
+            .unwrap_err();
+
+        // the outermost context describes the message sent in the transaction
+        let context: &AppError = err.downcast_ref().unwrap();
+        let AppError::WasmMsg {
+            sender,
+            contract,
+            nested,
+            ..
+        } = context;
+        assert_eq!(&random_addr, sender);
+        assert_eq!(Some(&caller_addr), contract.as_ref());
+        assert!(nested.is_some());
+
+        // the origin describes the message that failed in the nested call
+        let AppError::WasmMsg {
+            sender,
+            contract,
+            msg: origin_msg,
+            nested,
+            ..
+        } = context.origin();
+        assert_eq!(&caller_addr, sender);
+        assert_eq!(Some(&error_addr), contract.as_ref());
+        assert_eq!(&msg, origin_msg);
+        assert!(nested.is_none());
+
+        // both failed messages are the first messages sent
+        assert_eq!(vec![0, 0], context.msg_indices());
+
+        // display of the context is unchanged
+        assert!(err
+            .to_string()
+            .starts_with("Error executing WasmMsg:\n  sender: "));
+    }
+
+    #[test]
+    fn failed_message_indices_context() {
+        let owner = "owner".into_addr();
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &owner, coins(100, "uatom"))
+                .unwrap();
+        });
+        let random_addr = app.api().addr_make("random");
+
+        let error_code_id = app.store_code(error::contract(true));
+        let caller_code_id = app.store_code(caller::contract());
+
+        let msg = Empty {};
+        let caller_addr = app
+            .instantiate_contract(caller_code_id, owner.clone(), &msg, &[], "caller", None)
+            .unwrap();
+        let error_addr = app
+            .instantiate_contract(error_code_id, owner.clone(), &msg, &[], "error", None)
+            .unwrap();
+
+        let msg = WasmMsg::Execute {
+            contract_addr: error_addr.to_string(),
+            msg: to_json_binary(&Empty {}).unwrap(),
+            funds: vec![],
+        };
+        let err = app
+            .execute_multi(
+                owner,
+                vec![
+                    BankMsg::Send {
+                        to_address: random_addr.to_string(),
+                        amount: coins(10, "uatom"),
+                    }
+                    .into(),
+                    WasmMsg::Execute {
+                        contract_addr: caller_addr.to_string(),
+                        msg: to_json_binary(&msg).unwrap(),
+                        funds: vec![],
+                    }
+                    .into(),
+                ],
+            )
+            .unwrap_err();
+
+        // the second message of the transaction failed in its first sub-message
+        let context: &AppError = err.downcast_ref().unwrap();
+        assert_eq!(vec![1, 0], context.msg_indices());
+        let AppError::WasmMsg { msg_index, .. } = context.origin();
+        assert_eq!(Some(0), *msg_index);
+    }
+
+    #[test]
+    fn failed_instantiation_context() {
+        let mut app = App::default();
+
+        let owner = app.api().addr_make("owner");
+        let code_id = app.store_code(error::contract(false));
+        let contract_addr = app
+            .predict_instantiate2_address(code_id, &owner, b"salt")
+            .unwrap();
+
+        let msg = WasmMsg::Instantiate2 {
+            admin: None,
+            code_id,
+            msg: to_json_binary(&Empty {}).unwrap(),
+            funds: vec![],
+            label: "error".to_string(),
+            salt: Binary::from(b"salt"),
+        };
+        let err = app.execute(owner.clone(), msg.into()).unwrap_err();
+
+        // the context contains the address of the contract being instantiated
+        let AppError::WasmMsg {
+            sender, contract, ..
+        } = err.downcast_ref().unwrap();
+        assert_eq!(&owner, sender);
+        assert_eq!(Some(&contract_addr), contract.as_ref());
+    }
+
     #[test]
     fn double_nested_call() {
         let mut app = App::default();
@@ -2017,3 +2276,91 @@ mod errors {
         assert_eq!(err.chain().count(), 4);
     }
 }
+
+mod response_assertions {
+    use super::*;
+
+    #[test]
+    fn wasm_events_and_attributes_should_be_found() {
+        let mut app = BasicApp::new(no_init);
+        let owner = app.api().addr_make("owner");
+        let code_id = app.store_code(echo::contract());
+        let echo_addr = app
+            .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "echo", None)
+            .unwrap();
+
+        let msg = echo::Message::<Empty> {
+            attributes: vec![
+                Attribute::new("action", "transfer"),
+                Attribute::new("amount", "100"),
+            ],
+            events: vec![Event::new("custom").add_attribute("kind", "test")],
+            ..echo::Message::default()
+        };
+        let res = app
+            .execute_contract(owner, echo_addr.clone(), &msg, &[])
+            .unwrap();
+
+        let event = res.wasm_event("action", "transfer").unwrap();
+        assert!(event.attributes.contains(&Attribute::new("amount", "100")));
+        assert!(res.wasm_event("action", "burn").is_err());
+
+        assert_eq!(Some("test"), res.attribute_value("wasm-custom", "kind"));
+        assert_eq!(None, res.attribute_value("wasm-custom", "missing"));
+
+        assert_eq!(
+            vec![
+                &Attribute::new("action", "transfer"),
+                &Attribute::new("amount", "100")
+            ],
+            res.contract_attrs(&echo_addr)
+        );
+        assert!(res.contract_attrs(&app.api().addr_make("other")).is_empty());
+    }
+
+    #[test]
+    fn response_data_should_be_decoded() {
+        let mut app = BasicApp::new(no_init);
+        let owner = app.api().addr_make("owner");
+        let code_id = app.store_code(echo::contract());
+
+        let msg = echo::InitMessage::<Empty> {
+            data: Some("\"food\"".into()),
+            sub_msg: None,
+        };
+        let msg = WasmMsg::Instantiate {
+            admin: None,
+            code_id,
+            msg: to_json_binary(&msg).unwrap(),
+            funds: vec![],
+            label: "echo".into(),
+        };
+        let res = app.execute(owner.clone(), msg.into()).unwrap();
+        let instantiated = res.instantiate_response().unwrap();
+        assert_eq!(Some(Binary::from(b"\"food\"")), instantiated.data);
+        let echo_addr = Addr::unchecked(instantiated.contract_address);
+
+        let msg = echo::Message::<Empty> {
+            data: Some("\"hello\"".into()),
+            ..echo::Message::default()
+        };
+        let msg = WasmMsg::Execute {
+            contract_addr: echo_addr.to_string(),
+            msg: to_json_binary(&msg).unwrap(),
+            funds: vec![],
+        };
+        let res = app.execute(owner.clone(), msg.into()).unwrap();
+        assert_eq!(
+            Some(Binary::from(b"\"hello\"")),
+            res.execute_response_data().unwrap()
+        );
+
+        let msg = echo::Message::<Empty> {
+            data: Some("\"hello\"".into()),
+            ..echo::Message::default()
+        };
+        let res = app.execute_contract(owner, echo_addr, &msg, &[]).unwrap();
+        assert_eq!("hello", res.data_as::<String>().unwrap());
+        assert!(AppResponse::default().data_as::<String>().is_err());
+    }
+}
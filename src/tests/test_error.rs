@@ -40,6 +40,10 @@ fn instantiating_error_should_work() {
         "code id 53: no such code",
         Error::unregistered_code_id(53).to_string()
     );
+    assert_eq!(
+        "out of gas: gas used 1001, gas limit 1000",
+        Error::out_of_gas(1001, 1000).to_string()
+    );
     assert_eq!(
         "Contract with this address already exists: contract1984",
         Error::duplicated_contract_address("contract1984").to_string()
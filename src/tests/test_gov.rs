@@ -1,6 +1,13 @@
 use crate::test_helpers::gov;
-use crate::{no_init, App, AppBuilder, Executor, GovAcceptingModule};
-use cosmwasm_std::Empty;
+use crate::{
+    no_init, App, AppBuilder, BankKeeper, DistributionKeeper, Executor, FailingModule,
+    GovAcceptingModule, GovKeeper, GovSudo, IbcFailingModule, ProposalStatus, StakeKeeper,
+    WasmKeeper,
+};
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    coin, from_json, BankMsg, CosmosMsg, Empty, Event, GovMsg, Uint128, VoteOption,
+};
 
 #[test]
 fn default_gov() {
@@ -35,3 +42,205 @@ fn accepting_gov() {
     app.execute_contract(owner_addr, contract, &Empty {}, &[])
         .unwrap();
 }
+
+type GovApp = App<
+    BankKeeper,
+    MockApi,
+    MockStorage,
+    FailingModule<Empty, Empty, Empty>,
+    WasmKeeper<Empty, Empty>,
+    StakeKeeper,
+    DistributionKeeper,
+    IbcFailingModule,
+    GovKeeper,
+>;
+
+fn gov_keeper_app(voters: &[(&str, u128)]) -> GovApp {
+    let mut app = AppBuilder::new()
+        .with_gov(GovKeeper::new())
+        .build(|router, api, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &router.gov.module_addr(api).unwrap(),
+                    vec![coin(100, "uatom")],
+                )
+                .unwrap();
+        });
+    for (voter, power) in voters {
+        let voter = app.api().addr_make(voter);
+        app.sudo(
+            GovSudo::SetVotingPower {
+                voter: voter.to_string(),
+                power: Uint128::new(*power),
+            }
+            .into(),
+        )
+        .unwrap();
+    }
+    app
+}
+
+fn submit_proposal(app: &mut GovApp, messages: Vec<CosmosMsg>) -> u64 {
+    let proposer = app.api().addr_make("proposer");
+    let res = app
+        .sudo(
+            GovSudo::SubmitProposal {
+                proposer: proposer.to_string(),
+                title: "proposal".to_string(),
+                messages,
+            }
+            .into(),
+        )
+        .unwrap();
+    from_json(res.data.unwrap()).unwrap()
+}
+
+fn vote(app: &mut GovApp, voter: &str, proposal_id: u64, option: VoteOption) {
+    let voter = app.api().addr_make(voter);
+    app.execute(
+        voter,
+        GovMsg::Vote {
+            proposal_id,
+            option,
+        }
+        .into(),
+    )
+    .unwrap();
+}
+
+fn proposal_status(app: &GovApp, proposal_id: u64) -> ProposalStatus {
+    app.read_module(|router, _, storage| router.gov.proposal(storage, proposal_id))
+        .unwrap()
+        .status
+}
+
+#[test]
+fn gov_keeper_passed_proposal_should_be_executed() {
+    let mut app = gov_keeper_app(&[("alice", 60), ("bob", 40)]);
+    let recipient = app.api().addr_make("recipient");
+    let proposal_id = submit_proposal(
+        &mut app,
+        vec![BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin(30, "uatom")],
+        }
+        .into()],
+    );
+    assert_eq!(1, proposal_id);
+    // proposal messages are sent by the gov module account
+    assert_eq!(
+        app.module_address("gov").unwrap(),
+        app.read_module(|router, api, _| router.gov.module_addr(api))
+            .unwrap()
+    );
+
+    vote(&mut app, "alice", proposal_id, VoteOption::Yes);
+    vote(&mut app, "bob", proposal_id, VoteOption::No);
+    let tally = app
+        .read_module(|router, _, storage| router.gov.tally(storage, proposal_id))
+        .unwrap();
+    assert_eq!(Uint128::new(60), tally.yes);
+    assert_eq!(Uint128::new(40), tally.no);
+
+    app.sudo(GovSudo::Tally { proposal_id }.into()).unwrap();
+    assert_eq!(ProposalStatus::Passed, proposal_status(&app, proposal_id));
+    assert_eq!(
+        coin(30, "uatom"),
+        app.wrap().query_balance(recipient, "uatom").unwrap()
+    );
+
+    // voting on finished proposal fails
+    let alice = app.api().addr_make("alice");
+    app.execute(
+        alice,
+        GovMsg::Vote {
+            proposal_id,
+            option: VoteOption::No,
+        }
+        .into(),
+    )
+    .unwrap_err();
+}
+
+#[test]
+fn gov_keeper_rejected_proposal_should_not_be_executed() {
+    let mut app = gov_keeper_app(&[("alice", 60), ("bob", 40)]);
+    let recipient = app.api().addr_make("recipient");
+    let msg: CosmosMsg = BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![coin(30, "uatom")],
+    }
+    .into();
+
+    // vetoed proposal
+    let proposal_id = submit_proposal(&mut app, vec![msg.clone()]);
+    vote(&mut app, "alice", proposal_id, VoteOption::NoWithVeto);
+    vote(&mut app, "bob", proposal_id, VoteOption::Yes);
+    app.sudo(GovSudo::Tally { proposal_id }.into()).unwrap();
+    assert_eq!(ProposalStatus::Rejected, proposal_status(&app, proposal_id));
+
+    // proposal without quorum
+    let proposal_id = submit_proposal(&mut app, vec![msg]);
+    vote(&mut app, "bob", proposal_id, VoteOption::Abstain);
+    app.sudo(GovSudo::Tally { proposal_id }.into()).unwrap();
+    assert_eq!(ProposalStatus::Rejected, proposal_status(&app, proposal_id));
+
+    assert!(app
+        .wrap()
+        .query_balance(recipient, "uatom")
+        .unwrap()
+        .amount
+        .is_zero());
+}
+
+#[test]
+fn gov_keeper_failed_proposal_should_be_reverted() {
+    let mut app = gov_keeper_app(&[("alice", 1)]);
+    let recipient = app.api().addr_make("recipient");
+    let send = |amount| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![coin(amount, "uatom")],
+        }
+        .into()
+    };
+    // the second message fails, so the first one has to be reverted
+    let proposal_id = submit_proposal(&mut app, vec![send(50), send(100)]);
+    vote(&mut app, "alice", proposal_id, VoteOption::Yes);
+    let res = app.sudo(GovSudo::Tally { proposal_id }.into()).unwrap();
+    assert_eq!(ProposalStatus::Failed, proposal_status(&app, proposal_id));
+    let proposal = app
+        .read_module(|router, _, storage| router.gov.proposal(storage, proposal_id))
+        .unwrap();
+    let reason = proposal.failure_reason.unwrap();
+    assert!(reason.contains("Cannot Sub"), "{}", reason);
+    assert!(res.has_event(
+        &Event::new("active_proposal").add_attribute("proposal_failure_reason", reason)
+    ));
+    assert!(app
+        .wrap()
+        .query_balance(&recipient, "uatom")
+        .unwrap()
+        .amount
+        .is_zero());
+}
+
+#[test]
+fn gov_keeper_should_accept_votes_from_contracts() {
+    let mut app = gov_keeper_app(&[]);
+    let proposal_id = submit_proposal(&mut app, vec![]);
+
+    let owner_addr = app.api().addr_make("owner");
+    let code = app.store_code(gov::contract());
+    let contract = app
+        .instantiate_contract(code, owner_addr.clone(), &Empty {}, &[], "govenius", None)
+        .unwrap();
+    app.execute_contract(owner_addr, contract, &Empty {}, &[])
+        .unwrap();
+    assert_eq!(
+        ProposalStatus::VotingPeriod,
+        proposal_status(&app, proposal_id)
+    );
+}
@@ -1,6 +1,7 @@
+use crate::error::bail;
 use crate::test_helpers::ibc;
 use crate::{no_init, App, AppBuilder, Executor, IbcAcceptingModule};
-use cosmwasm_std::Empty;
+use cosmwasm_std::{Binary, Empty, Event, IbcMsg, IbcTimeout};
 
 #[test]
 fn default_ibc() {
@@ -35,3 +36,240 @@ fn accepting_ibc() {
     app.execute_contract(owner_addr, contract, &Empty {}, &[])
         .unwrap();
 }
+
+#[test]
+fn accepting_ibc_with_ack_handler() {
+    let mut app = AppBuilder::new()
+        .with_ibc(IbcAcceptingModule::new().with_ack_handler(|msg| match msg {
+            IbcMsg::SendPacket { channel_id, .. } => Ok((
+                Binary::from(br#"{"result":"AQ=="}"#),
+                vec![Event::new("ack").add_attribute("channel", channel_id)],
+            )),
+            _ => bail!("rejected"),
+        }))
+        .build(no_init);
+    let sender_addr = app.api().addr_make("sender");
+
+    let res = app
+        .execute(
+            sender_addr.clone(),
+            IbcMsg::SendPacket {
+                channel_id: "channel-0".to_string(),
+                data: Binary::default(),
+                timeout: IbcTimeout::with_timestamp(app.block_info().time.plus_seconds(60)),
+            }
+            .into(),
+        )
+        .unwrap();
+    assert_eq!(Some(Binary::from(br#"{"result":"AQ=="}"#)), res.data);
+    assert_eq!(
+        vec![Event::new("ack").add_attribute("channel", "channel-0")],
+        res.events
+    );
+
+    // contract sending a rejected message fails
+    let creator_addr = app.api().addr_make("creator");
+    let code = app.store_code_with_creator(creator_addr, ibc::contract());
+    let contract = app
+        .instantiate_contract(code, sender_addr.clone(), &Empty {}, &[], "ibanera", None)
+        .unwrap();
+    app.execute_contract(sender_addr, contract, &Empty {}, &[])
+        .unwrap_err();
+}
+
+mod ibc_entry_points {
+    use crate::test_helpers::ibc;
+    use crate::{App, Contract, ContractWrapper, Executor};
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_ibc_channel, mock_ibc_channel_close_init,
+        mock_ibc_channel_connect_ack, mock_ibc_channel_open_init, mock_ibc_packet_ack,
+        mock_ibc_packet_recv, mock_ibc_packet_timeout,
+    };
+    use cosmwasm_std::{
+        to_json_binary, Binary, Deps, DepsMut, Empty, Env, IbcAcknowledgement, IbcBasicResponse,
+        IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg, IbcChannelOpenResponse,
+        IbcOrder, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg, IbcReceiveResponse,
+        MessageInfo, Response, StdError, StdResult,
+    };
+
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        Ok(Binary::default())
+    }
+
+    fn channel_open(
+        _: DepsMut,
+        _: Env,
+        msg: IbcChannelOpenMsg,
+    ) -> StdResult<IbcChannelOpenResponse> {
+        match msg.channel().order {
+            IbcOrder::Unordered => Ok(None),
+            IbcOrder::Ordered => Err(StdError::generic_err("only unordered channels")),
+        }
+    }
+
+    fn channel_connect(_: DepsMut, _: Env, _: IbcChannelConnectMsg) -> StdResult<IbcBasicResponse> {
+        Ok(IbcBasicResponse::new().add_attribute("action", "connect"))
+    }
+
+    fn channel_close(_: DepsMut, _: Env, _: IbcChannelCloseMsg) -> StdResult<IbcBasicResponse> {
+        Ok(IbcBasicResponse::new().add_attribute("action", "close"))
+    }
+
+    fn packet_receive(
+        _: DepsMut,
+        _: Env,
+        msg: IbcPacketReceiveMsg,
+    ) -> StdResult<IbcReceiveResponse> {
+        if msg.packet.data == to_json_binary(&"malicious")? {
+            return Err(StdError::generic_err("invalid packet"));
+        }
+        Ok(IbcReceiveResponse::new(msg.packet.data).add_attribute("action", "receive"))
+    }
+
+    fn packet_ack(_: DepsMut, _: Env, _: IbcPacketAckMsg) -> StdResult<IbcBasicResponse> {
+        Ok(IbcBasicResponse::new().add_attribute("action", "ack"))
+    }
+
+    fn packet_timeout(_: DepsMut, _: Env, _: IbcPacketTimeoutMsg) -> StdResult<IbcBasicResponse> {
+        Ok(IbcBasicResponse::new().add_attribute("action", "timeout"))
+    }
+
+    fn ibc_contract() -> ContractWrapper<Empty, Empty, Empty, StdError, StdError, StdError> {
+        ContractWrapper::new(execute, instantiate, query).with_ibc(
+            channel_open,
+            channel_connect,
+            channel_close,
+            packet_receive,
+            packet_ack,
+            packet_timeout,
+        )
+    }
+
+    #[test]
+    fn ibc_entry_points_should_be_called() {
+        let contract = ibc_contract();
+        assert!(contract.has_ibc_entry_points());
+
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        let msg = mock_ibc_channel_open_init("channel-0", IbcOrder::Unordered, "v1");
+        let res = contract.ibc_channel_open(deps.as_mut(), env.clone(), msg);
+        assert_eq!(None, res.unwrap());
+        let msg = mock_ibc_channel_open_init("channel-0", IbcOrder::Ordered, "v1");
+        let err = contract.ibc_channel_open(deps.as_mut(), env.clone(), msg);
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("only unordered channels"));
+
+        let msg = mock_ibc_channel_connect_ack("channel-0", IbcOrder::Unordered, "v1");
+        let res = contract.ibc_channel_connect(deps.as_mut(), env.clone(), msg);
+        assert_eq!("connect", res.unwrap().attributes[0].value);
+
+        let msg = mock_ibc_channel_close_init("channel-0", IbcOrder::Unordered, "v1");
+        let res = contract.ibc_channel_close(deps.as_mut(), env.clone(), msg);
+        assert_eq!("close", res.unwrap().attributes[0].value);
+
+        let msg = mock_ibc_packet_recv("channel-0", &"hello").unwrap();
+        let res = contract.ibc_packet_receive(deps.as_mut(), env.clone(), msg);
+        assert_eq!(
+            Some(to_json_binary(&"hello").unwrap()),
+            res.unwrap().acknowledgement
+        );
+
+        let ack = IbcAcknowledgement::new(b"ok");
+        let msg = mock_ibc_packet_ack("channel-0", &"hello", ack).unwrap();
+        let res = contract.ibc_packet_ack(deps.as_mut(), env.clone(), msg);
+        assert_eq!("ack", res.unwrap().attributes[0].value);
+
+        let msg = mock_ibc_packet_timeout("channel-0", &"hello").unwrap();
+        let res = contract.ibc_packet_timeout(deps.as_mut(), env, msg);
+        assert_eq!("timeout", res.unwrap().attributes[0].value);
+    }
+
+    #[test]
+    fn missing_ibc_entry_points_should_fail() {
+        let contract = ContractWrapper::new(execute, instantiate, query);
+        assert!(!contract.has_ibc_entry_points());
+
+        let mut deps = mock_dependencies();
+        let msg = IbcChannelOpenMsg::OpenInit {
+            channel: mock_ibc_channel("channel-0", IbcOrder::Unordered, "v1"),
+        };
+        let err = contract
+            .ibc_channel_open(deps.as_mut(), mock_env(), msg)
+            .unwrap_err();
+        assert_eq!(
+            "ibc_channel_open is not implemented for contract",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn contract_info_should_report_ibc_port() {
+        let mut app = App::default();
+        let owner = app.api().addr_make("owner");
+
+        let code_id = app.store_code(Box::new(ibc_contract()));
+        let contract_addr = app
+            .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "ibc", None)
+            .unwrap();
+        let info = app.wrap().query_wasm_contract_info(&contract_addr).unwrap();
+        assert_eq!(Some(format!("wasm.{contract_addr}")), info.ibc_port);
+
+        let code_id = app.store_code(ibc::contract());
+        let contract_addr = app
+            .instantiate_contract(code_id, owner, &Empty {}, &[], "no-ibc", None)
+            .unwrap();
+        let info = app.wrap().query_wasm_contract_info(&contract_addr).unwrap();
+        assert_eq!(None, info.ibc_port);
+    }
+
+    #[test]
+    fn injected_packet_should_be_received() {
+        let mut app = App::default();
+        let owner = app.api().addr_make("owner");
+        let code_id = app.store_code(Box::new(ibc_contract()));
+        let contract_addr = app
+            .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "ibc", None)
+            .unwrap();
+
+        let msg = mock_ibc_packet_recv("channel-0", &"hello").unwrap();
+        let res = app.inject_ibc_packet_receive(&contract_addr, msg).unwrap();
+        assert_eq!(Some(to_json_binary(&"hello").unwrap()), res.data);
+        assert_eq!("ibc_packet_receive", res.events[0].ty);
+        assert_eq!(Some("receive"), res.attribute_value("wasm", "action"));
+
+        // hand-crafted packet rejected by the contract
+        let msg = mock_ibc_packet_recv("channel-0", &"malicious").unwrap();
+        let err = app
+            .inject_ibc_packet_receive(&contract_addr, msg)
+            .unwrap_err();
+        assert_eq!(
+            StdError::generic_err("invalid packet"),
+            err.downcast().unwrap()
+        );
+
+        // contract without IBC entry-points
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+        let contract_addr = app
+            .instantiate_contract(code_id, owner, &Empty {}, &[], "no-ibc", None)
+            .unwrap();
+        let msg = mock_ibc_packet_recv("channel-0", &"hello").unwrap();
+        let err = app
+            .inject_ibc_packet_receive(&contract_addr, msg)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("ibc_packet_receive is not implemented for contract"));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::test_helpers::{caller, hackatom};
+use crate::{no_init, AppBuilder, Executor, GasCosts, WasmKeeper};
+use cosmwasm_std::{to_json_binary, Empty, WasmMsg};
+
+#[test]
+fn profiling_should_be_disabled_by_default() {
+    let app = AppBuilder::default().build(no_init);
+    assert!(app.profiling_report().is_none());
+}
+
+#[test]
+fn profiling_should_accumulate_calls_across_transactions() {
+    let mut app = AppBuilder::default()
+        .with_wasm(
+            WasmKeeper::new()
+                .with_gas_costs(GasCosts::default())
+                .with_profiling(),
+        )
+        .build(no_init);
+    let owner = app.api().addr_make("owner");
+    let beneficiary = app.api().addr_make("beneficiary");
+    let caller_code_id = app.store_code(caller::contract());
+    let hackatom_code_id = app.store_code(hackatom::contract());
+    let caller_addr = app
+        .instantiate_contract(
+            caller_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "caller",
+            None,
+        )
+        .unwrap();
+
+    // caller instantiates hackatom contract twice
+    let msg = WasmMsg::Instantiate {
+        admin: None,
+        code_id: hackatom_code_id,
+        msg: to_json_binary(&hackatom::InstantiateMsg {
+            beneficiary: beneficiary.to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+        label: "hackatom".to_string(),
+    };
+    for _ in 0..2 {
+        app.execute_contract(owner.clone(), caller_addr.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    let report = app.profiling_report().unwrap();
+    let caller_entries = report.contract(&caller_addr);
+    assert_eq!(2, caller_entries.len());
+    assert_eq!("execute", caller_entries[0].entry_point);
+    assert_eq!(2, caller_entries[0].calls);
+    assert_eq!("instantiate", caller_entries[1].entry_point);
+    assert_eq!(1, caller_entries[1].calls);
+
+    // nested instantiations are profiled per instantiated contract
+    let hackatom_entries: Vec<_> = report
+        .entries
+        .iter()
+        .filter(|entry| entry.contract != caller_addr)
+        .collect();
+    assert_eq!(2, hackatom_entries.len());
+    assert!(hackatom_entries
+        .iter()
+        .all(|entry| entry.entry_point == "instantiate" && entry.calls == 1));
+
+    // gas of the caller includes gas of nested calls
+    let execute = caller_entries[0];
+    assert!(execute.total_gas > 0);
+    assert!(execute.max_gas <= execute.total_gas);
+    assert!(execute.max_gas > hackatom_entries[0].max_gas);
+    assert!(execute.max_time <= execute.total_time);
+    assert_eq!(execute.contract, report.by_total_gas()[0].contract);
+    assert_eq!(report.entries.len(), report.to_string().lines().count());
+
+    app.reset_profiling();
+    assert!(app.profiling_report().unwrap().entries.is_empty());
+}
@@ -1,6 +1,14 @@
 use crate::test_helpers::stargate;
-use crate::{no_init, App, AppBuilder, Executor, StargateAccepting};
-use cosmwasm_std::Empty;
+use crate::token_factory::ProtoCoin;
+use crate::{
+    no_init, App, AppBuilder, Executor, StargateAccepting, StargateKeeper, StargateMsgResult,
+};
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{
+    coin, coins, to_json_vec, AnyMsg, BankMsg, CosmosMsg, Empty, Event, GrpcQuery, Querier,
+    QueryRequest,
+};
+use prost::Message;
 
 #[test]
 fn default_failing_stargate_handler_should_work() {
@@ -52,3 +60,332 @@ fn accepting_stargate_handler_should_work() {
         .execute_contract(owner_addr, contract_addr, &Empty {}, &[])
         .is_ok());
 }
+
+fn any_msg(type_url: &str, msg: impl Message) -> CosmosMsg {
+    CosmosMsg::Any(AnyMsg {
+        type_url: type_url.to_string(),
+        value: msg.encode_to_vec().into(),
+    })
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct MsgSend {
+    #[prost(string, tag = "1")]
+    from_address: String,
+    #[prost(string, tag = "2")]
+    to_address: String,
+    #[prost(message, repeated, tag = "3")]
+    amount: Vec<ProtoCoin>,
+}
+
+#[test]
+fn stargate_keeper_should_route_bank_send() {
+    let owner = MockApi::default().addr_make("owner");
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new())
+        .build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &owner, coins(100, "uatom"))
+                .unwrap();
+        });
+    let receiver = app.api().addr_make("receiver");
+
+    let msg = MsgSend {
+        from_address: owner.to_string(),
+        to_address: receiver.to_string(),
+        amount: vec![ProtoCoin {
+            denom: "uatom".to_string(),
+            amount: "40".to_string(),
+        }],
+    };
+    app.execute(
+        owner.clone(),
+        any_msg("/cosmos.bank.v1beta1.MsgSend", msg.clone()),
+    )
+    .unwrap();
+    assert_eq!(
+        coin(40, "uatom"),
+        app.wrap().query_balance(&receiver, "uatom").unwrap()
+    );
+
+    // the signer must be the sender
+    app.execute(receiver, any_msg("/cosmos.bank.v1beta1.MsgSend", msg))
+        .unwrap_err();
+
+    // messages with unknown type URLs are rejected
+    app.execute(owner, any_msg("/unknown.MsgUnknown", MsgSend::default()))
+        .unwrap_err();
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct MsgMultiSend {
+    #[prost(message, repeated, tag = "1")]
+    inputs: Vec<BankIo>,
+    #[prost(message, repeated, tag = "2")]
+    outputs: Vec<BankIo>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct BankIo {
+    #[prost(string, tag = "1")]
+    address: String,
+    #[prost(message, repeated, tag = "2")]
+    coins: Vec<ProtoCoin>,
+}
+
+fn proto_coins(amount: u128) -> Vec<ProtoCoin> {
+    vec![ProtoCoin {
+        denom: "uatom".to_string(),
+        amount: amount.to_string(),
+    }]
+}
+
+#[test]
+fn stargate_keeper_should_route_bank_multi_send() {
+    let owner = MockApi::default().addr_make("owner");
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new())
+        .build(no_init);
+    app.init_balances(&[(owner.clone(), coins(100, "uatom"))])
+        .unwrap();
+    let alice = app.api().addr_make("alice");
+    let bob = app.api().addr_make("bob");
+
+    let multi_send = |input: u128, outputs: &[(&str, u128)]| {
+        let msg = MsgMultiSend {
+            inputs: vec![BankIo {
+                address: owner.to_string(),
+                coins: proto_coins(input),
+            }],
+            outputs: outputs
+                .iter()
+                .map(|(address, amount)| BankIo {
+                    address: address.to_string(),
+                    coins: proto_coins(*amount),
+                })
+                .collect(),
+        };
+        any_msg("/cosmos.bank.v1beta1.MsgMultiSend", msg)
+    };
+
+    app.execute(
+        owner.clone(),
+        multi_send(50, &[(alice.as_str(), 20), (bob.as_str(), 30)]),
+    )
+    .unwrap();
+    assert_eq!(
+        coin(20, "uatom"),
+        app.wrap().query_balance(&alice, "uatom").unwrap()
+    );
+    assert_eq!(
+        coin(30, "uatom"),
+        app.wrap().query_balance(&bob, "uatom").unwrap()
+    );
+    assert_eq!(
+        coin(50, "uatom"),
+        app.wrap().query_balance(&owner, "uatom").unwrap()
+    );
+
+    // inputs and outputs must match
+    app.execute(
+        owner.clone(),
+        multi_send(50, &[(alice.as_str(), 20), (bob.as_str(), 20)]),
+    )
+    .unwrap_err();
+
+    // the signer must be the sender
+    app.execute(alice.clone(), multi_send(10, &[(bob.as_str(), 10)]))
+        .unwrap_err();
+}
+
+#[test]
+fn stargate_keeper_should_call_registered_handlers() {
+    let stargate_keeper = StargateKeeper::new().with_msg_handler(
+        "/test.MsgBurn",
+        |_storage, block, sender, msg: MsgSend| {
+            Ok(StargateMsgResult {
+                messages: vec![BankMsg::Burn {
+                    amount: vec![coin(msg.amount[0].amount.parse().unwrap(), "uatom")],
+                }
+                .into()],
+                events: vec![Event::new("burn")
+                    .add_attribute("sender", sender)
+                    .add_attribute("height", block.height.to_string())],
+                data: Some(b"burned".into()),
+                ..Default::default()
+            })
+        },
+    );
+    let owner = MockApi::default().addr_make("owner");
+    let mut app =
+        AppBuilder::default()
+            .with_stargate(stargate_keeper)
+            .build(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(storage, &owner, coins(100, "uatom"))
+                    .unwrap();
+            });
+
+    let msg = MsgSend {
+        amount: vec![ProtoCoin {
+            denom: "uatom".to_string(),
+            amount: "30".to_string(),
+        }],
+        ..Default::default()
+    };
+    let res = app
+        .execute(owner.clone(), any_msg("/test.MsgBurn", msg))
+        .unwrap();
+    assert_eq!(Some(b"burned".into()), res.data);
+    assert!(res.has_event(&Event::new("burn").add_attribute("sender", owner.as_str())));
+    assert_eq!(
+        coin(70, "uatom"),
+        app.wrap().query_balance(&owner, "uatom").unwrap()
+    );
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryPriceRequest {
+    #[prost(string, tag = "1")]
+    denom: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryPriceResponse {
+    #[prost(string, tag = "1")]
+    price: String,
+}
+
+#[test]
+fn stargate_keeper_should_call_registered_query_handlers() {
+    let stargate_keeper = StargateKeeper::new().with_query_handler(
+        "/oracle.v1.Query/Price",
+        |_storage, _querier, block, request: QueryPriceRequest| {
+            Ok(QueryPriceResponse {
+                price: format!("{}@{}", request.denom, block.height),
+            })
+        },
+    );
+    let app = AppBuilder::default()
+        .with_stargate(stargate_keeper)
+        .build(no_init);
+    let height = app.block_info().height;
+    let request = QueryPriceRequest {
+        denom: "uatom".to_string(),
+    };
+
+    // grpc query
+    let query: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+        path: "/oracle.v1.Query/Price".to_string(),
+        data: request.encode_to_vec().into(),
+    });
+    let data = app
+        .raw_query(&to_json_vec(&query).unwrap())
+        .unwrap()
+        .unwrap();
+    let response = QueryPriceResponse::decode(data.as_slice()).unwrap();
+    assert_eq!(format!("uatom@{}", height), response.price);
+
+    // stargate query
+    #[allow(deprecated)]
+    let query: QueryRequest<Empty> = QueryRequest::Stargate {
+        path: "/oracle.v1.Query/Price".to_string(),
+        data: request.encode_to_vec().into(),
+    };
+    let data = app
+        .raw_query(&to_json_vec(&query).unwrap())
+        .unwrap()
+        .unwrap();
+    let response = QueryPriceResponse::decode(data.as_slice()).unwrap();
+    assert_eq!(format!("uatom@{}", height), response.price);
+
+    // queries with unknown paths are rejected
+    let query: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+        path: "/oracle.v1.Query/Unknown".to_string(),
+        data: request.encode_to_vec().into(),
+    });
+    assert!(app
+        .raw_query(&to_json_vec(&query).unwrap())
+        .unwrap()
+        .into_result()
+        .is_err());
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PageRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    limit: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PageResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    next_key: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryTotalSupplyRequest {
+    #[prost(message, optional, tag = "1")]
+    pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryTotalSupplyResponse {
+    #[prost(message, repeated, tag = "1")]
+    supply: Vec<ProtoCoin>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+#[test]
+fn stargate_keeper_should_query_total_supply() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new())
+        .build(no_init);
+    let alice = app.api().addr_make("alice");
+    let bob = app.api().addr_make("bob");
+    app.init_balances(&[
+        (alice, vec![coin(10, "uatom"), coin(20, "uosmo")]),
+        (bob, vec![coin(5, "uatom"), coin(1, "ujuno")]),
+    ])
+    .unwrap();
+    let query_page = |key: Vec<u8>| {
+        let request = QueryTotalSupplyRequest {
+            pagination: Some(PageRequest { key, limit: 2 }),
+        };
+        let query: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+            path: "/cosmos.bank.v1beta1.Query/TotalSupply".to_string(),
+            data: request.encode_to_vec().into(),
+        });
+        let data = app
+            .raw_query(&to_json_vec(&query).unwrap())
+            .unwrap()
+            .unwrap();
+        QueryTotalSupplyResponse::decode(data.as_slice()).unwrap()
+    };
+
+    let response = query_page(vec![]);
+    let supply: Vec<(String, String)> = response
+        .supply
+        .into_iter()
+        .map(|coin| (coin.denom, coin.amount))
+        .collect();
+    assert_eq!(
+        supply,
+        [
+            ("uatom".to_string(), "15".to_string()),
+            ("ujuno".to_string(), "1".to_string())
+        ]
+    );
+    let next_key = response.pagination.unwrap().next_key;
+    assert_eq!(b"uosmo".to_vec(), next_key);
+
+    let response = query_page(next_key);
+    assert_eq!(1, response.supply.len());
+    assert_eq!("20", response.supply[0].amount);
+    assert!(response.pagination.unwrap().next_key.is_empty());
+}
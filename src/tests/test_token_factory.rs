@@ -0,0 +1,334 @@
+use crate::token_factory::*;
+use crate::{no_init, AppBuilder, Executor, StargateKeeper, StargateMsgResult};
+use cosmwasm_std::{
+    coin, to_json_vec, Addr, AnyMsg, Binary, CosmosMsg, Empty, Event, GrpcQuery, Querier,
+    QueryRequest,
+};
+use prost::Message;
+
+fn any_msg(type_url: &str, msg: impl Message) -> CosmosMsg {
+    CosmosMsg::Any(AnyMsg {
+        type_url: type_url.to_string(),
+        value: Binary::from(msg.encode_to_vec()),
+    })
+}
+
+fn grpc_query<T: Message + Default>(querier: &dyn Querier, path: &str, request: impl Message) -> T {
+    let request: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+        path: path.to_string(),
+        data: Binary::from(request.encode_to_vec()),
+    });
+    let data = querier
+        .raw_query(&to_json_vec(&request).unwrap())
+        .unwrap()
+        .unwrap();
+    T::decode(data.as_slice()).unwrap()
+}
+
+fn mint(sender: &Addr, amount: u128, denom: &str, to: &Addr) -> CosmosMsg {
+    any_msg(
+        MsgMint::TYPE_URL,
+        MsgMint {
+            sender: sender.to_string(),
+            amount: Some(ProtoCoin {
+                denom: denom.to_string(),
+                amount: amount.to_string(),
+            }),
+            mint_to_address: to.to_string(),
+        },
+    )
+}
+
+#[test]
+fn create_mint_and_burn_should_work() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new().with_token_factory())
+        .build(no_init);
+
+    let creator = app.api().addr_make("creator");
+    let user = app.api().addr_make("user");
+
+    // create a new denom
+    let msg = any_msg(
+        MsgCreateDenom::TYPE_URL,
+        MsgCreateDenom {
+            sender: creator.to_string(),
+            subdenom: "token".to_string(),
+        },
+    );
+    let res = app.execute(creator.clone(), msg.clone()).unwrap();
+    let denom = format!("factory/{}/token", creator);
+    let data = MsgCreateDenomResponse::decode(res.data.unwrap().as_slice()).unwrap();
+    assert_eq!(denom, data.new_token_denom);
+
+    // the same denom can not be created twice
+    app.execute(creator.clone(), msg).unwrap_err();
+
+    // mint tokens to user, balances are visible via bank queries
+    app.execute(creator.clone(), mint(&creator, 100, &denom, &user))
+        .unwrap();
+    assert_eq!(
+        coin(100, &denom),
+        app.wrap().query_balance(&user, &denom).unwrap()
+    );
+    assert_eq!(coin(100, &denom), app.wrap().query_supply(&denom).unwrap());
+
+    // only admin can mint
+    app.execute(user.clone(), mint(&user, 100, &denom, &user))
+        .unwrap_err();
+
+    // burn tokens from user
+    let burn = any_msg(
+        MsgBurn::TYPE_URL,
+        MsgBurn {
+            sender: creator.to_string(),
+            amount: Some(ProtoCoin {
+                denom: denom.clone(),
+                amount: "40".to_string(),
+            }),
+            burn_from_address: user.to_string(),
+        },
+    );
+    app.execute(creator.clone(), burn).unwrap();
+    assert_eq!(
+        coin(60, &denom),
+        app.wrap().query_balance(&user, &denom).unwrap()
+    );
+}
+
+#[test]
+fn change_admin_and_metadata_should_work() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new().with_token_factory())
+        .build(no_init);
+
+    let creator = app.api().addr_make("creator");
+    let admin = app.api().addr_make("admin");
+
+    let msg = any_msg(
+        MsgCreateDenom::TYPE_URL,
+        MsgCreateDenom {
+            sender: creator.to_string(),
+            subdenom: "token".to_string(),
+        },
+    );
+    app.execute(creator.clone(), msg).unwrap();
+    let denom = format!("factory/{}/token", creator);
+
+    // change the admin
+    let msg = any_msg(
+        MsgChangeAdmin::TYPE_URL,
+        MsgChangeAdmin {
+            sender: creator.to_string(),
+            denom: denom.clone(),
+            new_admin: admin.to_string(),
+        },
+    );
+    app.execute(creator.clone(), msg).unwrap();
+
+    let res: QueryDenomAuthorityMetadataResponse = grpc_query(
+        &app,
+        QueryDenomAuthorityMetadataRequest::PATH,
+        QueryDenomAuthorityMetadataRequest {
+            denom: denom.clone(),
+        },
+    );
+    assert_eq!(admin.to_string(), res.authority_metadata.unwrap().admin);
+
+    // former admin can not mint anymore, the new one can
+    app.execute(creator.clone(), mint(&creator, 1, &denom, &creator))
+        .unwrap_err();
+    app.execute(admin.clone(), mint(&admin, 1, &denom, &creator))
+        .unwrap();
+
+    // set metadata
+    let msg = any_msg(
+        MsgSetDenomMetadata::TYPE_URL,
+        MsgSetDenomMetadata {
+            sender: admin.to_string(),
+            metadata: Some(ProtoMetadata {
+                base: denom.clone(),
+                display: "TOKEN".to_string(),
+                symbol: "TKN".to_string(),
+                denom_units: vec![ProtoDenomUnit {
+                    denom: denom.clone(),
+                    exponent: 0,
+                    aliases: vec![],
+                }],
+                ..Default::default()
+            }),
+        },
+    );
+    app.execute(admin.clone(), msg).unwrap();
+    let metadata = app.wrap().query_denom_metadata(&denom).unwrap();
+    assert_eq!("TKN", metadata.symbol);
+    assert_eq!(1, metadata.denom_units.len());
+
+    // denoms from creator
+    let res: QueryDenomsFromCreatorResponse = grpc_query(
+        &app,
+        QueryDenomsFromCreatorRequest::PATH,
+        QueryDenomsFromCreatorRequest {
+            creator: creator.to_string(),
+        },
+    );
+    assert_eq!(vec![denom], res.denoms);
+}
+
+#[test]
+fn message_signer_should_match_sender() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new().with_token_factory())
+        .build(no_init);
+
+    let creator = app.api().addr_make("creator");
+    let user = app.api().addr_make("user");
+
+    let msg = any_msg(
+        MsgCreateDenom::TYPE_URL,
+        MsgCreateDenom {
+            sender: creator.to_string(),
+            subdenom: "token".to_string(),
+        },
+    );
+    app.execute(creator.clone(), msg).unwrap();
+    let denom = format!("factory/{}/token", creator);
+
+    // the message is sent by the user, but signed as the admin
+    let err = app
+        .execute(user.clone(), mint(&creator, 100, &denom, &user))
+        .unwrap_err();
+    assert_eq!(
+        format!("message signer {} does not match {}", user, creator),
+        err.root_cause().to_string()
+    );
+
+    // the message is sent by the admin, but signed as the user
+    let msg = any_msg(
+        MsgChangeAdmin::TYPE_URL,
+        MsgChangeAdmin {
+            sender: user.to_string(),
+            denom: denom.clone(),
+            new_admin: user.to_string(),
+        },
+    );
+    app.execute(creator.clone(), msg).unwrap_err();
+    assert!(app
+        .wrap()
+        .query_balance(&user, &denom)
+        .unwrap()
+        .amount
+        .is_zero());
+}
+
+#[test]
+fn denoms_from_creator_should_list_only_denoms_of_creator() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new().with_token_factory())
+        .build(no_init);
+
+    let alice = app.api().addr_make("alice");
+    let bob = app.api().addr_make("bob");
+    for (creator, subdenom) in [(&alice, "zeta"), (&bob, "beta"), (&alice, "alpha")] {
+        let msg = any_msg(
+            MsgCreateDenom::TYPE_URL,
+            MsgCreateDenom {
+                sender: creator.to_string(),
+                subdenom: subdenom.to_string(),
+            },
+        );
+        app.execute(creator.clone(), msg).unwrap();
+    }
+
+    let res: QueryDenomsFromCreatorResponse = grpc_query(
+        &app,
+        QueryDenomsFromCreatorRequest::PATH,
+        QueryDenomsFromCreatorRequest {
+            creator: alice.to_string(),
+        },
+    );
+    assert_eq!(
+        vec![
+            format!("factory/{}/alpha", alice),
+            format!("factory/{}/zeta", alice)
+        ],
+        res.denoms
+    );
+
+    // denominations not created by the token factory have no admin
+    let request: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+        path: QueryDenomAuthorityMetadataRequest::PATH.to_string(),
+        data: Binary::from(
+            QueryDenomAuthorityMetadataRequest {
+                denom: "uatom".to_string(),
+            }
+            .encode_to_vec(),
+        ),
+    });
+    app.wrap().query::<Binary>(&request).unwrap_err();
+}
+
+#[test]
+fn token_factory_should_coexist_with_other_stargate_handlers() {
+    let stargate_keeper = StargateKeeper::new().with_token_factory().with_msg_handler(
+        "/test.MsgPing",
+        |_, _, _, _: MsgCreateDenom| {
+            Ok(StargateMsgResult {
+                events: vec![Event::new("pong")],
+                ..Default::default()
+            })
+        },
+    );
+    let mut app = AppBuilder::default()
+        .with_stargate(stargate_keeper)
+        .build(no_init);
+
+    let creator = app.api().addr_make("creator");
+    let user = app.api().addr_make("user");
+
+    let msg = any_msg(
+        MsgCreateDenom::TYPE_URL,
+        MsgCreateDenom {
+            sender: creator.to_string(),
+            subdenom: "token".to_string(),
+        },
+    );
+    app.execute(creator.clone(), msg).unwrap();
+    let denom = format!("factory/{}/token", creator);
+    app.execute(creator.clone(), mint(&creator, 100, &denom, &creator))
+        .unwrap();
+
+    // factory tokens are sent with the default bank handler
+    let send = any_msg(
+        "/cosmos.bank.v1beta1.MsgSend",
+        ProtoMsgSend {
+            from_address: creator.to_string(),
+            to_address: user.to_string(),
+            amount: vec![ProtoCoin {
+                denom: denom.clone(),
+                amount: "30".to_string(),
+            }],
+        },
+    );
+    app.execute(creator.clone(), send).unwrap();
+    assert_eq!(
+        coin(30, &denom),
+        app.wrap().query_balance(&user, &denom).unwrap()
+    );
+
+    // user handlers are still called
+    let ping = any_msg("/test.MsgPing", MsgCreateDenom::default());
+    let res = app.execute(user, ping).unwrap();
+    assert!(res.has_event(&Event::new("pong")));
+}
+
+/// Protobuf encoded `cosmos.bank.v1beta1.MsgSend`.
+#[derive(Clone, PartialEq, Message)]
+struct ProtoMsgSend {
+    #[prost(string, tag = "1")]
+    from_address: String,
+    #[prost(string, tag = "2")]
+    to_address: String,
+    #[prost(message, repeated, tag = "3")]
+    amount: Vec<ProtoCoin>,
+}
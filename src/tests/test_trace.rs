@@ -0,0 +1,104 @@
+use crate::test_helpers::{caller, hackatom};
+use crate::{no_init, AppBuilder, Executor, WasmKeeper};
+use cosmwasm_std::{to_json_binary, to_json_vec, Binary, Empty, WasmMsg};
+
+#[test]
+fn tracing_should_be_disabled_by_default() {
+    let app = AppBuilder::default().build(no_init);
+    assert!(app.last_trace().is_none());
+}
+
+#[test]
+fn tracing_should_record_nested_calls() {
+    let mut app = AppBuilder::default()
+        .with_wasm(WasmKeeper::new().with_tracing())
+        .build(no_init);
+    let owner = app.api().addr_make("owner");
+    let beneficiary = app.api().addr_make("beneficiary");
+    let caller_code_id = app.store_code(caller::contract());
+    let hackatom_code_id = app.store_code(hackatom::contract());
+    let caller_addr = app
+        .instantiate_contract(
+            caller_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "caller",
+            None,
+        )
+        .unwrap();
+
+    // caller instantiates hackatom contract
+    let init_msg = to_json_binary(&hackatom::InstantiateMsg {
+        beneficiary: beneficiary.to_string(),
+    })
+    .unwrap();
+    let msg = WasmMsg::Instantiate {
+        admin: None,
+        code_id: hackatom_code_id,
+        msg: init_msg.clone(),
+        funds: vec![],
+        label: "hackatom".to_string(),
+    };
+    let res = app
+        .execute_contract(owner.clone(), caller_addr.clone(), &msg, &[])
+        .unwrap();
+
+    let trace = app.last_trace().unwrap();
+    assert_eq!(1, trace.calls.len());
+    let call = &trace.calls[0];
+    assert_eq!(caller_addr, call.contract);
+    assert_eq!("execute", call.entry_point);
+    assert_eq!(Binary::from(to_json_vec(&msg).unwrap()), call.msg);
+    assert_eq!(res.events, call.events);
+    assert!(call.error.is_none());
+    assert!(call.storage_writes.is_empty());
+
+    assert_eq!(1, call.calls.len());
+    let nested = &call.calls[0];
+    assert_eq!("instantiate", nested.entry_point);
+    assert_eq!(init_msg, nested.msg);
+    assert_eq!(1, nested.storage_writes.len());
+    assert_eq!(Binary::from(b"hackatom"), nested.storage_writes[0].0);
+    assert!(nested.calls.is_empty());
+
+    // trace is exportable as JSON
+    assert!(trace
+        .to_json()
+        .unwrap()
+        .contains("\"entry_point\":\"instantiate\""));
+    assert!(trace.first_failure().is_none());
+}
+
+#[test]
+fn tracing_should_record_failed_calls() {
+    let mut app = AppBuilder::default()
+        .with_wasm(WasmKeeper::new().with_tracing())
+        .build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(caller::contract());
+    let outer = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "outer", None)
+        .unwrap();
+    let inner = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "inner", None)
+        .unwrap();
+
+    // outer caller passes a message that is not a WasmMsg to the inner caller
+    let msg = WasmMsg::Execute {
+        contract_addr: inner.to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: vec![],
+    };
+    app.execute_contract(owner, outer.clone(), &msg, &[])
+        .unwrap_err();
+
+    // trace of the failed transaction is available
+    let trace = app.last_trace().unwrap();
+    assert_eq!(1, trace.calls.len());
+    assert_eq!(outer, trace.calls[0].contract);
+    assert!(trace.calls[0].error.is_some());
+    let failure = trace.first_failure().unwrap();
+    assert_eq!(inner, failure.contract);
+    assert_eq!("execute", failure.entry_point);
+}
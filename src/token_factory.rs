@@ -0,0 +1,459 @@
+//! # Token factory module
+//!
+//! Emulates the `x/tokenfactory` module available on chains like Osmosis or Neutron.
+//! Token factory messages are sent as `CosmosMsg::Any` (or `CosmosMsg::Stargate`) messages
+//! with Protobuf encoded values, and queried with `QueryRequest::Grpc` queries,
+//! so they are handled by [StargateKeeper] after calling
+//! [with_token_factory](StargateKeeper::with_token_factory), next to other stargate handlers.
+//!
+//! Balances of factory denominations are kept by the bank module,
+//! so they are reported by all bank queries.
+//!
+//! # Example
+//!
+//! ```
+//! use cosmwasm_std::{AnyMsg, Binary, CosmosMsg};
+//! use cw_multi_test::token_factory::MsgCreateDenom;
+//! use cw_multi_test::{no_init, AppBuilder, Executor, StargateKeeper};
+//! use prost::Message;
+//!
+//! let mut app = AppBuilder::default()
+//!     .with_stargate(StargateKeeper::new().with_token_factory())
+//!     .build(no_init);
+//!
+//! let creator = app.api().addr_make("creator");
+//!
+//! let msg = MsgCreateDenom {
+//!     sender: creator.to_string(),
+//!     subdenom: "token".to_string(),
+//! };
+//! let msg = CosmosMsg::Any(AnyMsg {
+//!     type_url: MsgCreateDenom::TYPE_URL.to_string(),
+//!     value: Binary::from(msg.encode_to_vec()),
+//! });
+//! app.execute(creator.clone(), msg).unwrap();
+//! ```
+
+use crate::error::{anyhow, bail, AnyResult};
+use crate::prefixed_storage::{prefixed, prefixed_read};
+use crate::{BankSudo, StargateKeeper, StargateMsgResult};
+use cosmwasm_std::{
+    coins, Addr, Api, Binary, DenomMetadata, DenomUnit, Event, Order, StdResult, Storage, Uint128,
+};
+use cw_storage_plus::Map;
+use prost::Message;
+
+/// Administrators of denominations created by the token factory,
+/// keyed by the creator and the sub-denomination.
+const DENOM_ADMINS: Map<(&str, &str), Addr> = Map::new("admins");
+
+/// Default storage namespace for token factory module.
+const NAMESPACE_TOKEN_FACTORY: &[u8] = b"tokenfactory";
+
+/// Protobuf encoded `cosmos.base.v1beta1.Coin`.
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoCoin {
+    /// Denomination.
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    /// Amount encoded as decimal string.
+    #[prost(string, tag = "2")]
+    pub amount: String,
+}
+
+/// Protobuf encoded `cosmos.bank.v1beta1.DenomUnit`.
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoDenomUnit {
+    /// Name of the denomination unit.
+    #[prost(string, tag = "1")]
+    pub denom: String,
+    /// Power of 10 relating this unit to the base unit.
+    #[prost(uint32, tag = "2")]
+    pub exponent: u32,
+    /// Aliases of the denomination unit.
+    #[prost(string, repeated, tag = "3")]
+    pub aliases: Vec<String>,
+}
+
+/// Protobuf encoded `cosmos.bank.v1beta1.Metadata`.
+#[derive(Clone, PartialEq, Message)]
+pub struct ProtoMetadata {
+    /// Description of the token.
+    #[prost(string, tag = "1")]
+    pub description: String,
+    /// Units of the token.
+    #[prost(message, repeated, tag = "2")]
+    pub denom_units: Vec<ProtoDenomUnit>,
+    /// Base denomination.
+    #[prost(string, tag = "3")]
+    pub base: String,
+    /// Display denomination.
+    #[prost(string, tag = "4")]
+    pub display: String,
+    /// Name of the token.
+    #[prost(string, tag = "5")]
+    pub name: String,
+    /// Symbol of the token.
+    #[prost(string, tag = "6")]
+    pub symbol: String,
+    /// URI to a document with additional information.
+    #[prost(string, tag = "7")]
+    pub uri: String,
+    /// SHA-256 hash of the document pointed by URI.
+    #[prost(string, tag = "8")]
+    pub uri_hash: String,
+}
+
+/// Message creating a new denomination `factory/{sender}/{subdenom}`.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgCreateDenom {
+    /// Creator and the first administrator of the denomination.
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    /// Sub-denomination.
+    #[prost(string, tag = "2")]
+    pub subdenom: String,
+}
+
+/// Response to [MsgCreateDenom], returned as the `data` of the response.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgCreateDenomResponse {
+    /// Full name of the created denomination.
+    #[prost(string, tag = "1")]
+    pub new_token_denom: String,
+}
+
+/// Message minting tokens of the factory denomination.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgMint {
+    /// Administrator of the denomination.
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    /// Minted amount.
+    #[prost(message, optional, tag = "2")]
+    pub amount: Option<ProtoCoin>,
+    /// Recipient of minted tokens, defaults to sender when empty.
+    #[prost(string, tag = "3")]
+    pub mint_to_address: String,
+}
+
+/// Message burning tokens of the factory denomination.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgBurn {
+    /// Administrator of the denomination.
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    /// Burned amount.
+    #[prost(message, optional, tag = "2")]
+    pub amount: Option<ProtoCoin>,
+    /// Account the tokens are burned from, defaults to sender when empty.
+    #[prost(string, tag = "3")]
+    pub burn_from_address: String,
+}
+
+/// Message changing the administrator of the factory denomination.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgChangeAdmin {
+    /// Current administrator of the denomination.
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    /// Denomination.
+    #[prost(string, tag = "2")]
+    pub denom: String,
+    /// New administrator of the denomination.
+    #[prost(string, tag = "3")]
+    pub new_admin: String,
+}
+
+/// Message setting the metadata of the factory denomination.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgSetDenomMetadata {
+    /// Administrator of the denomination.
+    #[prost(string, tag = "1")]
+    pub sender: String,
+    /// Metadata, the base denomination must be the factory denomination.
+    #[prost(message, optional, tag = "2")]
+    pub metadata: Option<ProtoMetadata>,
+}
+
+/// Query returning the administrator of the factory denomination.
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryDenomAuthorityMetadataRequest {
+    /// Denomination.
+    #[prost(string, tag = "1")]
+    pub denom: String,
+}
+
+/// Authority metadata of the factory denomination.
+#[derive(Clone, PartialEq, Message)]
+pub struct DenomAuthorityMetadata {
+    /// Administrator of the denomination, empty when there is no administrator.
+    #[prost(string, tag = "1")]
+    pub admin: String,
+}
+
+/// Response to [QueryDenomAuthorityMetadataRequest].
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryDenomAuthorityMetadataResponse {
+    /// Authority metadata of the denomination.
+    #[prost(message, optional, tag = "1")]
+    pub authority_metadata: Option<DenomAuthorityMetadata>,
+}
+
+/// Query returning all denominations created by specified creator.
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryDenomsFromCreatorRequest {
+    /// Creator of denominations.
+    #[prost(string, tag = "1")]
+    pub creator: String,
+}
+
+/// Response to [QueryDenomsFromCreatorRequest].
+#[derive(Clone, PartialEq, Message)]
+pub struct QueryDenomsFromCreatorResponse {
+    /// Denominations created by the creator, sorted by name.
+    #[prost(string, repeated, tag = "1")]
+    pub denoms: Vec<String>,
+}
+
+impl MsgCreateDenom {
+    /// Type URL of this message.
+    pub const TYPE_URL: &'static str = "/osmosis.tokenfactory.v1beta1.MsgCreateDenom";
+}
+
+impl MsgMint {
+    /// Type URL of this message.
+    pub const TYPE_URL: &'static str = "/osmosis.tokenfactory.v1beta1.MsgMint";
+}
+
+impl MsgBurn {
+    /// Type URL of this message.
+    pub const TYPE_URL: &'static str = "/osmosis.tokenfactory.v1beta1.MsgBurn";
+}
+
+impl MsgChangeAdmin {
+    /// Type URL of this message.
+    pub const TYPE_URL: &'static str = "/osmosis.tokenfactory.v1beta1.MsgChangeAdmin";
+}
+
+impl MsgSetDenomMetadata {
+    /// Type URL of this message.
+    pub const TYPE_URL: &'static str = "/osmosis.tokenfactory.v1beta1.MsgSetDenomMetadata";
+}
+
+impl QueryDenomAuthorityMetadataRequest {
+    /// Path of this query.
+    pub const PATH: &'static str = "/osmosis.tokenfactory.v1beta1.Query/DenomAuthorityMetadata";
+}
+
+impl QueryDenomsFromCreatorRequest {
+    /// Path of this query.
+    pub const PATH: &'static str = "/osmosis.tokenfactory.v1beta1.Query/DenomsFromCreator";
+}
+
+impl StargateKeeper {
+    /// Registers handlers of `x/tokenfactory` messages and queries,
+    /// see [module](crate::token_factory) documentation.
+    pub fn with_token_factory(self) -> Self {
+        self.with_api_msg_handler(
+            MsgCreateDenom::TYPE_URL,
+            |api, storage, _, sender, msg: MsgCreateDenom| {
+                let creator = ensure_signer(api, &msg.sender, sender)?;
+                if msg.subdenom.is_empty() || msg.subdenom.contains('/') {
+                    bail!("invalid subdenom: {}", msg.subdenom);
+                }
+                let denom = format!("factory/{}/{}", creator, msg.subdenom);
+                let mut storage = prefixed(storage, NAMESPACE_TOKEN_FACTORY);
+                let key = (creator.as_str(), msg.subdenom.as_str());
+                if DENOM_ADMINS.has(&storage, key) {
+                    bail!("denom already exists: {}", denom);
+                }
+                DENOM_ADMINS.save(&mut storage, key, &creator)?;
+                let data = MsgCreateDenomResponse {
+                    new_token_denom: denom.clone(),
+                }
+                .encode_to_vec();
+                Ok(StargateMsgResult {
+                    events: vec![Event::new("create_denom")
+                        .add_attribute("creator", creator)
+                        .add_attribute("new_token_denom", denom)],
+                    data: Some(Binary::from(data)),
+                    ..Default::default()
+                })
+            },
+        )
+        .with_api_msg_handler(
+            MsgMint::TYPE_URL,
+            |api, storage, _, sender, msg: MsgMint| {
+                ensure_signer(api, &msg.sender, sender)?;
+                let (amount, denom) = proto_amount(msg.amount)?;
+                ensure_admin(storage, &denom, sender)?;
+                let recipient = if msg.mint_to_address.is_empty() {
+                    sender.clone()
+                } else {
+                    api.addr_validate(&msg.mint_to_address)?
+                };
+                Ok(StargateMsgResult {
+                    sudo_messages: vec![BankSudo::Mint {
+                        to_address: recipient.to_string(),
+                        amount: coins(amount.u128(), &denom),
+                    }
+                    .into()],
+                    events: vec![Event::new("tf_mint")
+                        .add_attribute("mint_to_address", recipient)
+                        .add_attribute("amount", format!("{}{}", amount, denom))],
+                    ..Default::default()
+                })
+            },
+        )
+        .with_api_msg_handler(
+            MsgBurn::TYPE_URL,
+            |api, storage, _, sender, msg: MsgBurn| {
+                ensure_signer(api, &msg.sender, sender)?;
+                let (amount, denom) = proto_amount(msg.amount)?;
+                ensure_admin(storage, &denom, sender)?;
+                let burn_from = if msg.burn_from_address.is_empty() {
+                    sender.clone()
+                } else {
+                    api.addr_validate(&msg.burn_from_address)?
+                };
+                Ok(StargateMsgResult {
+                    sudo_messages: vec![BankSudo::Burn {
+                        from_address: burn_from.to_string(),
+                        amount: coins(amount.u128(), &denom),
+                    }
+                    .into()],
+                    events: vec![Event::new("tf_burn")
+                        .add_attribute("burn_from_address", burn_from)
+                        .add_attribute("amount", format!("{}{}", amount, denom))],
+                    ..Default::default()
+                })
+            },
+        )
+        .with_api_msg_handler(
+            MsgChangeAdmin::TYPE_URL,
+            |api, storage, _, sender, msg: MsgChangeAdmin| {
+                ensure_signer(api, &msg.sender, sender)?;
+                ensure_admin(storage, &msg.denom, sender)?;
+                let new_admin = api.addr_validate(&msg.new_admin)?;
+                let mut storage = prefixed(storage, NAMESPACE_TOKEN_FACTORY);
+                DENOM_ADMINS.save(&mut storage, denom_key(&msg.denom)?, &new_admin)?;
+                Ok(StargateMsgResult {
+                    events: vec![Event::new("change_admin")
+                        .add_attribute("denom", msg.denom)
+                        .add_attribute("new_admin", new_admin)],
+                    ..Default::default()
+                })
+            },
+        )
+        .with_api_msg_handler(
+            MsgSetDenomMetadata::TYPE_URL,
+            |api, storage, _, sender, msg: MsgSetDenomMetadata| {
+                ensure_signer(api, &msg.sender, sender)?;
+                let metadata = msg
+                    .metadata
+                    .ok_or_else(|| anyhow!("metadata is required"))?;
+                ensure_admin(storage, &metadata.base, sender)?;
+                let denom = metadata.base.clone();
+                let metadata = DenomMetadata {
+                    description: metadata.description,
+                    denom_units: metadata
+                        .denom_units
+                        .into_iter()
+                        .map(|unit| DenomUnit {
+                            denom: unit.denom,
+                            exponent: unit.exponent,
+                            aliases: unit.aliases,
+                        })
+                        .collect(),
+                    base: metadata.base,
+                    display: metadata.display,
+                    name: metadata.name,
+                    symbol: metadata.symbol,
+                    uri: metadata.uri,
+                    uri_hash: metadata.uri_hash,
+                };
+                Ok(StargateMsgResult {
+                    sudo_messages: vec![BankSudo::SetDenomMetadata {
+                        denom: denom.clone(),
+                        metadata,
+                    }
+                    .into()],
+                    events: vec![Event::new("set_denom_metadata").add_attribute("denom", denom)],
+                    ..Default::default()
+                })
+            },
+        )
+        .with_query_handler(
+            QueryDenomAuthorityMetadataRequest::PATH,
+            |storage, _, _, req: QueryDenomAuthorityMetadataRequest| {
+                Ok(QueryDenomAuthorityMetadataResponse {
+                    authority_metadata: Some(DenomAuthorityMetadata {
+                        admin: admin(storage, &req.denom)?.to_string(),
+                    }),
+                })
+            },
+        )
+        .with_query_handler(
+            QueryDenomsFromCreatorRequest::PATH,
+            |storage, _, _, req: QueryDenomsFromCreatorRequest| {
+                let storage = prefixed_read(storage, NAMESPACE_TOKEN_FACTORY);
+                let denoms = DENOM_ADMINS
+                    .prefix(&req.creator)
+                    .keys(&storage, None, None, Order::Ascending)
+                    .map(|subdenom| Ok(format!("factory/{}/{}", req.creator, subdenom?)))
+                    .collect::<StdResult<_>>()?;
+                Ok(QueryDenomsFromCreatorResponse { denoms })
+            },
+        )
+    }
+}
+
+/// Splits the factory denomination `factory/{creator}/{subdenom}`
+/// into the creator and the sub-denomination.
+fn denom_key(denom: &str) -> AnyResult<(&str, &str)> {
+    denom
+        .strip_prefix("factory/")
+        .and_then(|rest| rest.split_once('/'))
+        .ok_or_else(|| anyhow!("denom does not exist: {}", denom))
+}
+
+/// Returns the administrator of specified denomination,
+/// fails when the denomination was not created by the token factory.
+fn admin(storage: &dyn Storage, denom: &str) -> AnyResult<Addr> {
+    let storage = prefixed_read(storage, NAMESPACE_TOKEN_FACTORY);
+    DENOM_ADMINS
+        .may_load(&storage, denom_key(denom)?)?
+        .ok_or_else(|| anyhow!("denom does not exist: {}", denom))
+}
+
+/// Checks if the signer declared in the message is the sender of the message.
+fn ensure_signer(api: &dyn Api, signer: &str, sender: &Addr) -> AnyResult<Addr> {
+    let signer = api.addr_validate(signer)?;
+    if signer != sender {
+        bail!("message signer {} does not match {}", sender, signer);
+    }
+    Ok(signer)
+}
+
+/// Checks if the sender is the administrator of specified denomination.
+fn ensure_admin(storage: &dyn Storage, denom: &str, sender: &Addr) -> AnyResult<()> {
+    if admin(storage, denom)? != sender {
+        bail!(
+            "unauthorized account: {} is not the admin of {}",
+            sender,
+            denom
+        );
+    }
+    Ok(())
+}
+
+/// Converts Protobuf encoded coin into amount and denomination.
+fn proto_amount(coin: Option<ProtoCoin>) -> AnyResult<(Uint128, String)> {
+    let coin = coin.ok_or_else(|| anyhow!("amount is required"))?;
+    let amount: Uint128 = coin.amount.parse()?;
+    if amount.is_zero() {
+        bail!("amount must be positive");
+    }
+    Ok((amount, coin.denom))
+}
@@ -0,0 +1,171 @@
+//! # Tracing of contract calls executed by [WasmKeeper](crate::WasmKeeper)
+
+use crate::error::AnyResult;
+use crate::AppResponse;
+use cosmwasm_std::{to_json_string, Addr, Binary, Event, Order, Record, Storage};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Trace of all contract calls made while processing the last transaction,
+/// see [App::last_trace](crate::App::last_trace).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Trace {
+    /// Contract calls made directly by the transaction messages, in execution order.
+    pub calls: Vec<CallTrace>,
+}
+
+impl Trace {
+    /// Returns this trace serialized to JSON.
+    pub fn to_json(&self) -> AnyResult<String> {
+        Ok(to_json_string(self)?)
+    }
+
+    /// Returns the first failed call found in the depth-first order, when any.
+    ///
+    /// Nested calls are checked before their parent, so the returned call
+    /// is the deepest one where the failure originated.
+    pub fn first_failure(&self) -> Option<&CallTrace> {
+        fn find(calls: &[CallTrace]) -> Option<&CallTrace> {
+            calls
+                .iter()
+                .find_map(|call| find(&call.calls).or_else(|| call.error.is_some().then_some(call)))
+        }
+        find(&self.calls)
+    }
+}
+
+/// Single call of a contract's entry-point, together with all calls
+/// made while processing the messages returned by this entry-point.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CallTrace {
+    /// Address of the called contract.
+    pub contract: Addr,
+    /// Name of the called entry-point, like `instantiate`, `execute` or `reply`.
+    pub entry_point: String,
+    /// Message passed to the entry-point (JSON-serialized [Reply](cosmwasm_std::Reply) for `reply`).
+    pub msg: Binary,
+    /// Events emitted by this call, including events from nested calls.
+    pub events: Vec<Event>,
+    /// Data returned by this call.
+    pub data: Option<Binary>,
+    /// Error message, when the call failed.
+    pub error: Option<String>,
+    /// Gas consumed by this call, including nested calls. Zero when gas is not metered.
+    pub gas_used: u64,
+    /// Writes to the contract storage made by the entry-point, in order.
+    /// Removed keys have no value.
+    pub storage_writes: Vec<(Binary, Option<Binary>)>,
+    /// Calls made while processing messages returned by the entry-point.
+    pub calls: Vec<CallTrace>,
+}
+
+/// Tracer building the tree of contract calls.
+#[derive(Default)]
+pub(crate) struct Tracer {
+    state: Mutex<TracerState>,
+}
+
+#[derive(Default)]
+struct TracerState {
+    /// Calls that are still being processed, the innermost one is the last.
+    stack: Vec<CallTrace>,
+    /// Completed top-level calls.
+    calls: Vec<CallTrace>,
+}
+
+impl Tracer {
+    /// Starts tracing a call of the contract's entry-point.
+    pub fn enter(&self, contract: &Addr, entry_point: &str, msg: &[u8]) {
+        self.state.lock().unwrap().stack.push(CallTrace {
+            contract: contract.clone(),
+            entry_point: entry_point.to_string(),
+            msg: msg.to_vec().into(),
+            events: vec![],
+            data: None,
+            error: None,
+            gas_used: 0,
+            storage_writes: vec![],
+            calls: vec![],
+        });
+    }
+
+    /// Completes tracing of the innermost call with its result.
+    pub fn exit(&self, result: &AnyResult<AppResponse>, gas_used: u64) {
+        let mut state = self.state.lock().unwrap();
+        let Some(mut call) = state.stack.pop() else {
+            return;
+        };
+        match result {
+            Ok(res) => {
+                call.events = res.events.clone();
+                call.data = res.data.clone();
+            }
+            Err(err) => call.error = Some(err.to_string()),
+        }
+        call.gas_used = gas_used;
+        match state.stack.last_mut() {
+            Some(parent) => parent.calls.push(call),
+            None => state.calls.push(call),
+        }
+    }
+
+    /// Records a write to the storage of the innermost call.
+    fn record_write(&self, key: &[u8], value: Option<&[u8]>) {
+        if let Some(call) = self.state.lock().unwrap().stack.last_mut() {
+            call.storage_writes
+                .push((key.to_vec().into(), value.map(|v| v.to_vec().into())));
+        }
+    }
+
+    /// Removes all traced calls.
+    pub fn reset(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.stack.clear();
+        state.calls.clear();
+    }
+
+    /// Returns the trace of all completed top-level calls.
+    pub fn trace(&self) -> Trace {
+        Trace {
+            calls: self.state.lock().unwrap().calls.clone(),
+        }
+    }
+}
+
+/// Storage recording every write to the wrapped storage in the tracer.
+pub(crate) struct TracedStorage<'a> {
+    storage: Box<dyn Storage + 'a>,
+    tracer: &'a Tracer,
+}
+
+impl<'a> TracedStorage<'a> {
+    /// Wraps the storage, all writes will be recorded by specified tracer.
+    pub fn new(storage: Box<dyn Storage + 'a>, tracer: &'a Tracer) -> Self {
+        Self { storage, tracer }
+    }
+}
+
+impl<'a> Storage for TracedStorage<'a> {
+    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.storage.get(key)
+    }
+
+    fn range<'b>(
+        &'b self,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        order: Order,
+    ) -> Box<dyn Iterator<Item = Record> + 'b> {
+        self.storage.range(start, end, order)
+    }
+
+    fn set(&mut self, key: &[u8], value: &[u8]) {
+        self.tracer.record_write(key, Some(value));
+        self.storage.set(key, value)
+    }
+
+    fn remove(&mut self, key: &[u8]) {
+        self.tracer.record_write(key, None);
+        self.storage.remove(key)
+    }
+}
@@ -1,5 +1,6 @@
 use crate::error::AnyResult;
-use cosmwasm_std::Storage;
+use crate::wasm::split_contract_key;
+use cosmwasm_std::{Addr, Storage};
 use cosmwasm_std::{Order, Record};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
@@ -21,6 +22,36 @@ where
     Ok(res)
 }
 
+/// Like [transactional], but also returns all writes committed to the base storage.
+pub fn transactional_with_writes<F, T>(
+    base: &mut dyn Storage,
+    action: F,
+) -> AnyResult<(T, Vec<StorageWrite>)>
+where
+    F: FnOnce(&mut dyn Storage, &dyn Storage) -> AnyResult<T>,
+{
+    let mut cache = StorageTransaction::new(base);
+    let res = action(&mut cache, base)?;
+    let rep_log = cache.prepare();
+    let writes = rep_log.writes(base);
+    rep_log.commit(base);
+    Ok((res, writes))
+}
+
+/// Single key written to the application storage,
+/// see [App::last_storage_writes](crate::App::last_storage_writes).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageWrite {
+    /// Address of the contract, when the key belongs to the contract's storage.
+    pub contract: Option<Addr>,
+    /// The key, relative to the contract's storage when the contract is set.
+    pub key: Vec<u8>,
+    /// Value before the write, `None` when the key did not exist.
+    pub old_value: Option<Vec<u8>>,
+    /// Value after the write, `None` when the key was removed.
+    pub new_value: Option<Vec<u8>>,
+}
+
 pub struct StorageTransaction<'a> {
     /// read-only access to backing storage
     storage: &'a dyn Storage,
@@ -119,6 +150,35 @@ impl RepLog {
         self.ops_log.push(op);
     }
 
+    /// returns all keys changed by the stored list of `Op`s, sorted by key,
+    /// with values from the provided `Storage` and values after applying the changes
+    fn writes(&self, storage: &dyn Storage) -> Vec<StorageWrite> {
+        let mut changes = BTreeMap::new();
+        for op in &self.ops_log {
+            let (key, value) = match op {
+                Op::Set { key, value } => (key, Some(value.clone())),
+                Op::Delete { key } => (key, None),
+            };
+            changes.insert(key.clone(), value);
+        }
+        changes
+            .into_iter()
+            .map(|(raw_key, new_value)| {
+                let old_value = storage.get(&raw_key);
+                let (contract, key) = match split_contract_key(&raw_key) {
+                    Some((contract, key)) => (Some(contract), key.to_vec()),
+                    None => (None, raw_key),
+                };
+                StorageWrite {
+                    contract,
+                    key,
+                    old_value,
+                    new_value,
+                }
+            })
+            .collect()
+    }
+
     /// applies the stored list of `Op`s to the provided `Storage`
     pub fn commit(self, storage: &mut dyn Storage) {
         for op in self.ops_log {
@@ -1,17 +1,24 @@
-use crate::addresses::{AddressGenerator, SimpleAddressGenerator};
+use crate::addresses::{module_address, AddressGenerator, SimpleAddressGenerator};
 use crate::app::{CosmosRouter, RouterQuerier};
 use crate::checksums::{ChecksumGenerator, SimpleChecksumGenerator};
 use crate::contracts::Contract;
-use crate::error::{bail, AnyContext, AnyError, AnyResult, Error};
+use crate::error::{bail, AnyError, AnyResult, AppError, Error};
 use crate::executor::AppResponse;
+use crate::gas::{GasCosts, GasMeter, MeteredStorage};
 use crate::prefixed_storage::{prefixed, prefixed_read, PrefixedStorage, ReadonlyPrefixedStorage};
+use crate::profiler::{Profiler, ProfilingReport};
+use crate::stargate::{paginate, PageResponse, ProtoPageRequest};
+use crate::storage_limits::{LimitedStorage, StorageLimits};
+use crate::trace::{Trace, TracedStorage, Tracer};
 use crate::transactions::transactional;
 use cosmwasm_std::testing::mock_wasmd_attr;
 use cosmwasm_std::{
-    to_json_binary, Addr, Api, Attribute, BankMsg, Binary, BlockInfo, Checksum, Coin, ContractInfo,
-    ContractInfoResponse, CustomMsg, CustomQuery, Deps, DepsMut, Env, Event, MessageInfo, Order,
-    Querier, QuerierWrapper, Record, Reply, ReplyOn, Response, StdResult, Storage, SubMsg,
-    SubMsgResponse, SubMsgResult, TransactionInfo, WasmMsg, WasmQuery,
+    to_json_binary, to_json_vec, Addr, AnyMsg, Api, Attribute, BankMsg, Binary, BlockInfo,
+    Checksum, Coin, ContractInfo, ContractInfoResponse, CosmosMsg, CustomMsg, CustomQuery, Deps,
+    DepsMut, DistributionMsg, Empty, Env, Event, GovMsg, GrpcQuery, IbcMsg, IbcPacketReceiveMsg,
+    MessageInfo, MsgResponse, Order, Querier, QuerierWrapper, Record, Reply, ReplyOn, Response,
+    StakingMsg, StdResult, Storage, SubMsg, SubMsgResponse, SubMsgResult, TransactionInfo, WasmMsg,
+    WasmQuery,
 };
 use cw_storage_plus::Map;
 use prost::Message;
@@ -19,15 +26,84 @@ use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
+use std::cell::RefCell;
 use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::time::Instant;
 
 /// Contract state kept in storage, separate from the contracts themselves (contract code).
 const CONTRACTS: Map<&Addr, ContractData> = Map::new("contracts");
 
+/// Identifiers of pinned codes, see [Wasm::pin_code].
+const PINNED_CODES: Map<u64, Empty> = Map::new("pinned_codes");
+
+/// Histories of code changes of contracts, see [Wasm::contract_history].
+const CONTRACT_HISTORY: Map<&Addr, Vec<ContractCodeHistoryEntry>> = Map::new("contract_history");
+
 /// Wasm module namespace.
 const NAMESPACE_WASM: &[u8] = b"wasm";
 
+/// Prefix of the namespace of the contract storage, followed by the contract address.
+const CONTRACT_STORAGE_PREFIX: &[u8] = b"contract_data/";
+
+/// Prefix of the contract storage in `wasmd`, followed by the canonical contract address.
+const WASMD_CONTRACT_STORE_PREFIX: &[u8] = &[0x03];
+
+/// Splits the key from the application storage into the contract address and the key
+/// in contract's storage, when the key belongs to the storage of a contract
+/// in the default namespace (see [Wasm::contract_namespace]).
+pub(crate) fn split_contract_key(key: &[u8]) -> Option<(Addr, &[u8])> {
+    fn split_namespace(key: &[u8]) -> Option<(&[u8], &[u8])> {
+        let len = u16::from_be_bytes(key.get(..2)?.try_into().ok()?) as usize;
+        let rest = &key[2..];
+        (rest.len() >= len).then(|| rest.split_at(len))
+    }
+    let (namespace, rest) = split_namespace(key)?;
+    if namespace != NAMESPACE_WASM {
+        return None;
+    }
+    let (namespace, rest) = split_namespace(rest)?;
+    let address = namespace.strip_prefix(CONTRACT_STORAGE_PREFIX)?;
+    let address = String::from_utf8(address.to_vec()).ok()?;
+    Some((Addr::unchecked(address), rest))
+}
+
+/// Returns addresses of contracts using the code with specified identifier, ordered like
+/// the `ContractsByCode` query of `wasmd` does: by the position (block height and transaction index)
+/// of the instantiation or migration that set the code, then by address.
+/// Every address is returned together with its sorting key.
+pub(crate) fn contracts_by_code<ExecC, QueryC, W>(
+    wasm: &W,
+    storage: &dyn Storage,
+    code_id: u64,
+) -> AnyResult<Vec<(Vec<u8>, Addr)>>
+where
+    W: Wasm<ExecC, QueryC> + ?Sized,
+{
+    let mut contracts = vec![];
+    for (addr, data) in wasm.contracts(storage)? {
+        if data.code_id != code_id {
+            continue;
+        }
+        let (height, tx_index) = wasm
+            .contract_history(storage, &addr)?
+            .last()
+            .map_or((data.created, 0), |entry| {
+                (entry.block_height, entry.tx_index)
+            });
+        let key = [
+            &height.to_be_bytes()[..],
+            &tx_index.to_be_bytes(),
+            addr.as_bytes(),
+        ]
+        .concat();
+        contracts.push((key, addr));
+    }
+    contracts.sort();
+    Ok(contracts)
+}
+
 /// Contract [address namespace].
 ///
 /// [address namespace]: https://github.com/CosmWasm/wasmd/blob/96e2b91144c9a371683555f3c696f882583cc6a2/x/wasm/types/events.go#L59
@@ -52,6 +128,28 @@ impl WasmSudo {
     }
 }
 
+/// Privileged contract administration actions, executed regardless of the contract admin,
+/// like the governance proposals with `MsgMigrateContract` and `MsgUpdateAdmin` do in `wasmd`.
+#[derive(Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub enum WasmAdminSudo {
+    /// Migrates the contract to the code with specified identifier.
+    Migrate {
+        /// Address of the migrated contract.
+        contract_addr: Addr,
+        /// Identifier of the new code of the contract.
+        new_code_id: u64,
+        /// Message passed to the `migrate` entry-point of the contract.
+        msg: Binary,
+    },
+    /// Sets the admin of the contract, `None` clears the admin.
+    UpdateAdmin {
+        /// Address of the contract.
+        contract_addr: Addr,
+        /// New admin of the contract.
+        admin: Option<Addr>,
+    },
+}
+
 /// Contract data includes information about contract,
 /// equivalent of `ContractInfo` in `wasmd` interface.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
@@ -68,6 +166,32 @@ pub struct ContractData {
     pub created: u64,
 }
 
+/// Operation changing the code of a contract, recorded in [ContractCodeHistoryEntry].
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractCodeHistoryOperation {
+    /// Contract was instantiated.
+    Init,
+    /// Contract was migrated to another code.
+    Migrate,
+}
+
+/// Entry of the history of code changes of a contract,
+/// equivalent of `ContractCodeHistoryEntry` in `wasmd` interface.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct ContractCodeHistoryEntry {
+    /// Operation that changed the code of the contract.
+    pub operation: ContractCodeHistoryOperation,
+    /// Identifier of the code the contract uses since this operation.
+    pub code_id: u64,
+    /// Height of the block in which the operation was executed.
+    pub block_height: u64,
+    /// Index of the transaction in the block in which the operation was executed.
+    pub tx_index: u32,
+    /// Message passed to the `instantiate` or `migrate` entry-point.
+    pub msg: Binary,
+}
+
 /// Contract code base data.
 struct CodeData {
     /// Address of an account that initially stored the contract code.
@@ -113,6 +237,16 @@ pub trait Wasm<ExecC, QueryC> {
         msg: WasmSudo,
     ) -> AnyResult<AppResponse>;
 
+    /// Handles gRPC queries of the `cosmwasm.wasm.v1.Query` service, like `ContractsByCode`
+    /// or `Codes`. Returns `None` for queries not handled by this module,
+    /// these are passed to the stargate module.
+    ///
+    /// The default implementation handles no gRPC queries.
+    fn query_grpc(&self, storage: &dyn Storage, request: &GrpcQuery) -> AnyResult<Option<Binary>> {
+        let _ = (storage, request);
+        Ok(None)
+    }
+
     /// Stores the contract's code and returns an identifier of the stored contract's code.
     fn store_code(&mut self, creator: Addr, code: Box<dyn Contract<ExecC, QueryC>>) -> u64;
 
@@ -135,9 +269,245 @@ pub trait Wasm<ExecC, QueryC> {
     /// Returns a raw state dump of all key-values held by a contract with specified address.
     fn dump_wasm_raw(&self, storage: &dyn Storage, address: &Addr) -> Vec<Record>;
 
+    /// Returns addresses and `ContractData` of all instantiated contracts, sorted by address.
+    ///
+    /// The default implementation returns no contracts.
+    fn contracts(&self, storage: &dyn Storage) -> AnyResult<Vec<(Addr, ContractData)>> {
+        let _ = storage;
+        Ok(vec![])
+    }
+
+    /// Returns the history of code changes of the contract with specified address,
+    /// oldest first: its instantiation followed by all migrations.
+    ///
+    /// The default implementation does not record the history and returns no entries.
+    fn contract_history(
+        &self,
+        storage: &dyn Storage,
+        address: &Addr,
+    ) -> AnyResult<Vec<ContractCodeHistoryEntry>> {
+        let _ = (storage, address);
+        Ok(vec![])
+    }
+
+    /// Returns identifiers of all stored codes, in ascending order.
+    ///
+    /// The default implementation returns no codes.
+    fn code_ids(&self) -> Vec<u64> {
+        vec![]
+    }
+
+    /// Returns the total gas consumed by contracts since this module was created.
+    ///
+    /// The default implementation does not meter gas and always returns zero.
+    fn gas_used(&self) -> u64 {
+        0
+    }
+
+    /// Limits the amount of gas that contracts can consume from now on,
+    /// calling contracts fails with an out of gas error when the limit is exceeded.
+    /// `None` removes the limit.
+    ///
+    /// The default implementation does not meter gas and ignores the limit.
+    fn set_gas_limit(&self, limit: Option<u64>) {
+        let _ = limit;
+    }
+
+    /// Sets the index of the currently executed transaction in the block,
+    /// passed to contracts in `env.transaction.index`.
+    ///
+    /// The default implementation ignores the index.
+    fn set_transaction_index(&self, index: u32) {
+        let _ = index;
+    }
+
+    /// Removes all calls traced so far, see [trace](Wasm::trace).
+    ///
+    /// The default implementation does nothing.
+    fn reset_trace(&self) {}
+
+    /// Runs the simulation of the transaction with specified index in the block,
+    /// returns its result and the gas consumed by contracts.
+    ///
+    /// Contract calls made by the simulation are neither traced nor profiled,
+    /// the consumed gas is not added to [gas_used](Wasm::gas_used),
+    /// and the previous transaction index is restored afterwards.
+    ///
+    /// The default implementation runs the simulation and reports no consumed gas.
+    fn run_simulation<T, F>(&self, tx_index: u32, simulation: F) -> (T, u64)
+    where
+        Self: Sized,
+        F: FnOnce() -> T,
+    {
+        let _ = tx_index;
+        (simulation(), 0)
+    }
+
+    /// Returns the trace of contract calls made since the trace was reset,
+    /// `None` when tracing is not enabled.
+    ///
+    /// The default implementation does not trace contract calls and always returns `None`.
+    fn trace(&self) -> Option<Trace> {
+        None
+    }
+
+    /// Returns statistics of contract calls accumulated since profiling was enabled,
+    /// `None` when profiling is not enabled.
+    ///
+    /// The default implementation does not profile contract calls and always returns `None`.
+    fn profiling_report(&self) -> Option<ProfilingReport> {
+        None
+    }
+
+    /// Removes all statistics accumulated by the profiler.
+    ///
+    /// The default implementation does nothing.
+    fn reset_profiling(&self) {}
+
+    /// Returns the address of the contract that would be instantiated now
+    /// from the code with specified identifier, by the specified creator with specified salt,
+    /// like it is done when processing `WasmMsg::Instantiate2` message.
+    ///
+    /// The default implementation does not support predictable addresses and returns an error.
+    fn predictable_contract_address(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        code_id: u64,
+        creator: &Addr,
+        salt: &[u8],
+    ) -> AnyResult<Addr> {
+        let _ = (api, storage, creator, salt);
+        bail!(
+            "predictable address for code id {} is not supported",
+            code_id
+        )
+    }
+
+    /// Makes the contract with specified address use the code with specified identifier,
+    /// without migrating it, so the state of the contract is kept untouched.
+    ///
+    /// The default implementation does not support overriding contract code and returns an error.
+    fn override_contract_code(
+        &self,
+        storage: &mut dyn Storage,
+        address: &Addr,
+        code_id: u64,
+    ) -> AnyResult<()> {
+        let _ = storage;
+        bail!(
+            "overriding code of contract {} with code id {} is not supported",
+            address,
+            code_id
+        )
+    }
+
+    /// Pins the code with specified identifier, like `MsgPinCodes` does in `wasmd`.
+    /// Pinned codes are reported as such in the contract info.
+    ///
+    /// The default implementation does not support pinning codes and returns an error.
+    fn pin_code(&self, storage: &mut dyn Storage, code_id: u64) -> AnyResult<()> {
+        let _ = storage;
+        bail!("pinning code id {} is not supported", code_id)
+    }
+
+    /// Unpins the code with specified identifier, like `MsgUnpinCodes` does in `wasmd`.
+    ///
+    /// The default implementation does not support pinning codes and returns an error.
+    fn unpin_code(&self, storage: &mut dyn Storage, code_id: u64) -> AnyResult<()> {
+        let _ = storage;
+        bail!("unpinning code id {} is not supported", code_id)
+    }
+
+    /// Executes the wasm module message encoded as `Any`, sent in `CosmosMsg::Any`
+    /// or `CosmosMsg::Stargate`, like [MsgPinCodes] and [MsgUnpinCodes] sent by the governance.
+    /// Returns `None` when the message is not handled by this module,
+    /// then it is passed to the [Stargate](crate::Stargate) handler.
+    ///
+    /// The default implementation does not handle any messages.
+    fn execute_any(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        msg: &AnyMsg,
+    ) -> AnyResult<Option<AppResponse>> {
+        let _ = (api, storage, sender, msg);
+        Ok(None)
+    }
+
+    /// Returns `true` when the code with specified identifier is pinned.
+    ///
+    /// The default implementation does not support pinning codes and always returns `false`.
+    fn is_code_pinned(&self, storage: &dyn Storage, code_id: u64) -> AnyResult<bool> {
+        let _ = (storage, code_id);
+        Ok(false)
+    }
+
+    /// Delivers the packet to the `ibc_packet_receive` entry-point of the contract,
+    /// as if it was relayed from the counterparty chain, and processes the returned messages.
+    /// The acknowledgement written by the contract is returned as response data.
+    ///
+    /// The default implementation does not support IBC packets and returns an error.
+    fn ibc_packet_receive(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        contract_addr: Addr,
+        msg: IbcPacketReceiveMsg,
+    ) -> AnyResult<AppResponse> {
+        let _ = (api, storage, router, block, msg);
+        bail!(
+            "delivering IBC packets to contract {} is not supported",
+            contract_addr
+        )
+    }
+
+    /// Migrates the contract to the code with specified identifier regardless of its admin,
+    /// like `MsgMigrateContract` executed by the governance authority does in `wasmd`.
+    /// Contracts without admin can be migrated this way too.
+    ///
+    /// The default implementation does not support privileged migrations and returns an error.
+    fn sudo_migrate(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        contract_addr: Addr,
+        new_code_id: u64,
+        msg: Binary,
+    ) -> AnyResult<AppResponse> {
+        let _ = (api, storage, router, block, new_code_id, msg);
+        bail!(
+            "privileged migration of contract {} is not supported",
+            contract_addr
+        )
+    }
+
+    /// Sets the admin of the contract regardless of its current admin,
+    /// like `MsgUpdateAdmin` executed by the governance authority does in `wasmd`.
+    /// `None` clears the admin.
+    ///
+    /// The default implementation does not support privileged admin updates and returns an error.
+    fn sudo_update_admin(
+        &self,
+        storage: &mut dyn Storage,
+        contract_addr: &Addr,
+        admin: Option<Addr>,
+    ) -> AnyResult<AppResponse> {
+        let _ = (storage, admin);
+        bail!(
+            "privileged admin update of contract {} is not supported",
+            contract_addr
+        )
+    }
+
     /// Returns the namespace of the contract storage.
     fn contract_namespace(&self, contract: &Addr) -> Vec<u8> {
-        let mut name = b"contract_data/".to_vec();
+        let mut name = CONTRACT_STORAGE_PREFIX.to_vec();
         name.extend_from_slice(contract.as_bytes());
         name
     }
@@ -179,6 +549,20 @@ pub struct WasmKeeper<ExecC, QueryC> {
     address_generator: Box<dyn AddressGenerator>,
     /// Contract's code checksum generator.
     checksum_generator: Box<dyn ChecksumGenerator>,
+    /// Optional gas meter charging contract calls and storage accesses.
+    gas_meter: Option<GasMeter>,
+    /// Optional tracer recording the tree of contract calls.
+    tracer: Option<Tracer>,
+    /// Optional profiler accumulating statistics of contract calls.
+    profiler: Option<Profiler>,
+    /// Optional limits of contract storage accesses.
+    storage_limits: Option<StorageLimits>,
+    /// Flag indicating if the contract storage is laid out like in `wasmd`.
+    wasmd_layout: bool,
+    /// Index of the currently executed transaction in the block.
+    transaction_index: AtomicU32,
+    /// Flag indicating if a simulation is running, simulated calls are neither traced nor profiled.
+    simulating: AtomicBool,
     /// Just markers to make type elision fork when using it as `Wasm` trait
     _p: std::marker::PhantomData<QueryC>,
 }
@@ -191,6 +575,13 @@ impl<ExecC, QueryC> Default for WasmKeeper<ExecC, QueryC> {
             code_data: BTreeMap::default(),
             address_generator: Box::new(SimpleAddressGenerator),
             checksum_generator: Box::new(SimpleChecksumGenerator),
+            gas_meter: None,
+            tracer: None,
+            profiler: None,
+            storage_limits: None,
+            wasmd_layout: false,
+            transaction_index: AtomicU32::new(0),
+            simulating: AtomicBool::new(false),
             _p: std::marker::PhantomData,
         }
     }
@@ -210,11 +601,7 @@ where
         sender: Addr,
         msg: WasmMsg,
     ) -> AnyResult<AppResponse> {
-        self.execute_wasm(api, storage, router, block, sender.clone(), msg.clone())
-            .context(format!(
-                "Error executing WasmMsg:\n  sender: {}\n  {:?}",
-                sender, msg
-            ))
+        self.execute_wasm(api, storage, router, block, sender, msg)
     }
 
     fn query(
@@ -237,12 +624,17 @@ where
             WasmQuery::ContractInfo { contract_addr } => {
                 let addr = api.addr_validate(&contract_addr)?;
                 let contract = self.contract_data(storage, &addr)?;
+                let ibc_port = self
+                    .contract_code(contract.code_id)?
+                    .has_ibc_entry_points()
+                    .then(|| format!("wasm.{addr}"));
+                let pinned = self.is_code_pinned(storage, contract.code_id)?;
                 let res = ContractInfoResponse::new(
                     contract.code_id,
                     contract.creator,
                     contract.admin,
-                    false,
-                    None,
+                    pinned,
+                    ibc_port,
                 );
                 to_json_binary(&res).map_err(Into::into)
             }
@@ -259,6 +651,79 @@ where
         }
     }
 
+    fn query_grpc(&self, storage: &dyn Storage, request: &GrpcQuery) -> AnyResult<Option<Binary>> {
+        let response = match request.path.as_str() {
+            CONTRACTS_BY_CODE_QUERY_PATH => {
+                let request = QueryContractsByCodeRequest::decode(request.data.as_slice())?;
+                let contracts = contracts_by_code(self, storage, request.code_id)?
+                    .into_iter()
+                    .map(|(key, addr)| (key, addr.to_string()))
+                    .collect();
+                let (contracts, pagination) = paginate(contracts, request.pagination);
+                QueryContractsByCodeResponse {
+                    contracts,
+                    pagination: Some(pagination),
+                }
+                .encode_to_vec()
+            }
+            CODES_QUERY_PATH => {
+                let request = QueryCodesRequest::decode(request.data.as_slice())?;
+                let code_infos = self
+                    .code_data
+                    .iter()
+                    .map(|(code_id, code_data)| {
+                        let code_info = ProtoCodeInfoResponse {
+                            code_id: *code_id,
+                            creator: code_data.creator.to_string(),
+                            data_hash: code_data.checksum.as_slice().to_vec(),
+                        };
+                        (code_id.to_be_bytes().to_vec(), code_info)
+                    })
+                    .collect();
+                let (code_infos, pagination) = paginate(code_infos, request.pagination);
+                QueryCodesResponse {
+                    code_infos,
+                    pagination: Some(pagination),
+                }
+                .encode_to_vec()
+            }
+            CONTRACT_HISTORY_QUERY_PATH => {
+                let request = QueryContractHistoryRequest::decode(request.data.as_slice())?;
+                let address = Addr::unchecked(request.address);
+                let entries = self
+                    .contract_history(storage, &address)?
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, entry)| {
+                        let entry = ProtoContractCodeHistoryEntry {
+                            // values of `ContractCodeHistoryOperationType` in wasmd,
+                            // 3 is used for contracts imported in genesis
+                            operation: match entry.operation {
+                                ContractCodeHistoryOperation::Init => 1,
+                                ContractCodeHistoryOperation::Migrate => 2,
+                            },
+                            code_id: entry.code_id,
+                            updated: Some(AbsoluteTxPosition {
+                                block_height: entry.block_height,
+                                tx_index: entry.tx_index as u64,
+                            }),
+                            msg: entry.msg.to_vec(),
+                        };
+                        ((index as u64).to_be_bytes().to_vec(), entry)
+                    })
+                    .collect();
+                let (entries, pagination) = paginate(entries, request.pagination);
+                QueryContractHistoryResponse {
+                    entries,
+                    pagination: Some(pagination),
+                }
+                .encode_to_vec()
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(response.into()))
+    }
+
     fn sudo(
         &self,
         api: &dyn Api,
@@ -267,17 +732,28 @@ where
         block: &BlockInfo,
         msg: WasmSudo,
     ) -> AnyResult<AppResponse> {
-        let custom_event = Event::new("sudo").add_attribute(CONTRACT_ATTR, &msg.contract_addr);
-        let res = self.call_sudo(
-            msg.contract_addr.clone(),
-            api,
-            storage,
-            router,
-            block,
-            msg.message.to_vec(),
-        )?;
-        let (res, msgs) = self.build_app_response(&msg.contract_addr, custom_event, res);
-        self.process_response(api, router, storage, block, msg.contract_addr, res, msgs)
+        let contract_addr = msg.contract_addr;
+        self.traced(&contract_addr, "sudo", msg.message.as_slice(), || {
+            let custom_event = Event::new("sudo").add_attribute(CONTRACT_ATTR, &contract_addr);
+            let res = self.call_sudo(
+                contract_addr.clone(),
+                api,
+                storage,
+                router,
+                block,
+                msg.message.to_vec(),
+            )?;
+            let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
+            self.process_response(
+                api,
+                router,
+                storage,
+                block,
+                contract_addr.clone(),
+                res,
+                msgs,
+            )
+        })
     }
 
     /// Stores the contract's code in the in-memory lookup table.
@@ -332,10 +808,287 @@ where
     }
 
     /// Returns a raw state dump of all key-values held by a contract with specified address.
+    fn contract_storage<'a>(
+        &self,
+        storage: &'a dyn Storage,
+        address: &Addr,
+    ) -> Box<dyn Storage + 'a> {
+        if self.wasmd_layout {
+            let prefix = Self::wasmd_contract_prefix(address);
+            return Box::new(ReadonlyPrefixedStorage::raw(storage, &prefix));
+        }
+        let namespace = self.contract_namespace(address);
+        let storage = ReadonlyPrefixedStorage::multilevel(storage, &[NAMESPACE_WASM, &namespace]);
+        Box::new(storage)
+    }
+
+    fn contract_storage_mut<'a>(
+        &self,
+        storage: &'a mut dyn Storage,
+        address: &Addr,
+    ) -> Box<dyn Storage + 'a> {
+        if self.wasmd_layout {
+            let prefix = Self::wasmd_contract_prefix(address);
+            return Box::new(PrefixedStorage::raw(storage, &prefix));
+        }
+        let namespace = self.contract_namespace(address);
+        let storage = PrefixedStorage::multilevel(storage, &[NAMESPACE_WASM, &namespace]);
+        Box::new(storage)
+    }
+
     fn dump_wasm_raw(&self, storage: &dyn Storage, address: &Addr) -> Vec<Record> {
         let storage = self.contract_storage(storage, address);
         storage.range(None, None, Order::Ascending).collect()
     }
+
+    /// Returns addresses and `ContractData` of all instantiated contracts, sorted by address.
+    fn contracts(&self, storage: &dyn Storage) -> AnyResult<Vec<(Addr, ContractData)>> {
+        CONTRACTS
+            .range(
+                &prefixed_read(storage, NAMESPACE_WASM),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .collect::<StdResult<_>>()
+            .map_err(Into::into)
+    }
+
+    fn contract_history(
+        &self,
+        storage: &dyn Storage,
+        address: &Addr,
+    ) -> AnyResult<Vec<ContractCodeHistoryEntry>> {
+        Ok(CONTRACT_HISTORY
+            .may_load(&prefixed_read(storage, NAMESPACE_WASM), address)?
+            .unwrap_or_default())
+    }
+
+    fn code_ids(&self) -> Vec<u64> {
+        self.code_data.keys().copied().collect()
+    }
+
+    /// Returns the total gas consumed by contracts since this keeper was created.
+    fn gas_used(&self) -> u64 {
+        self.consumed_gas()
+    }
+
+    /// Limits the amount of gas consumed by contracts from now on, when gas is metered.
+    fn set_gas_limit(&self, limit: Option<u64>) {
+        if let Some(meter) = &self.gas_meter {
+            meter.set_limit(limit);
+        }
+    }
+
+    /// Sets the index of the currently executed transaction, passed to contracts in [Env].
+    fn set_transaction_index(&self, index: u32) {
+        self.transaction_index.store(index, Ordering::Relaxed);
+    }
+
+    fn reset_trace(&self) {
+        if let Some(tracer) = &self.tracer {
+            tracer.reset();
+        }
+    }
+
+    fn run_simulation<T, F>(&self, tx_index: u32, simulation: F) -> (T, u64)
+    where
+        F: FnOnce() -> T,
+    {
+        let saved_index = self.transaction_index.swap(tx_index, Ordering::Relaxed);
+        let was_simulating = self.simulating.swap(true, Ordering::Relaxed);
+        let gas_before = self.consumed_gas();
+        let res = simulation();
+        let gas_used = self.consumed_gas() - gas_before;
+        if let Some(meter) = &self.gas_meter {
+            meter.restore_consumed(gas_before);
+        }
+        self.simulating.store(was_simulating, Ordering::Relaxed);
+        self.transaction_index.store(saved_index, Ordering::Relaxed);
+        (res, gas_used)
+    }
+
+    fn trace(&self) -> Option<Trace> {
+        self.tracer.as_ref().map(Tracer::trace)
+    }
+
+    fn profiling_report(&self) -> Option<ProfilingReport> {
+        self.profiler.as_ref().map(Profiler::report)
+    }
+
+    fn reset_profiling(&self) {
+        if let Some(profiler) = &self.profiler {
+            profiler.reset();
+        }
+    }
+
+    /// Returns the address generated by the address generator for `WasmMsg::Instantiate2`,
+    /// the default generator gives the same address as `wasmd` does.
+    fn predictable_contract_address(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        code_id: u64,
+        creator: &Addr,
+        salt: &[u8],
+    ) -> AnyResult<Addr> {
+        let code_data = self.code_data(code_id)?;
+        let instance_id = self.instance_count(storage) as u64;
+        let canonical_addr = &api.addr_canonicalize(creator.as_ref())?;
+        self.address_generator.predictable_contract_address(
+            api,
+            storage,
+            code_id,
+            instance_id,
+            code_data.checksum.as_slice(),
+            canonical_addr,
+            salt,
+        )
+    }
+
+    fn override_contract_code(
+        &self,
+        storage: &mut dyn Storage,
+        address: &Addr,
+        code_id: u64,
+    ) -> AnyResult<()> {
+        self.code_data(code_id)?;
+        let mut contract = self.contract_data(storage, address)?;
+        contract.code_id = code_id;
+        self.save_contract(storage, address, &contract)
+    }
+
+    fn pin_code(&self, storage: &mut dyn Storage, code_id: u64) -> AnyResult<()> {
+        self.code_data(code_id)?;
+        PINNED_CODES.save(&mut prefixed(storage, NAMESPACE_WASM), code_id, &Empty {})?;
+        Ok(())
+    }
+
+    fn unpin_code(&self, storage: &mut dyn Storage, code_id: u64) -> AnyResult<()> {
+        self.code_data(code_id)?;
+        PINNED_CODES.remove(&mut prefixed(storage, NAMESPACE_WASM), code_id);
+        Ok(())
+    }
+
+    fn is_code_pinned(&self, storage: &dyn Storage, code_id: u64) -> AnyResult<bool> {
+        Ok(PINNED_CODES.has(&prefixed_read(storage, NAMESPACE_WASM), code_id))
+    }
+
+    /// Handles [MsgPinCodes] and [MsgUnpinCodes], allowed only for the governance module account.
+    fn execute_any(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        sender: &Addr,
+        msg: &AnyMsg,
+    ) -> AnyResult<Option<AppResponse>> {
+        let (authority, code_ids, event_type) = match msg.type_url.as_str() {
+            MSG_PIN_CODES_TYPE_URL => {
+                let msg = MsgPinCodes::decode(msg.value.as_slice())?;
+                (msg.authority, msg.code_ids, "pin_code")
+            }
+            MSG_UNPIN_CODES_TYPE_URL => {
+                let msg = MsgUnpinCodes::decode(msg.value.as_slice())?;
+                (msg.authority, msg.code_ids, "unpin_code")
+            }
+            _ => return Ok(None),
+        };
+        if authority != sender.as_str() || *sender != module_address(api, "gov")? {
+            bail!(
+                "unauthorized: {} is not the governance module account",
+                sender
+            );
+        }
+        let mut events = vec![];
+        for code_id in code_ids {
+            if event_type == "pin_code" {
+                self.pin_code(storage, code_id)?;
+            } else {
+                self.unpin_code(storage, code_id)?;
+            }
+            events.push(Event::new(event_type).add_attribute("code_id", code_id.to_string()));
+        }
+        Ok(Some(AppResponse {
+            events,
+            ..Default::default()
+        }))
+    }
+
+    fn ibc_packet_receive(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        contract_addr: Addr,
+        msg: IbcPacketReceiveMsg,
+    ) -> AnyResult<AppResponse> {
+        let packet_data = msg.packet.data.clone();
+        self.traced(
+            &contract_addr,
+            "ibc_packet_receive",
+            packet_data.as_slice(),
+            || {
+                let custom_event =
+                    Event::new("ibc_packet_receive").add_attribute(CONTRACT_ATTR, &contract_addr);
+                let res = self.call_ibc_packet_receive(
+                    contract_addr.clone(),
+                    api,
+                    storage,
+                    router,
+                    block,
+                    msg,
+                )?;
+                let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
+                self.process_response(
+                    api,
+                    router,
+                    storage,
+                    block,
+                    contract_addr.clone(),
+                    res,
+                    msgs,
+                )
+            },
+        )
+    }
+
+    fn sudo_migrate(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        contract_addr: Addr,
+        new_code_id: u64,
+        msg: Binary,
+    ) -> AnyResult<AppResponse> {
+        let mut res =
+            self.migrate_contract(api, storage, router, block, contract_addr, new_code_id, msg)?;
+        res.data = execute_response(res.data);
+        Ok(res)
+    }
+
+    fn sudo_update_admin(
+        &self,
+        storage: &mut dyn Storage,
+        contract_addr: &Addr,
+        admin: Option<Addr>,
+    ) -> AnyResult<AppResponse> {
+        let mut data = self.contract_data(storage, contract_addr)?;
+        let event = Event::new("update_contract_admin")
+            .add_attribute(CONTRACT_ATTR, contract_addr)
+            .add_attribute(
+                "new_admin_address",
+                admin.as_ref().map(Addr::as_str).unwrap_or_default(),
+            );
+        data.admin = admin;
+        self.save_contract(storage, contract_addr, &data)?;
+        Ok(AppResponse {
+            events: vec![event],
+            ..Default::default()
+        })
+    }
 }
 
 impl<ExecC, QueryC> WasmKeeper<ExecC, QueryC> {
@@ -399,11 +1152,18 @@ impl<ExecC, QueryC> WasmKeeper<ExecC, QueryC> {
         code_id: u64,
         creator: Addr,
         code: Box<dyn Contract<ExecC, QueryC>>,
-    ) -> u64 {
+    ) -> u64
+    where
+        ExecC: CustomMsg,
+        QueryC: CustomQuery,
+    {
         // prepare the next identifier for the contract 'source' code
         let source_id = self.code_base.len();
-        // calculate the checksum of the contract 'source' code based on code_id
-        let checksum = self.checksum_generator.checksum(&creator, code_id);
+        // take the checksum of the contract's wasm code when known,
+        // otherwise calculate the checksum of the contract 'source' code based on code_id
+        let checksum = code
+            .checksum()
+            .unwrap_or_else(|| self.checksum_generator.checksum(&creator, code_id));
         // store the 'source' code of the contract
         self.code_base.push(code);
         // store the additional code attributes like creator address and checksum
@@ -516,6 +1276,208 @@ where
         self
     }
 
+    /// Populates an existing [WasmKeeper] with a gas meter charging specified costs
+    /// for calling contracts and accessing contract storage.
+    ///
+    /// Gas consumed by a transaction is reported in the [TxMetadata](crate::TxMetadata)
+    /// of the returned [AppResponse], and gas consumed by a submessage
+    /// is passed to the contract in the `gas_used` field of the [Reply].
+    /// Submessages with `gas_limit` set fail with an out of gas error when they exceed the limit,
+    /// and this error can be handled in the `reply` entry-point.
+    /// By default, gas is not metered, so the `gas_limit` of submessages is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{AppBuilder, GasCosts, no_init, WasmKeeper};
+    ///
+    /// let wasm_keeper = WasmKeeper::new().with_gas_costs(GasCosts::default());
+    ///
+    /// let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    /// ```
+    pub fn with_gas_costs(mut self, gas_costs: GasCosts) -> Self {
+        self.gas_meter = Some(GasMeter::new(gas_costs));
+        self
+    }
+
+    /// Populates an existing [WasmKeeper] with a tracer recording the tree of all contract calls,
+    /// available after every transaction with [App::last_trace](crate::App::last_trace).
+    /// By default, contract calls are not traced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{AppBuilder, no_init, WasmKeeper};
+    ///
+    /// let wasm_keeper = WasmKeeper::new().with_tracing();
+    ///
+    /// let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    ///
+    /// assert!(app.last_trace().unwrap().calls.is_empty());
+    /// ```
+    pub fn with_tracing(mut self) -> Self {
+        self.tracer = Some(Tracer::default());
+        self
+    }
+
+    /// Populates an existing [WasmKeeper] with a profiler accumulating per-contract
+    /// and per-entry-point statistics of all contract calls (call counts, gas and wall time),
+    /// available with [App::profiling_report](crate::App::profiling_report).
+    /// By default, contract calls are not profiled.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{AppBuilder, no_init, WasmKeeper};
+    ///
+    /// let wasm_keeper = WasmKeeper::new().with_profiling();
+    ///
+    /// let app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    ///
+    /// assert!(app.profiling_report().unwrap().entries.is_empty());
+    /// ```
+    pub fn with_profiling(mut self) -> Self {
+        self.profiler = Some(Profiler::default());
+        self
+    }
+
+    /// Populates an existing [WasmKeeper] with limits of contract storage accesses,
+    /// like the maximum length of keys and values, so contracts abusing the storage
+    /// fail in tests the same way they would fail on a real chain.
+    /// By default, contract storage accesses are not limited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{AppBuilder, no_init, StorageLimits, WasmKeeper};
+    ///
+    /// let wasm_keeper = WasmKeeper::new().with_storage_limits(StorageLimits {
+    ///     max_iterated_items: Some(100),
+    ///     ..Default::default()
+    /// });
+    ///
+    /// let app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    /// ```
+    pub fn with_storage_limits(mut self, limits: StorageLimits) -> Self {
+        self.storage_limits = Some(limits);
+        self
+    }
+
+    /// Lays out the contract storage like `wasmd` does, so the raw keys of contracts' state
+    /// in the application storage match the keys on a real chain byte-for-byte:
+    /// `0x03 | canonical contract address | key`.
+    ///
+    /// Only the contract storage is affected, other data of this keeper
+    /// (like [ContractData]) is kept in the default layout. Contracts' state written
+    /// in this layout is not attributed to contracts in [StorageWrite](crate::StorageWrite)s.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cw_multi_test::{AppBuilder, no_init, WasmKeeper};
+    ///
+    /// let wasm_keeper = WasmKeeper::new().with_wasmd_storage_layout();
+    ///
+    /// let app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    /// ```
+    pub fn with_wasmd_storage_layout(mut self) -> Self {
+        self.wasmd_layout = true;
+        self
+    }
+
+    /// Returns the prefix of the contract storage in `wasmd` layout.
+    fn wasmd_contract_prefix(address: &Addr) -> Vec<u8> {
+        let canonical_addr = match bech32::decode(address.as_str()) {
+            Ok((_, data)) => data,
+            Err(_) => address.as_bytes().to_vec(),
+        };
+        let mut prefix = WASMD_CONTRACT_STORE_PREFIX.to_vec();
+        prefix.extend(canonical_addr);
+        prefix
+    }
+
+    /// Traces and profiles the call of the contract's entry-point, when enabled.
+    /// The action calls the entry-point and processes returned messages.
+    fn traced<F>(
+        &self,
+        contract: &Addr,
+        entry_point: &str,
+        msg: &[u8],
+        action: F,
+    ) -> AnyResult<AppResponse>
+    where
+        F: FnOnce() -> AnyResult<AppResponse>,
+    {
+        if self.tracer.is_none() && self.profiler.is_none() || self.is_simulating() {
+            return action();
+        }
+        let gas_before = self.consumed_gas();
+        let started = Instant::now();
+        if let Some(tracer) = &self.tracer {
+            tracer.enter(contract, entry_point, msg);
+        }
+        let res = action();
+        let gas_used = self.consumed_gas() - gas_before;
+        if let Some(tracer) = &self.tracer {
+            tracer.exit(&res, gas_used);
+        }
+        if let Some(profiler) = &self.profiler {
+            profiler.record(contract, entry_point, gas_used, started.elapsed());
+        }
+        res
+    }
+
+    /// Wraps the contract storage with storage recording writes, when tracing is enabled.
+    fn traced_storage<'a>(&'a self, storage: Box<dyn Storage + 'a>) -> Box<dyn Storage + 'a> {
+        match &self.tracer {
+            Some(tracer) if !self.is_simulating() => Box::new(TracedStorage::new(storage, tracer)),
+            _ => storage,
+        }
+    }
+
+    /// Returns `true` when a simulation is running, see [Wasm::run_simulation].
+    fn is_simulating(&self) -> bool {
+        self.simulating.load(Ordering::Relaxed)
+    }
+
+    /// Wraps the contract storage with storage enforcing the limits, when limits are set.
+    fn limited_storage<'a>(
+        &'a self,
+        storage: Box<dyn Storage + 'a>,
+        violation: &'a RefCell<Option<Error>>,
+    ) -> Box<dyn Storage + 'a> {
+        match &self.storage_limits {
+            Some(limits) => Box::new(LimitedStorage::new(storage, limits, violation)),
+            None => storage,
+        }
+    }
+
+    /// Returns the total gas consumed by contracts, zero when gas is not metered.
+    fn consumed_gas(&self) -> u64 {
+        self.gas_meter.as_ref().map_or(0, GasMeter::consumed)
+    }
+
+    /// Charges the gas cost selected from configured costs, when gas is metered.
+    /// Fails when the consumed gas exceeds the limit, including gas consumed
+    /// by storage accesses since the previous charge.
+    fn charge_gas(&self, cost: fn(&GasCosts) -> u64) -> AnyResult<()> {
+        if let Some(meter) = &self.gas_meter {
+            meter.consume(cost(meter.costs()));
+            if let Some((gas_used, gas_limit)) = meter.exceeded() {
+                bail!(Error::out_of_gas(gas_used, gas_limit));
+            }
+        }
+        Ok(())
+    }
+
+    /// Wraps the contract storage with storage charging gas, when gas is metered.
+    fn metered_storage<'a>(&'a self, storage: Box<dyn Storage + 'a>) -> Box<dyn Storage + 'a> {
+        match &self.gas_meter {
+            Some(meter) => Box::new(MeteredStorage::new(storage, meter)),
+            None => storage,
+        }
+    }
+
     /// Executes contract's `query` entry-point.
     pub fn query_smart(
         &self,
@@ -526,6 +1488,7 @@ where
         block: &BlockInfo,
         msg: Vec<u8>,
     ) -> AnyResult<Binary> {
+        self.charge_gas(|costs| costs.query)?;
         self.with_storage_readonly(
             api,
             storage,
@@ -591,13 +1554,64 @@ where
         self.save_contract(storage, &contract_addr, &data)?;
 
         // no custom event here
-        Ok(AppResponse {
-            data: None,
-            events: vec![],
+        Ok(AppResponse::default())
+    }
+
+    /// Updates the stored code identifier of the contract and calls its `migrate` entry-point,
+    /// without checking the admin.
+    fn migrate_contract(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        contract_addr: Addr,
+        new_code_id: u64,
+        msg: Binary,
+    ) -> AnyResult<AppResponse> {
+        if new_code_id as usize > self.code_data.len() {
+            bail!("Cannot migrate contract to unregistered code id");
+        }
+        let mut data = self.contract_data(storage, &contract_addr)?;
+        data.code_id = new_code_id;
+        self.save_contract(storage, &contract_addr, &data)?;
+        self.append_contract_history(
+            storage,
+            &contract_addr,
+            ContractCodeHistoryOperation::Migrate,
+            new_code_id,
+            block,
+            msg.clone(),
+        )?;
+
+        self.traced(&contract_addr, "migrate", msg.as_slice(), || {
+            let res = self.call_migrate(
+                contract_addr.clone(),
+                api,
+                storage,
+                router,
+                block,
+                msg.to_vec(),
+            )?;
+
+            let custom_event = Event::new("migrate")
+                .add_attribute(CONTRACT_ATTR, &contract_addr)
+                .add_attribute("code_id", new_code_id.to_string());
+            let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
+            self.process_response(
+                api,
+                router,
+                storage,
+                block,
+                contract_addr.clone(),
+                res,
+                msgs,
+            )
         })
     }
 
     // this returns the contract address as well, so we can properly resend the data
+    /// Executes specified wasm message, errors are returned with [AppError] context attached.
     fn execute_wasm(
         &self,
         api: &dyn Api,
@@ -606,6 +1620,34 @@ where
         block: &BlockInfo,
         sender: Addr,
         msg: WasmMsg,
+    ) -> AnyResult<AppResponse> {
+        let mut instantiated = None;
+        self.process_wasm_msg(
+            api,
+            storage,
+            router,
+            block,
+            sender.clone(),
+            msg.clone(),
+            &mut instantiated,
+        )
+        .map_err(|err| {
+            let context = AppError::wasm_msg(sender, msg, instantiated, &err);
+            err.context(context)
+        })
+    }
+
+    /// Processes specified wasm message, the address of the instantiated contract
+    /// is stored in `instantiated` as soon as it is generated.
+    fn process_wasm_msg(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: WasmMsg,
+        instantiated: &mut Option<Addr>,
     ) -> AnyResult<AppResponse> {
         match msg {
             WasmMsg::Execute {
@@ -626,23 +1668,32 @@ where
                 )?;
 
                 // then call the contract
-                let info = MessageInfo { sender, funds };
-                let res = self.call_execute(
-                    api,
-                    storage,
-                    contract_addr.clone(),
-                    router,
-                    block,
-                    info,
-                    msg.to_vec(),
-                )?;
-
-                let custom_event =
-                    Event::new("execute").add_attribute(CONTRACT_ATTR, &contract_addr);
-
-                let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
-                let mut res =
-                    self.process_response(api, router, storage, block, contract_addr, res, msgs)?;
+                let mut res = self.traced(&contract_addr, "execute", msg.as_slice(), || {
+                    let info = MessageInfo { sender, funds };
+                    let res = self.call_execute(
+                        api,
+                        storage,
+                        contract_addr.clone(),
+                        router,
+                        block,
+                        info,
+                        msg.to_vec(),
+                    )?;
+
+                    let custom_event =
+                        Event::new("execute").add_attribute(CONTRACT_ATTR, &contract_addr);
+
+                    let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
+                    self.process_response(
+                        api,
+                        router,
+                        storage,
+                        block,
+                        contract_addr.clone(),
+                        res,
+                        msgs,
+                    )
+                })?;
                 res.data = execute_response(res.data);
                 Ok(res)
             }
@@ -653,7 +1704,17 @@ where
                 funds,
                 label,
             } => self.process_wasm_msg_instantiate(
-                api, storage, router, block, sender, admin, code_id, msg, funds, label, None,
+                api,
+                storage,
+                router,
+                block,
+                MessageInfo { sender, funds },
+                admin,
+                code_id,
+                msg,
+                label,
+                None,
+                instantiated,
             ),
             WasmMsg::Instantiate2 {
                 admin,
@@ -667,13 +1728,13 @@ where
                 storage,
                 router,
                 block,
-                sender,
+                MessageInfo { sender, funds },
                 admin,
                 code_id,
                 msg,
-                funds,
                 label,
                 Some(salt),
+                instantiated,
             ),
             WasmMsg::Migrate {
                 contract_addr,
@@ -682,33 +1743,20 @@ where
             } => {
                 let contract_addr = api.addr_validate(&contract_addr)?;
 
-                // check admin status and update the stored code_id
-                if new_code_id as usize > self.code_data.len() {
-                    bail!("Cannot migrate contract to unregistered code id");
-                }
-                let mut data = self.contract_data(storage, &contract_addr)?;
+                // check admin status
+                let data = self.contract_data(storage, &contract_addr)?;
                 if data.admin != Some(sender) {
                     bail!("Only admin can migrate contract: {:?}", data.admin);
                 }
-                data.code_id = new_code_id;
-                self.save_contract(storage, &contract_addr, &data)?;
-
-                // then call migrate
-                let res = self.call_migrate(
-                    contract_addr.clone(),
+                let mut res = self.migrate_contract(
                     api,
                     storage,
                     router,
                     block,
-                    msg.to_vec(),
+                    contract_addr,
+                    new_code_id,
+                    msg,
                 )?;
-
-                let custom_event = Event::new("migrate")
-                    .add_attribute(CONTRACT_ATTR, &contract_addr)
-                    .add_attribute("code_id", new_code_id.to_string());
-                let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
-                let mut res =
-                    self.process_response(api, router, storage, block, contract_addr, res, msgs)?;
                 res.data = execute_response(res.data);
                 Ok(res)
             }
@@ -730,13 +1778,13 @@ where
         storage: &mut dyn Storage,
         router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
         block: &BlockInfo,
-        sender: Addr,
+        info: MessageInfo,
         admin: Option<String>,
         code_id: u64,
         msg: Binary,
-        funds: Vec<Coin>,
         label: String,
         salt: Option<Binary>,
+        instantiated: &mut Option<Addr>,
     ) -> AnyResult<AppResponse> {
         if label.is_empty() {
             bail!("Label is required on all contracts");
@@ -746,12 +1794,21 @@ where
             api,
             storage,
             code_id,
-            sender.clone(),
+            info.sender.clone(),
             admin.map(Addr::unchecked),
             label,
             block.height,
             salt,
         )?;
+        *instantiated = Some(contract_addr.clone());
+        self.append_contract_history(
+            storage,
+            &contract_addr,
+            ContractCodeHistoryOperation::Init,
+            code_id,
+            block,
+            msg.clone(),
+        )?;
 
         // move the cash
         self.send(
@@ -759,37 +1816,38 @@ where
             storage,
             router,
             block,
-            sender.clone(),
+            info.sender.clone(),
             contract_addr.clone().into(),
-            &funds,
+            &info.funds,
         )?;
 
         // then call the contract
-        let info = MessageInfo { sender, funds };
-        let res = self.call_instantiate(
-            contract_addr.clone(),
-            api,
-            storage,
-            router,
-            block,
-            info,
-            msg.to_vec(),
-        )?;
+        let mut res = self.traced(&contract_addr, "instantiate", msg.as_slice(), || {
+            let res = self.call_instantiate(
+                contract_addr.clone(),
+                api,
+                storage,
+                router,
+                block,
+                info,
+                msg.to_vec(),
+            )?;
 
-        let custom_event = Event::new("instantiate")
-            .add_attribute(CONTRACT_ATTR, &contract_addr)
-            .add_attribute("code_id", code_id.to_string());
+            let custom_event = Event::new("instantiate")
+                .add_attribute(CONTRACT_ATTR, &contract_addr)
+                .add_attribute("code_id", code_id.to_string());
 
-        let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
-        let mut res = self.process_response(
-            api,
-            router,
-            storage,
-            block,
-            contract_addr.clone(),
-            res,
-            msgs,
-        )?;
+            let (res, msgs) = self.build_app_response(&contract_addr, custom_event, res);
+            self.process_response(
+                api,
+                router,
+                storage,
+                block,
+                contract_addr.clone(),
+                res,
+                msgs,
+            )
+        })?;
         res.data = Some(instantiate_response(res.data, &contract_addr));
         Ok(res)
     }
@@ -813,27 +1871,56 @@ where
         msg: SubMsg<ExecC>,
     ) -> AnyResult<AppResponse> {
         let SubMsg {
-            msg, id, reply_on, ..
+            msg,
+            id,
+            reply_on,
+            payload,
+            gas_limit,
         } = msg;
-
-        // execute in cache
+        let response_type_url = msg_response_type_url(&msg);
+
+        // execute in cache, within the gas limit of the submessage when gas is metered
+        let gas_before = self.consumed_gas();
+        let saved_limit = self
+            .gas_meter
+            .as_ref()
+            .zip(gas_limit)
+            .map(|(meter, limit)| meter.push_limit(limit));
         let res = transactional(storage, |write_cache, _| {
-            router.execute(api, write_cache, block, contract.clone(), msg)
+            let res = router.execute(api, write_cache, block, contract.clone(), msg)?;
+            // storage accesses after the last contract call are checked here
+            if let Some(meter) = self.gas_meter.as_ref().filter(|_| saved_limit.is_some()) {
+                if let Some((gas_used, gas_limit)) = meter.exceeded() {
+                    bail!(Error::out_of_gas(gas_used, gas_limit));
+                }
+            }
+            Ok(res)
         });
+        if let Some((meter, saved)) = self.gas_meter.as_ref().zip(saved_limit) {
+            meter.restore_limit(saved);
+        }
+        let gas_used = self.consumed_gas() - gas_before;
 
         // call reply if meaningful
         if let Ok(mut r) = res {
             if matches!(reply_on, ReplyOn::Always | ReplyOn::Success) {
+                let msg_responses = response_type_url
+                    .map(|type_url| MsgResponse {
+                        type_url,
+                        value: r.data.clone().unwrap_or_default(),
+                    })
+                    .into_iter()
+                    .collect();
                 let reply = Reply {
                     id,
-                    payload: Default::default(),
-                    gas_used: 0,
+                    payload,
+                    gas_used,
                     result: SubMsgResult::Ok(
                         #[allow(deprecated)]
                         SubMsgResponse {
                             events: r.events.clone(),
                             data: r.data,
-                            msg_responses: vec![],
+                            msg_responses,
                         },
                     ),
                 };
@@ -852,8 +1939,8 @@ where
             if matches!(reply_on, ReplyOn::Always | ReplyOn::Error) {
                 let reply = Reply {
                     id,
-                    payload: Default::default(),
-                    gas_used: 0,
+                    payload,
+                    gas_used,
                     result: SubMsgResult::Err(format!("{:?}", e)),
                 };
                 self.reply(api, router, storage, block, contract, reply)
@@ -883,9 +1970,12 @@ where
             .add_attribute(CONTRACT_ATTR, &contract)
             .add_attribute("mode", ok_attr);
 
-        let res = self.call_reply(contract.clone(), api, storage, router, block, reply)?;
-        let (res, msgs) = self.build_app_response(&contract, custom_event, res);
-        self.process_response(api, router, storage, block, contract, res, msgs)
+        let msg = to_json_vec(&reply)?;
+        self.traced(&contract, "reply", &msg, || {
+            let res = self.call_reply(contract.clone(), api, storage, router, block, reply)?;
+            let (res, msgs) = self.build_app_response(&contract, custom_event, res);
+            self.process_response(api, router, storage, block, contract.clone(), res, msgs)
+        })
     }
 
     // this captures all the events and data from the contract call.
@@ -930,6 +2020,7 @@ where
         let app = AppResponse {
             events: app_events,
             data,
+            ..Default::default()
         };
         (app, messages)
     }
@@ -944,17 +2035,30 @@ where
         response: AppResponse,
         messages: Vec<SubMsg<ExecC>>,
     ) -> AnyResult<AppResponse> {
-        let AppResponse { mut events, data } = response;
+        let AppResponse {
+            mut events, data, ..
+        } = response;
 
         // recurse in all messages
-        let data = messages.into_iter().try_fold(data, |data, resend| {
-            let sub_res =
-                self.execute_submsg(api, router, storage, block, contract.clone(), resend)?;
-            events.extend_from_slice(&sub_res.events);
-            Ok::<_, AnyError>(sub_res.data.or(data))
-        })?;
+        let data = messages
+            .into_iter()
+            .enumerate()
+            .try_fold(data, |data, (index, resend)| {
+                let sub_res = self
+                    .execute_submsg(api, router, storage, block, contract.clone(), resend)
+                    .map_err(|mut err| {
+                        AppError::set_msg_index(&mut err, index);
+                        err
+                    })?;
+                events.extend_from_slice(&sub_res.events);
+                Ok::<_, AnyError>(sub_res.data.or(data))
+            })?;
 
-        Ok(AppResponse { events, data })
+        Ok(AppResponse {
+            events,
+            data,
+            ..Default::default()
+        })
     }
 
     /// Creates a contract address and empty storage instance.
@@ -982,15 +2086,11 @@ where
         let instance_id = self.instance_count(storage) as u64;
         let addr = if let Some(salt_binary) = salt.into() {
             // generate predictable contract address when salt is provided
-            let code_data = self.code_data(code_id)?;
-            let canonical_addr = &api.addr_canonicalize(creator.as_ref())?;
-            self.address_generator.predictable_contract_address(
+            self.predictable_contract_address(
                 api,
                 storage,
                 code_id,
-                instance_id,
-                code_data.checksum.as_slice(),
-                canonical_addr,
+                &creator,
                 salt_binary.as_slice(),
             )?
         } else {
@@ -1027,6 +2127,7 @@ where
         info: MessageInfo,
         msg: Vec<u8>,
     ) -> AnyResult<Response<ExecC>> {
+        self.charge_gas(|costs| costs.execute)?;
         Self::verify_response(self.with_storage(
             api,
             storage,
@@ -1048,6 +2149,7 @@ where
         info: MessageInfo,
         msg: Vec<u8>,
     ) -> AnyResult<Response<ExecC>> {
+        self.charge_gas(|costs| costs.instantiate)?;
         Self::verify_response(self.with_storage(
             api,
             storage,
@@ -1068,6 +2170,7 @@ where
         block: &BlockInfo,
         reply: Reply,
     ) -> AnyResult<Response<ExecC>> {
+        self.charge_gas(|costs| costs.reply)?;
         Self::verify_response(self.with_storage(
             api,
             storage,
@@ -1088,6 +2191,7 @@ where
         block: &BlockInfo,
         msg: Vec<u8>,
     ) -> AnyResult<Response<ExecC>> {
+        self.charge_gas(|costs| costs.sudo)?;
         Self::verify_response(self.with_storage(
             api,
             storage,
@@ -1098,6 +2202,36 @@ where
         )?)
     }
 
+    /// Executes contract's `ibc_packet_receive` entry-point.
+    ///
+    /// The returned acknowledgement is passed as the response data.
+    /// Gas is charged like for the `execute` entry-point.
+    pub fn call_ibc_packet_receive(
+        &self,
+        address: Addr,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        msg: IbcPacketReceiveMsg,
+    ) -> AnyResult<Response<ExecC>> {
+        self.charge_gas(|costs| costs.execute)?;
+        let res = self.with_storage(
+            api,
+            storage,
+            router,
+            block,
+            address,
+            |contract, deps, env| contract.ibc_packet_receive(deps, env, msg),
+        )?;
+        let mut response = Response::new()
+            .add_submessages(res.messages)
+            .add_attributes(res.attributes)
+            .add_events(res.events);
+        response.data = res.acknowledgement;
+        Self::verify_response(response)
+    }
+
     /// Executes contract's `migrate` entry-point.
     pub fn call_migrate(
         &self,
@@ -1108,6 +2242,7 @@ where
         block: &BlockInfo,
         msg: Vec<u8>,
     ) -> AnyResult<Response<ExecC>> {
+        self.charge_gas(|costs| costs.migrate)?;
         Self::verify_response(self.with_storage(
             api,
             storage,
@@ -1124,7 +2259,9 @@ where
             contract: ContractInfo {
                 address: address.into(),
             },
-            transaction: Some(TransactionInfo { index: 0 }),
+            transaction: Some(TransactionInfo {
+                index: self.transaction_index.load(Ordering::Relaxed),
+            }),
         }
     }
 
@@ -1142,7 +2279,11 @@ where
     {
         let contract = self.contract_data(storage, &address)?;
         let handler = self.contract_code(contract.code_id)?;
-        let storage = self.contract_storage(storage, &address);
+        let violation = RefCell::new(None);
+        let storage = self.limited_storage(
+            self.metered_storage(self.contract_storage(storage, &address)),
+            &violation,
+        );
         let env = self.get_env(address, block);
 
         let deps = Deps {
@@ -1150,7 +2291,11 @@ where
             api,
             querier: QuerierWrapper::new(querier),
         };
-        action(handler, deps, env)
+        let res = action(handler, deps, env);
+        if let Some(err) = violation.take() {
+            bail!(err);
+        }
+        res
     }
 
     fn with_storage<F, T>(
@@ -1174,7 +2319,13 @@ where
         // However, we need to get write and read access to the same storage in two different objects,
         // and this is the only way I know how to do so.
         transactional(storage, |write_cache, read_store| {
-            let mut contract_storage = self.contract_storage_mut(write_cache, &address);
+            let violation = RefCell::new(None);
+            let mut contract_storage = self.limited_storage(
+                self.metered_storage(
+                    self.traced_storage(self.contract_storage_mut(write_cache, &address)),
+                ),
+                &violation,
+            );
             let querier = RouterQuerier::new(router, api, read_store, block);
             let env = self.get_env(address, block);
 
@@ -1183,7 +2334,11 @@ where
                 api,
                 querier: QuerierWrapper::new(&querier),
             };
-            action(handler, deps, env)
+            let res = action(handler, deps, env);
+            if let Some(err) = violation.take() {
+                bail!(err);
+            }
+            res
         })
     }
 
@@ -1199,6 +2354,31 @@ where
             .map_err(Into::into)
     }
 
+    /// Appends the entry to the history of code changes of the contract.
+    fn append_contract_history(
+        &self,
+        storage: &mut dyn Storage,
+        address: &Addr,
+        operation: ContractCodeHistoryOperation,
+        code_id: u64,
+        block: &BlockInfo,
+        msg: Binary,
+    ) -> AnyResult<()> {
+        let mut storage = prefixed(storage, NAMESPACE_WASM);
+        let mut history = CONTRACT_HISTORY
+            .may_load(&storage, address)?
+            .unwrap_or_default();
+        history.push(ContractCodeHistoryEntry {
+            operation,
+            code_id,
+            block_height: block.height,
+            tx_index: self.transaction_index.load(Ordering::Relaxed),
+            msg,
+        });
+        CONTRACT_HISTORY.save(&mut storage, address, &history)?;
+        Ok(())
+    }
+
     /// Returns the number of all contract instances.
     fn instance_count(&self, storage: &dyn Storage) -> usize {
         CONTRACTS
@@ -1212,6 +2392,131 @@ where
     }
 }
 
+/// Path of the `ContractsByCode` gRPC query of `wasmd`.
+const CONTRACTS_BY_CODE_QUERY_PATH: &str = "/cosmwasm.wasm.v1.Query/ContractsByCode";
+
+/// Path of the `Codes` gRPC query of `wasmd`.
+const CODES_QUERY_PATH: &str = "/cosmwasm.wasm.v1.Query/Codes";
+
+/// Protobuf encoded `cosmwasm.wasm.v1.QueryContractsByCodeRequest`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractsByCodeRequest {
+    #[prost(uint64, tag = "1")]
+    code_id: u64,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<ProtoPageRequest>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.QueryContractsByCodeResponse`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractsByCodeResponse {
+    #[prost(string, repeated, tag = "1")]
+    contracts: Vec<String>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.QueryCodesRequest`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryCodesRequest {
+    #[prost(message, optional, tag = "1")]
+    pagination: Option<ProtoPageRequest>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.QueryCodesResponse`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryCodesResponse {
+    #[prost(message, repeated, tag = "1")]
+    code_infos: Vec<ProtoCodeInfoResponse>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.CodeInfoResponse`, without the instantiate permission.
+#[derive(Clone, PartialEq, Message)]
+struct ProtoCodeInfoResponse {
+    #[prost(uint64, tag = "1")]
+    code_id: u64,
+    #[prost(string, tag = "2")]
+    creator: String,
+    #[prost(bytes = "vec", tag = "3")]
+    data_hash: Vec<u8>,
+}
+
+/// Type URL of the `MsgPinCodes` message of `wasmd`.
+pub const MSG_PIN_CODES_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgPinCodes";
+
+/// Type URL of the `MsgUnpinCodes` message of `wasmd`.
+pub const MSG_UNPIN_CODES_TYPE_URL: &str = "/cosmwasm.wasm.v1.MsgUnpinCodes";
+
+/// Protobuf encoded `cosmwasm.wasm.v1.MsgPinCodes`, pinning codes when sent by the governance
+/// module account, usually in a governance proposal. Sent as `CosmosMsg::Any`
+/// with [MSG_PIN_CODES_TYPE_URL] type URL.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgPinCodes {
+    /// Address of the governance module account.
+    #[prost(string, tag = "1")]
+    pub authority: String,
+    /// Identifiers of codes to pin.
+    #[prost(uint64, repeated, tag = "2")]
+    pub code_ids: Vec<u64>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.MsgUnpinCodes`, unpinning codes when sent by the governance
+/// module account. Sent as `CosmosMsg::Any` with [MSG_UNPIN_CODES_TYPE_URL] type URL.
+#[derive(Clone, PartialEq, Message)]
+pub struct MsgUnpinCodes {
+    /// Address of the governance module account.
+    #[prost(string, tag = "1")]
+    pub authority: String,
+    /// Identifiers of codes to unpin.
+    #[prost(uint64, repeated, tag = "2")]
+    pub code_ids: Vec<u64>,
+}
+
+/// Path of the `ContractHistory` gRPC query of `wasmd`.
+const CONTRACT_HISTORY_QUERY_PATH: &str = "/cosmwasm.wasm.v1.Query/ContractHistory";
+
+/// Protobuf encoded `cosmwasm.wasm.v1.QueryContractHistoryRequest`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractHistoryRequest {
+    #[prost(string, tag = "1")]
+    address: String,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<ProtoPageRequest>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.QueryContractHistoryResponse`.
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractHistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    entries: Vec<ProtoContractCodeHistoryEntry>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.ContractCodeHistoryEntry`.
+#[derive(Clone, PartialEq, Message)]
+struct ProtoContractCodeHistoryEntry {
+    #[prost(int32, tag = "1")]
+    operation: i32,
+    #[prost(uint64, tag = "2")]
+    code_id: u64,
+    #[prost(message, optional, tag = "3")]
+    updated: Option<AbsoluteTxPosition>,
+    #[prost(bytes = "vec", tag = "4")]
+    msg: Vec<u8>,
+}
+
+/// Protobuf encoded `cosmwasm.wasm.v1.AbsoluteTxPosition`.
+#[derive(Clone, PartialEq, Message)]
+struct AbsoluteTxPosition {
+    #[prost(uint64, tag = "1")]
+    block_height: u64,
+    #[prost(uint64, tag = "2")]
+    tx_index: u64,
+}
+
 #[derive(Clone, PartialEq, Message)]
 struct InstantiateResponse {
     #[prost(string, tag = "1")]
@@ -1249,6 +2554,51 @@ fn execute_response(data: Option<Binary>) -> Option<Binary> {
     })
 }
 
+/// Returns the type URL of the response to specified message, like the type URLs
+/// of `msg_responses` returned by Cosmos SDK. The value of the response is the data
+/// returned from processing the message, which for wasm messages is already Protobuf encoded.
+/// Returns `None` for messages without a known response type, like custom messages.
+fn msg_response_type_url<ExecC>(msg: &CosmosMsg<ExecC>) -> Option<String> {
+    let type_url = match msg {
+        CosmosMsg::Bank(BankMsg::Send { .. }) => "/cosmos.bank.v1beta1.MsgSendResponse",
+        CosmosMsg::Bank(BankMsg::Burn { .. }) => "/cosmos.bank.v1beta1.MsgBurnResponse",
+        CosmosMsg::Staking(StakingMsg::Delegate { .. }) => {
+            "/cosmos.staking.v1beta1.MsgDelegateResponse"
+        }
+        CosmosMsg::Staking(StakingMsg::Undelegate { .. }) => {
+            "/cosmos.staking.v1beta1.MsgUndelegateResponse"
+        }
+        CosmosMsg::Staking(StakingMsg::Redelegate { .. }) => {
+            "/cosmos.staking.v1beta1.MsgBeginRedelegateResponse"
+        }
+        CosmosMsg::Distribution(DistributionMsg::SetWithdrawAddress { .. }) => {
+            "/cosmos.distribution.v1beta1.MsgSetWithdrawAddressResponse"
+        }
+        CosmosMsg::Distribution(DistributionMsg::WithdrawDelegatorReward { .. }) => {
+            "/cosmos.distribution.v1beta1.MsgWithdrawDelegatorRewardResponse"
+        }
+        CosmosMsg::Wasm(WasmMsg::Execute { .. }) => "/cosmwasm.wasm.v1.MsgExecuteContractResponse",
+        CosmosMsg::Wasm(WasmMsg::Instantiate { .. }) => {
+            "/cosmwasm.wasm.v1.MsgInstantiateContractResponse"
+        }
+        CosmosMsg::Wasm(WasmMsg::Instantiate2 { .. }) => {
+            "/cosmwasm.wasm.v1.MsgInstantiateContract2Response"
+        }
+        CosmosMsg::Wasm(WasmMsg::Migrate { .. }) => "/cosmwasm.wasm.v1.MsgMigrateContractResponse",
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin { .. }) => "/cosmwasm.wasm.v1.MsgUpdateAdminResponse",
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin { .. }) => "/cosmwasm.wasm.v1.MsgClearAdminResponse",
+        CosmosMsg::Gov(GovMsg::Vote { .. }) => "/cosmos.gov.v1beta1.MsgVoteResponse",
+        CosmosMsg::Ibc(IbcMsg::Transfer { .. }) => {
+            "/ibc.applications.transfer.v1.MsgTransferResponse"
+        }
+        CosmosMsg::Any(msg) => return Some(format!("{}Response", msg.type_url)),
+        #[allow(deprecated)]
+        CosmosMsg::Stargate { type_url, .. } => return Some(format!("{}Response", type_url)),
+        _ => return None,
+    };
+    Some(type_url.to_string())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -1437,6 +2787,65 @@ mod test {
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn query_contract_info_with_ibc_port() {
+        struct IbcContract(Box<dyn Contract<Empty>>);
+
+        #[rustfmt::skip]
+        impl Contract<Empty> for IbcContract {
+            fn execute(&self, deps: DepsMut, env: Env, info: MessageInfo, msg: Vec<u8>) -> AnyResult<Response> { self.0.execute(deps, env, info, msg) }
+            fn instantiate(&self, deps: DepsMut, env: Env, info: MessageInfo, msg: Vec<u8>) -> AnyResult<Response> { self.0.instantiate(deps, env, info, msg) }
+            fn query(&self, deps: Deps, env: Env, msg: Vec<u8>) -> AnyResult<Binary> { self.0.query(deps, env, msg) }
+            fn sudo(&self, deps: DepsMut, env: Env, msg: Vec<u8>) -> AnyResult<Response> { self.0.sudo(deps, env, msg) }
+            fn reply(&self, deps: DepsMut, env: Env, msg: Reply) -> AnyResult<Response> { self.0.reply(deps, env, msg) }
+            fn migrate(&self, deps: DepsMut, env: Env, msg: Vec<u8>) -> AnyResult<Response> { self.0.migrate(deps, env, msg) }
+            fn has_ibc_entry_points(&self) -> bool { true }
+        }
+
+        let api = MockApi::default();
+        let creator_addr = api.addr_make("creator");
+
+        let mut wasm_storage = MockStorage::new();
+        let mut wasm_keeper = wasm_keeper();
+        let block = mock_env().block;
+        let code_id = wasm_keeper.store_code(
+            creator_addr.clone(),
+            Box::new(IbcContract(payout::contract())),
+        );
+
+        let contract_addr = wasm_keeper
+            .register_contract(
+                &api,
+                &mut wasm_storage,
+                code_id,
+                creator_addr.clone(),
+                None,
+                "label".to_owned(),
+                1000,
+                None,
+            )
+            .unwrap();
+
+        let querier: MockQuerier<Empty> = MockQuerier::new(&[]);
+        let query = WasmQuery::ContractInfo {
+            contract_addr: contract_addr.to_string(),
+        };
+
+        let contract_info = wasm_keeper
+            .query(&api, &wasm_storage, &querier, &block, query)
+            .unwrap();
+
+        let actual: ContractInfoResponse = from_json(contract_info).unwrap();
+        let expected = ContractInfoResponse::new(
+            code_id,
+            creator_addr,
+            None,
+            false,
+            Some(format!("wasm.{contract_addr}")),
+        );
+        assert_eq!(expected, actual);
+    }
+
     #[test]
     fn query_code_info() {
         let api = MockApi::default();
@@ -12,8 +12,8 @@ mod test_contracts {
 
     pub mod counter {
         use cosmwasm_std::{
-            to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
-            WasmMsg,
+            to_json_binary, Binary, Checksum, Deps, DepsMut, Empty, Env, MessageInfo, Response,
+            StdError, WasmMsg,
         };
         use cw_multi_test::{Contract, ContractWrapper};
         use cw_storage_plus::Item;
@@ -63,8 +63,26 @@ mod test_contracts {
             }
         }
 
+        fn migrate(deps: DepsMut, _env: Env, _msg: Empty) -> Result<Response, StdError> {
+            COUNTER.save(deps.storage, &100).unwrap();
+            Ok(Response::default())
+        }
+
         pub fn contract() -> Box<dyn Contract<Empty>> {
             Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
         }
+
+        pub fn contract_with_migrate() -> Box<dyn Contract<Empty>> {
+            Box::new(
+                ContractWrapper::new_with_empty(execute, instantiate, query).with_migrate(migrate),
+            )
+        }
+
+        pub fn contract_with_checksum(checksum: Checksum) -> Box<dyn Contract<Empty>> {
+            Box::new(
+                ContractWrapper::new_with_empty(execute, instantiate, query)
+                    .with_checksum(checksum),
+            )
+        }
     }
 }
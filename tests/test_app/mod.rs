@@ -1,4 +1,28 @@
+mod test_account_generator;
+mod test_address_book;
+mod test_auto_block_advance;
+mod test_capabilities;
+mod test_chain_upgrade;
+mod test_contract_history;
+#[cfg(feature = "cw20")]
+mod test_cw20;
+mod test_end_blockers;
+mod test_events_since;
+mod test_execute_as_module;
+mod test_execute_block;
+mod test_execute_cosmos_msgs;
 mod test_instantiate2;
+mod test_override_contract_code;
+mod test_pin_code;
+mod test_query_handle;
+mod test_randomness;
+mod test_replace_stargate;
+mod test_simulate;
+mod test_snapshot;
+mod test_storage_writes;
 mod test_store_code;
 mod test_store_code_with_creator;
 mod test_store_code_with_id;
+mod test_sudo_migrate;
+mod test_tx_metadata;
+mod test_unbonding;
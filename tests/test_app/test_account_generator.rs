@@ -0,0 +1,44 @@
+use cosmwasm_std::testing::MockApi;
+use cw_multi_test::{
+    no_init, App, AppBuilder, MockApiBech32, ModuleAccountGenerator, SequentialAccountGenerator,
+};
+
+#[test]
+fn default_account_generator_should_work() {
+    let mut app = App::default();
+    let alice = app.account_address("alice").unwrap();
+    assert_eq!(MockApi::default().addr_make("alice"), alice);
+    // the same name always gives the same address
+    assert_eq!(alice, app.account_address("alice").unwrap());
+    assert_ne!(alice, app.account_address("bob").unwrap());
+}
+
+#[test]
+fn sequential_account_generator_should_work() {
+    let mut app = AppBuilder::default()
+        .with_account_generator(SequentialAccountGenerator)
+        .build(no_init);
+    let first = app.account_address("alice").unwrap();
+    let second = app.account_address("alice").unwrap();
+    assert_ne!(first, second);
+
+    // the sequence is the same in every new application
+    let mut other_app = AppBuilder::default()
+        .with_account_generator(SequentialAccountGenerator)
+        .build(no_init);
+    assert_eq!(first, other_app.account_address("bob").unwrap());
+    assert_eq!(second, other_app.account_address("bob").unwrap());
+}
+
+#[test]
+fn module_account_generator_should_work() {
+    let mut app = AppBuilder::default()
+        .with_api(MockApiBech32::new("cosmos"))
+        .with_account_generator(ModuleAccountGenerator)
+        .build(no_init);
+    // well-known address of the governance module account in Cosmos Hub
+    assert_eq!(
+        "cosmos10d07y265gmmuvt4z0w9aw880jnsr700j6zn9kn",
+        app.account_address("gov").unwrap().as_str()
+    );
+}
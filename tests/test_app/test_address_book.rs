@@ -0,0 +1,98 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::Empty;
+use cw_multi_test::{AddressBook, App, Executor};
+
+#[test]
+fn exporting_address_book_should_work() {
+    let mut app = App::default();
+    let owner = app.account_address("owner").unwrap();
+    let code_id = app.store_code(counter::contract());
+
+    // instantiate contracts, two of them with the same label
+    let mut contracts = vec![];
+    for label in ["alpha", "beta", "alpha"] {
+        contracts.push(
+            app.instantiate_contract(code_id, owner.clone(), &Empty {}, &[], label, None)
+                .unwrap(),
+        );
+    }
+
+    let mut address_book = app.address_book().unwrap();
+    assert_eq!(Some(&contracts[1]), address_book.get("beta"));
+    let alphas = [
+        address_book.get("alpha").unwrap().clone(),
+        address_book.get("alpha_2").unwrap().clone(),
+    ];
+    assert!(alphas.contains(&contracts[0]));
+    assert!(alphas.contains(&contracts[2]));
+
+    // user and module accounts are included
+    assert_eq!(Some("owner"), address_book.name_of(&owner));
+    assert_eq!(
+        Some(&app.module_address("gov").unwrap()),
+        address_book.get("gov")
+    );
+    assert_eq!(
+        Some(&app.module_address("fee_collector").unwrap()),
+        address_book.get("fee_collector")
+    );
+
+    // add other accounts
+    let admin = app.api().addr_make("admin");
+    address_book.insert("admin", admin.clone());
+
+    // export and import the address book
+    let json = address_book.to_json().unwrap();
+    assert!(json.starts_with(r#"{"admin":"cosmwasm1"#));
+    let imported = AddressBook::from_json(&json).unwrap();
+    assert_eq!(address_book, imported);
+    assert_eq!(
+        vec![
+            "admin",
+            "alpha",
+            "alpha_2",
+            "beta",
+            "bonded_tokens_pool",
+            "distribution",
+            "fee_collector",
+            "gov",
+            "mint",
+            "not_bonded_tokens_pool",
+            "owner"
+        ],
+        imported.iter().map(|(name, _)| name).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn importing_address_book_should_work() {
+    let mut app = App::default();
+    let owner = app.account_address("owner").unwrap();
+    let code_id = app.store_code(counter::contract());
+    app.instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+    let mut exported = app.address_book().unwrap();
+    exported.insert("admin", app.api().addr_make("admin"));
+
+    // setup repeated in another application gives the same address book
+    let mut other = App::default();
+    other.import_address_book(AddressBook::from_json(&exported.to_json().unwrap()).unwrap());
+    let owner = other.account_address("owner").unwrap();
+    let code_id = other.store_code(counter::contract());
+    other
+        .instantiate_contract(code_id, owner, &Empty {}, &[], "counter", None)
+        .unwrap();
+    assert_eq!(exported, other.address_book().unwrap());
+}
+
+#[test]
+fn default_address_book_should_contain_module_accounts() {
+    let app = App::default();
+    let address_book = app.address_book().unwrap();
+    assert_eq!(6, address_book.len());
+    assert!(address_book
+        .iter()
+        .all(|(name, addr)| app.module_address(name).unwrap() == addr));
+    assert!(AddressBook::new().is_empty());
+    assert_eq!("{}", AddressBook::new().to_json().unwrap());
+}
@@ -0,0 +1,55 @@
+use cosmwasm_std::testing::MockApi;
+use cosmwasm_std::{coins, BankMsg};
+use cw_multi_test::{App, AppBuilder, BlockParams, Executor};
+
+#[test]
+fn block_should_not_advance_by_default() {
+    let mut app = App::default();
+    let sender = app.api().addr_make("sender");
+    let block = app.block_info();
+    let msg = BankMsg::Burn { amount: vec![] };
+    app.execute(sender, msg.into()).unwrap_err();
+    assert_eq!(block, app.block_info());
+}
+
+#[test]
+fn block_should_advance_after_every_transaction() {
+    let sender = MockApi::default().addr_make("sender");
+    let mut app = AppBuilder::default()
+        .with_auto_block_advance(BlockParams {
+            seconds_per_block: 6,
+            blocks_per_tx: 2,
+        })
+        .build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &sender, coins(100, "uatom"))
+                .unwrap();
+        });
+    let block = app.block_info();
+
+    // successful transaction advances the block
+    let msg = BankMsg::Burn {
+        amount: coins(10, "uatom"),
+    };
+    let res = app.execute(sender.clone(), msg.into()).unwrap();
+    assert_eq!(block.height, res.tx.unwrap().height);
+    assert_eq!(block.height + 2, app.block_info().height);
+    assert_eq!(block.time.plus_seconds(12), app.block_info().time);
+
+    // failed transaction advances the block too
+    let msg = BankMsg::Burn {
+        amount: coins(1000, "uatom"),
+    };
+    app.execute(sender.clone(), msg.into()).unwrap_err();
+    assert_eq!(block.height + 4, app.block_info().height);
+    assert_eq!(block.time.plus_seconds(24), app.block_info().time);
+
+    // automatic block advancement can be disabled
+    app.set_auto_block_advance(None);
+    let msg = BankMsg::Burn {
+        amount: coins(10, "uatom"),
+    };
+    app.execute(sender, msg.into()).unwrap();
+    assert_eq!(block.height + 4, app.block_info().height);
+}
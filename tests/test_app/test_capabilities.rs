@@ -0,0 +1,73 @@
+use cosmwasm_std::{to_json_vec, Empty, GrpcQuery, Querier, QueryRequest};
+use cw_multi_test::{
+    no_init, App, AppBuilder, IbcAcceptingModule, MockApiBech32, QueryCapabilitiesRequest,
+    QueryCapabilitiesResponse, CAPABILITIES_QUERY_PATH,
+};
+use prost::Message;
+
+#[test]
+fn default_capabilities_should_work() {
+    let app = App::default();
+    let capabilities = app.capabilities();
+    assert_eq!("cosmwasm", capabilities.bech32_prefix);
+    assert!(!capabilities.ibc_enabled);
+    assert!(capabilities.has_feature("iterator"));
+    assert!(capabilities.has_feature("staking"));
+    assert!(capabilities.has_feature("stargate"));
+    assert_eq!(
+        cfg!(feature = "cosmwasm_2_0"),
+        capabilities.has_feature("cosmwasm_2_0")
+    );
+    assert_eq!(
+        cfg!(feature = "cosmwasm_1_4"),
+        capabilities.has_feature("cosmwasm_1_4")
+    );
+    assert!(!capabilities.has_feature("cosmwasm_9_9"));
+    assert_eq!(
+        vec!["bank", "distribution", "staking", "wasm"],
+        capabilities.modules
+    );
+}
+
+#[test]
+fn custom_capabilities_should_work() {
+    let app = AppBuilder::default()
+        .with_api(MockApiBech32::new("juno"))
+        .with_ibc(IbcAcceptingModule::new())
+        .build(no_init);
+    let capabilities = app.capabilities();
+    assert_eq!("juno", capabilities.bech32_prefix);
+    assert!(capabilities.ibc_enabled);
+    assert!(capabilities.has_module("ibc"));
+}
+
+#[test]
+fn querying_capabilities_should_work() {
+    let app = AppBuilder::default()
+        .with_ibc(IbcAcceptingModule::new())
+        .build(no_init);
+    let expected: QueryCapabilitiesResponse = app.capabilities().into();
+    assert!(expected.ibc_enabled);
+
+    let data = QueryCapabilitiesRequest {}.encode_to_vec();
+
+    #[allow(deprecated)]
+    let stargate = QueryRequest::<Empty>::Stargate {
+        path: CAPABILITIES_QUERY_PATH.to_string(),
+        data: data.clone().into(),
+    };
+    let grpc = QueryRequest::<Empty>::Grpc(GrpcQuery {
+        path: CAPABILITIES_QUERY_PATH.to_string(),
+        data: data.into(),
+    });
+    for query in [stargate, grpc] {
+        let response = app
+            .raw_query(&to_json_vec(&query).unwrap())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            expected,
+            QueryCapabilitiesResponse::decode(response.as_slice()).unwrap()
+        );
+    }
+}
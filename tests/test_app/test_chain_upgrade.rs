@@ -0,0 +1,59 @@
+use cosmwasm_std::testing::mock_env;
+use cw_multi_test::{next_block, App};
+
+#[test]
+fn chain_revision_should_work() {
+    let mut app = App::default();
+    for (chain_id, revision) in [
+        ("osmosis-1", 1),
+        ("evmos_9000-4", 4),
+        ("my-chain-12", 12),
+        ("cosmos-testnet", 0),
+        ("chain-0", 0),
+        ("chain-01", 0),
+        ("-1", 0),
+        ("testing", 0),
+    ] {
+        app.set_chain_id(chain_id);
+        assert_eq!(chain_id, app.block_info().chain_id);
+        assert_eq!(revision, app.chain_revision(), "chain id: {chain_id}");
+    }
+}
+
+#[test]
+fn scheduled_chain_upgrades_should_be_applied() {
+    let mut app = App::default();
+    app.set_chain_id("testing-1");
+    let height = app.block_info().height;
+    app.schedule_chain_upgrade(height + 1, "testing-2");
+    app.schedule_chain_upgrade(height + 3, "testing-3");
+    app.schedule_chain_upgrade(height + 4, "testing-4");
+
+    app.update_block(next_block);
+    assert_eq!("testing-2", app.block_info().chain_id);
+    assert_eq!(2, app.chain_revision());
+
+    app.update_block(next_block);
+    assert_eq!("testing-2", app.block_info().chain_id);
+
+    // skipping over several upgrades applies the latest one
+    app.update_block(|block| block.height += 5);
+    assert_eq!("testing-4", app.block_info().chain_id);
+    assert_eq!(4, app.chain_revision());
+
+    // applied upgrades are not applied again
+    app.set_chain_id("testing-5");
+    app.update_block(next_block);
+    assert_eq!("testing-5", app.block_info().chain_id);
+}
+
+#[test]
+fn chain_upgrade_should_be_applied_when_setting_block() {
+    let mut app = App::default();
+    let mut block = mock_env().block;
+    app.schedule_chain_upgrade(block.height + 10, "upgraded-2");
+
+    block.height += 10;
+    app.set_block(block);
+    assert_eq!("upgraded-2", app.block_info().chain_id);
+}
@@ -0,0 +1,117 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{to_json_binary, to_json_vec, Empty, GrpcQuery, Querier, QueryRequest};
+use cw_multi_test::{next_block, App, ContractCodeHistoryOperation, Executor};
+use prost::Message;
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractHistoryRequest {
+    #[prost(string, tag = "1")]
+    address: String,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct AbsoluteTxPosition {
+    #[prost(uint64, tag = "1")]
+    block_height: u64,
+    #[prost(uint64, tag = "2")]
+    tx_index: u64,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct ContractCodeHistoryEntry {
+    #[prost(int32, tag = "1")]
+    operation: i32,
+    #[prost(uint64, tag = "2")]
+    code_id: u64,
+    #[prost(message, optional, tag = "3")]
+    updated: Option<AbsoluteTxPosition>,
+    #[prost(bytes = "vec", tag = "4")]
+    msg: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractHistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    entries: Vec<ContractCodeHistoryEntry>,
+}
+
+#[test]
+fn contract_history_should_record_instantiation_and_migrations() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract_with_migrate());
+    let new_code_id = app.duplicate_code(code_id).unwrap();
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "counter",
+            Some(owner.to_string()),
+        )
+        .unwrap();
+    let init_height = app.block_info().height;
+
+    // failed migration is not recorded
+    app.migrate_contract(owner.clone(), contract_addr.clone(), &Empty {}, 100)
+        .unwrap_err();
+    assert_eq!(1, app.contract_history(&contract_addr).unwrap().len());
+
+    app.update_block(next_block);
+    app.migrate_contract(owner, contract_addr.clone(), &Empty {}, new_code_id)
+        .unwrap();
+    app.sudo_migrate(&contract_addr, code_id, &Empty {})
+        .unwrap();
+
+    let history = app.contract_history(&contract_addr).unwrap();
+    let operations: Vec<(ContractCodeHistoryOperation, u64)> = history
+        .iter()
+        .map(|entry| (entry.operation, entry.code_id))
+        .collect();
+    assert_eq!(
+        vec![
+            (ContractCodeHistoryOperation::Init, code_id),
+            (ContractCodeHistoryOperation::Migrate, new_code_id),
+            (ContractCodeHistoryOperation::Migrate, code_id),
+        ],
+        operations
+    );
+    assert_eq!(init_height, history[0].block_height);
+    assert_eq!(init_height + 1, history[1].block_height);
+    assert_eq!(to_json_binary(&Empty {}).unwrap(), history[1].msg);
+
+    // history is available through the wasmd gRPC query
+    let request = QueryContractHistoryRequest {
+        address: contract_addr.to_string(),
+    };
+    let query: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+        path: "/cosmwasm.wasm.v1.Query/ContractHistory".to_string(),
+        data: request.encode_to_vec().into(),
+    });
+    let data = app
+        .raw_query(&to_json_vec(&query).unwrap())
+        .unwrap()
+        .unwrap();
+    let response = QueryContractHistoryResponse::decode(data.as_slice()).unwrap();
+    let operations: Vec<(i32, u64)> = response
+        .entries
+        .iter()
+        .map(|entry| (entry.operation, entry.code_id))
+        .collect();
+    assert_eq!(
+        vec![(1, code_id), (2, new_code_id), (2, code_id)],
+        operations
+    );
+    assert_eq!(
+        init_height + 1,
+        response.entries[2].updated.as_ref().unwrap().block_height
+    );
+}
+
+#[test]
+fn unknown_contract_should_have_empty_history() {
+    let app = App::default();
+    let contract_addr = app.api().addr_make("contract");
+    assert!(app.contract_history(&contract_addr).unwrap().is_empty());
+}
@@ -0,0 +1,157 @@
+use cosmwasm_std::{
+    to_json_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw_multi_test::cw20::{
+    Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, MinterResponse, TokenInfoResponse,
+};
+use cw_multi_test::{App, Contract, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+const RECEIVED: Item<Cw20ReceiveMsg> = Item::new("received");
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum ReceiverMsg {
+    Receive(Cw20ReceiveMsg),
+}
+
+fn receiver() -> Box<dyn Contract<Empty>> {
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::default())
+    }
+    fn execute(deps: DepsMut, _: Env, _: MessageInfo, msg: ReceiverMsg) -> StdResult<Response> {
+        let ReceiverMsg::Receive(msg) = msg;
+        RECEIVED.save(deps.storage, &msg)?;
+        Ok(Response::default())
+    }
+    fn query(deps: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+        to_json_binary(&RECEIVED.load(deps.storage)?)
+    }
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
+fn create_token(app: &mut App, owner: &Addr) -> Addr {
+    let initial_balances = vec![Cw20Coin {
+        address: owner.to_string(),
+        amount: Uint128::new(1000),
+    }];
+    app.create_cw20(owner, "TOKEN", initial_balances).unwrap()
+}
+
+#[test]
+fn create_cw20_should_work() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let token = create_token(&mut app, &owner);
+
+    let token_info: TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!("TOKEN", token_info.name);
+    assert_eq!("TOKEN", token_info.symbol);
+    assert_eq!(6, token_info.decimals);
+    assert_eq!(Uint128::new(1000), token_info.total_supply);
+
+    let minter: Option<MinterResponse> = app
+        .wrap()
+        .query_wasm_smart(&token, &Cw20QueryMsg::Minter {})
+        .unwrap();
+    assert_eq!(owner.to_string(), minter.unwrap().minter);
+
+    let contract_data = app.contract_data(&token).unwrap();
+    assert_eq!(Some(owner.clone()), contract_data.admin);
+
+    assert_eq!(
+        Uint128::new(1000),
+        app.cw20_balance(&token, &owner).unwrap()
+    );
+    let alice = app.api().addr_make("alice");
+    assert_eq!(Uint128::zero(), app.cw20_balance(&token, &alice).unwrap());
+}
+
+#[test]
+fn transfer_burn_and_mint_should_work() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let alice = app.api().addr_make("alice");
+    let token = create_token(&mut app, &owner);
+
+    let msg = Cw20ExecuteMsg::Transfer {
+        recipient: alice.to_string(),
+        amount: Uint128::new(300),
+    };
+    app.execute_contract(owner.clone(), token.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = Cw20ExecuteMsg::Burn {
+        amount: Uint128::new(100),
+    };
+    app.execute_contract(alice.clone(), token.clone(), &msg, &[])
+        .unwrap();
+
+    let msg = Cw20ExecuteMsg::Mint {
+        recipient: alice.to_string(),
+        amount: Uint128::new(50),
+    };
+    app.execute_contract(owner.clone(), token.clone(), &msg, &[])
+        .unwrap();
+
+    // only the owner can mint new tokens
+    let err = app
+        .execute_contract(alice.clone(), token.clone(), &msg, &[])
+        .unwrap_err();
+    assert_eq!(
+        StdError::generic_err("Unauthorized"),
+        err.downcast().unwrap()
+    );
+
+    // transferring more than the balance fails
+    let msg = Cw20ExecuteMsg::Transfer {
+        recipient: owner.to_string(),
+        amount: Uint128::new(251),
+    };
+    app.execute_contract(alice.clone(), token.clone(), &msg, &[])
+        .unwrap_err();
+
+    assert_eq!(Uint128::new(700), app.cw20_balance(&token, &owner).unwrap());
+    assert_eq!(Uint128::new(250), app.cw20_balance(&token, &alice).unwrap());
+    let token_info: TokenInfoResponse = app
+        .wrap()
+        .query_wasm_smart(&token, &Cw20QueryMsg::TokenInfo {})
+        .unwrap();
+    assert_eq!(Uint128::new(950), token_info.total_supply);
+}
+
+#[test]
+fn send_should_call_receiving_contract() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let token = create_token(&mut app, &owner);
+    let code_id = app.store_code(receiver());
+    let receiver_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "receiver", None)
+        .unwrap();
+
+    let msg = Cw20ExecuteMsg::Send {
+        contract: receiver_addr.to_string(),
+        amount: Uint128::new(10),
+        msg: Binary::from(b"hello"),
+    };
+    app.execute_contract(owner.clone(), token.clone(), &msg, &[])
+        .unwrap();
+
+    assert_eq!(
+        Uint128::new(10),
+        app.cw20_balance(&token, &receiver_addr).unwrap()
+    );
+    let received: Cw20ReceiveMsg = app
+        .wrap()
+        .query_wasm_smart(&receiver_addr, &Empty {})
+        .unwrap();
+    assert_eq!(owner.to_string(), received.sender);
+    assert_eq!(Uint128::new(10), received.amount);
+    assert_eq!(Binary::from(b"hello"), received.msg);
+}
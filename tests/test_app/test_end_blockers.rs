@@ -0,0 +1,98 @@
+use cosmwasm_std::{
+    coin, coins, Binary, Decimal, Deps, DepsMut, Empty, Env, Event, MessageInfo, Response,
+    StdResult,
+};
+use cw_multi_test::{next_block, App, BankSudo, ContractWrapper, Executor, StakingSudo, WasmSudo};
+
+fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn execute(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn query(_: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    Ok(Binary::default())
+}
+
+fn sudo(_: DepsMut, env: Env, _: Empty) -> StdResult<Response> {
+    Ok(Response::new()
+        .add_event(Event::new("end_block").add_attribute("height", env.block.height.to_string())))
+}
+
+#[test]
+fn end_blockers_should_run_when_block_changes() {
+    let mut app = App::default();
+    let addr = app.api().addr_make("rewards");
+    let validator = app.api().addr_make("unknown");
+
+    app.add_end_blocker(BankSudo::Mint {
+        to_address: addr.to_string(),
+        amount: coins(10, "reward"),
+    });
+    // slashing not existing validator fails
+    app.add_end_blocker(StakingSudo::Slash {
+        validator: validator.to_string(),
+        percentage: Decimal::percent(10),
+        jail: false,
+    });
+    app.add_end_blocker(BankSudo::Mint {
+        to_address: addr.to_string(),
+        amount: coins(1, "reward"),
+    });
+
+    // end blockers do not run when the height does not change
+    app.update_block(|block| block.time = block.time.plus_seconds(1));
+    assert!(app.end_block_results().is_empty());
+
+    // failing end blocker does not stop remaining ones
+    app.update_block(next_block);
+    let results = app.end_block_results();
+    assert_eq!(3, results.len());
+    assert!(results[0].is_ok());
+    assert!(results[1].is_err());
+    assert!(results[2].is_ok());
+    assert_eq!(
+        coin(11, "reward"),
+        app.wrap().query_balance(&addr, "reward").unwrap()
+    );
+
+    // end blockers run also when the block is set
+    let mut block = app.block_info();
+    block.height += 10;
+    app.set_block(block);
+    assert_eq!(
+        coin(22, "reward"),
+        app.wrap().query_balance(&addr, "reward").unwrap()
+    );
+
+    // removed end blockers do not run anymore
+    app.clear_end_blockers();
+    app.update_block(next_block);
+    assert!(app.end_block_results().is_empty());
+    assert_eq!(
+        coin(22, "reward"),
+        app.wrap().query_balance(&addr, "reward").unwrap()
+    );
+}
+
+#[test]
+fn end_blockers_should_run_with_ending_block_info() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(Box::new(
+        ContractWrapper::new(execute, instantiate, query).with_sudo(sudo),
+    ));
+    let contract_addr = app
+        .instantiate_contract(code_id, owner, &Empty {}, &[], "end-blocker", None)
+        .unwrap();
+    app.add_end_blocker(WasmSudo::new(&contract_addr, &Empty {}).unwrap());
+
+    let height = app.block_info().height;
+    app.update_block(next_block);
+    assert_eq!(height + 1, app.block_info().height);
+    // contract events are prefixed with `wasm-`
+    let res = app.end_block_results()[0].as_ref().unwrap();
+    res.assert_event(&Event::new("wasm-end_block").add_attribute("height", height.to_string()));
+}
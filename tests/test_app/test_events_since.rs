@@ -0,0 +1,145 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{coins, to_json_binary, BankMsg, Empty, WasmMsg};
+use cw_multi_test::{App, BankSudo, Executor};
+
+#[test]
+fn events_since_should_work() {
+    let mut app = App::default();
+    app.set_event_log_enabled(true);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    // instantiation events are recorded
+    let instantiated = app
+        .events_since(0)
+        .iter()
+        .filter(|event| event.ty == "instantiate")
+        .count();
+    assert_eq!(1, instantiated);
+
+    let cursor = app.event_cursor();
+    assert!(app.events_since(cursor).is_empty());
+
+    // events of committed transactions are recorded
+    let msg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&WasmMsg::ClearAdmin {
+            contract_addr: contract_addr.to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    let res = app.execute(owner.clone(), msg.clone().into()).unwrap();
+    assert_eq!(res.events, app.events_since(cursor));
+
+    // events of failed transactions are not recorded
+    let cursor = app.event_cursor();
+    let burn = BankMsg::Burn {
+        amount: coins(10, "uatom"),
+    };
+    app.execute(owner.clone(), burn.into()).unwrap_err();
+    assert!(app.events_since(cursor).is_empty());
+
+    // events of sudo calls are recorded
+    let res = app
+        .sudo(
+            BankSudo::Mint {
+                to_address: owner.to_string(),
+                amount: coins(10, "uatom"),
+            }
+            .into(),
+        )
+        .unwrap();
+    assert_eq!(res.events, app.events_since(cursor));
+
+    // events of all messages in a transaction are recorded
+    let cursor = app.event_cursor();
+    app.execute_multi(owner, vec![msg.clone().into(), msg.into()])
+        .unwrap();
+    let executed = app
+        .events_since(cursor)
+        .iter()
+        .filter(|event| event.ty == "execute")
+        .count();
+    assert_eq!(2, executed);
+
+    // cursor past the end returns no events
+    assert!(app.events_since(usize::MAX).is_empty());
+}
+
+#[test]
+fn event_log_should_be_disabled_by_default() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    app.init_balances(&[(owner.clone(), coins(100, "uatom"))])
+        .unwrap();
+    let msg = BankMsg::Send {
+        to_address: owner.to_string(),
+        amount: coins(10, "uatom"),
+    };
+    let res = app.execute(owner, msg.into()).unwrap();
+    assert!(!res.events.is_empty());
+    assert_eq!(0, app.event_cursor());
+    assert!(app.events_since(0).is_empty());
+}
+
+#[test]
+fn clearing_event_log_should_keep_cursors_valid() {
+    let mut app = App::default();
+    app.set_event_log_enabled(true);
+    let owner = app.api().addr_make("owner");
+    app.init_balances(&[(owner.clone(), coins(100, "uatom"))])
+        .unwrap();
+    let send = |app: &mut App| {
+        let msg = BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: coins(10, "uatom"),
+        };
+        app.execute(owner.clone(), msg.into()).unwrap()
+    };
+    send(&mut app);
+    let cursor = app.event_cursor();
+    assert!(cursor > 0);
+
+    app.clear_event_log();
+    assert_eq!(cursor, app.event_cursor());
+    assert!(app.events_since(0).is_empty());
+
+    let res = send(&mut app);
+    assert_eq!(res.events, app.events_since(cursor));
+    assert_eq!(res.events, app.events_since(0));
+}
+
+#[test]
+fn restoring_snapshot_should_roll_back_event_log() {
+    let mut app = App::default();
+    app.set_event_log_enabled(true);
+    let owner = app.api().addr_make("owner");
+    app.init_balances(&[(owner.clone(), coins(100, "uatom"))])
+        .unwrap();
+    let send = |app: &mut App| {
+        let msg = BankMsg::Send {
+            to_address: owner.to_string(),
+            amount: coins(10, "uatom"),
+        };
+        app.execute(owner.clone(), msg.into()).unwrap()
+    };
+    let res = send(&mut app);
+    let snapshot = app.snapshot();
+    let cursor = app.event_cursor();
+
+    send(&mut app);
+    app.restore(&snapshot);
+    assert_eq!(cursor, app.event_cursor());
+    assert_eq!(res.events, app.events_since(0));
+
+    // events cleared after the snapshot are rolled back too
+    send(&mut app);
+    app.clear_event_log();
+    app.restore(&snapshot);
+    assert_eq!(cursor, app.event_cursor());
+    assert!(app.events_since(0).is_empty());
+}
@@ -0,0 +1,49 @@
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+const AUTHORITY: Item<Addr> = Item::new("authority");
+
+fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, msg: Addr) -> StdResult<Response> {
+    AUTHORITY.save(deps.storage, &msg)?;
+    Ok(Response::default())
+}
+
+fn execute(deps: DepsMut, _env: Env, info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    if info.sender != AUTHORITY.load(deps.storage)? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+    Ok(Response::default())
+}
+
+fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Ok(Binary::default())
+}
+
+#[test]
+fn execute_as_module_should_use_module_address() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let gov = app.module_address("gov").unwrap();
+    assert_eq!(
+        "cosmwasm10d07y265gmmuvt4z0w9aw880jnsr700jgytlue",
+        gov.as_str()
+    );
+
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &gov, &[], "gated", None)
+        .unwrap();
+
+    // regular accounts are not authorized
+    app.execute_contract(owner, contract_addr.clone(), &Empty {}, &[])
+        .unwrap_err();
+    // other modules are not authorized
+    app.execute_as_module("distribution", contract_addr.clone(), &Empty {})
+        .unwrap_err();
+    // governance module is authorized
+    app.execute_as_module("gov", contract_addr, &Empty {})
+        .unwrap();
+}
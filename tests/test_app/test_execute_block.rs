@@ -0,0 +1,99 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+    WasmMsg,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+const INDEXES: Item<Vec<u32>> = Item::new("indexes");
+
+fn instantiate(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    INDEXES.save(deps.storage, &vec![])?;
+    Ok(Response::default())
+}
+
+fn execute(deps: DepsMut, env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    let index = env.transaction.unwrap().index;
+    INDEXES.update(deps.storage, |mut indexes| -> StdResult<_> {
+        indexes.push(index);
+        Ok(indexes)
+    })?;
+    Ok(Response::default())
+}
+
+fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    to_json_binary(&INDEXES.load(deps.storage)?)
+}
+
+#[test]
+fn transaction_index_should_be_passed_to_contracts() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "indexes", None)
+        .unwrap();
+    let height = app.block_info().height;
+
+    let msg: CosmosMsg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: vec![],
+    }
+    .into();
+    let results = app.execute_block(vec![
+        (owner.clone(), vec![msg.clone()]),
+        (owner.clone(), vec![msg.clone(), msg.clone()]),
+        (owner.clone(), vec![msg]),
+    ]);
+    assert_eq!(height + 1, app.block_info().height);
+    for (index, result) in results.into_iter().enumerate() {
+        let responses = result.unwrap();
+        assert_eq!(index as u32, responses[0].tx.as_ref().unwrap().index);
+    }
+
+    let indexes: Vec<u32> = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &Empty {})
+        .unwrap();
+    assert_eq!(vec![0, 1, 1, 2], indexes);
+}
+
+#[test]
+fn failed_transaction_should_consume_index() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "indexes", None)
+        .unwrap();
+
+    let msg: CosmosMsg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: vec![],
+    }
+    .into();
+    let failing_msg: CosmosMsg = WasmMsg::Execute {
+        contract_addr: owner.to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: vec![],
+    }
+    .into();
+    let results = app.execute_block(vec![
+        (owner.clone(), vec![msg.clone()]),
+        (owner.clone(), vec![failing_msg]),
+        (owner.clone(), vec![msg]),
+    ]);
+    assert!(results[1].is_err());
+    assert_eq!(
+        2,
+        results[2].as_ref().unwrap()[0].tx.as_ref().unwrap().index
+    );
+
+    let indexes: Vec<u32> = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &Empty {})
+        .unwrap();
+    assert_eq!(vec![0, 2], indexes);
+}
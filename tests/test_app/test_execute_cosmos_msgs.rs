@@ -0,0 +1,66 @@
+use cosmwasm_std::{coin, Addr, AnyMsg, BankMsg, CosmosMsg};
+use cw_multi_test::{App, AppBuilder, Executor, StargateAccepting};
+
+#[test]
+fn executing_cosmos_msgs_should_work() {
+    let mut app = App::default();
+    let sender = app.api().addr_make("sender");
+    let alice = app.api().addr_make("alice");
+    let bob = app.api().addr_make("bob");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(100, "uatom")])
+            .unwrap();
+    });
+    let send = |to: &Addr, amount: u128| -> CosmosMsg {
+        BankMsg::Send {
+            to_address: to.to_string(),
+            amount: vec![coin(amount, "uatom")],
+        }
+        .into()
+    };
+
+    // one response per message, all sharing the same transaction
+    let responses = app
+        .execute_cosmos_msgs(sender.clone(), vec![send(&alice, 10), send(&bob, 20)])
+        .unwrap();
+    assert_eq!(2, responses.len());
+    assert_eq!(responses[0].tx, responses[1].tx);
+    assert_eq!(
+        coin(70, "uatom"),
+        app.wrap().query_balance(&sender, "uatom").unwrap()
+    );
+
+    // the batch is atomic, failing message reverts all changes
+    app.execute_cosmos_msgs(sender.clone(), vec![send(&alice, 10), send(&bob, 1000)])
+        .unwrap_err();
+    assert_eq!(
+        coin(70, "uatom"),
+        app.wrap().query_balance(&sender, "uatom").unwrap()
+    );
+    assert_eq!(
+        coin(10, "uatom"),
+        app.wrap().query_balance(&alice, "uatom").unwrap()
+    );
+}
+
+#[test]
+fn executing_any_msgs_should_work() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateAccepting)
+        .build(|_, _, _| {});
+    let sender = app.api().addr_make("sender");
+    let msg = AnyMsg {
+        type_url: "/cosmos.bank.v1beta1.MsgSend".to_string(),
+        value: Default::default(),
+    };
+    let responses = app
+        .execute_any_msgs(sender.clone(), vec![msg.clone(), msg.clone()])
+        .unwrap();
+    assert_eq!(2, responses.len());
+
+    // default application rejects all `Any` messages
+    let mut app = App::default();
+    app.execute_any_msgs(sender, vec![msg]).unwrap_err();
+}
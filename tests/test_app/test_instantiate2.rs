@@ -166,3 +166,35 @@ fn instantiate2_fails_for_duplicated_addresses() {
     // contract addresses should be the same
     assert_eq!(contract_addr_1, contract_addr_2);
 }
+
+#[test]
+fn predict_instantiate2_address_works() {
+    let mut app = AppBuilder::default()
+        .with_api(MockApi::default().with_prefix("juno"))
+        .build(no_init);
+
+    let sender = app.api().addr_make("sender");
+    let creator = app.api().addr_make("creator");
+    let code_id = app.store_code_with_creator(creator, counter::contract());
+    let salt = "bad kids".as_bytes();
+
+    // predict the address before the contract is instantiated
+    let predicted = app
+        .predict_instantiate2_address(code_id, &sender, salt)
+        .unwrap();
+    assert_eq!(
+        "juno1navvz5rjlvn43xjqxlpl7dunk6hglmhuh7c6a53eq6qamfam3dus7a220h",
+        predicted.as_str()
+    );
+
+    // instantiate the contract, the address is the same as predicted
+    let contract_addr = app
+        .instantiate2_contract(code_id, sender, &Empty {}, &[], "label", None, salt)
+        .unwrap();
+    assert_eq!(predicted, contract_addr);
+
+    // predicting the address for not existing code fails
+    assert!(app
+        .predict_instantiate2_address(100, &predicted, salt)
+        .is_err());
+}
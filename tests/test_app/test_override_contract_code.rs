@@ -0,0 +1,61 @@
+use crate::test_contracts::counter::{self, CounterQueryMsg, CounterResponseMsg};
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult, WasmMsg,
+};
+use cw_multi_test::{App, ContractWrapper, Executor};
+use cw_storage_plus::Item;
+
+const COUNTER: Item<u64> = Item::new("counter");
+
+/// Patched counter, incrementing the counter by 10.
+fn execute(deps: DepsMut, _env: Env, _info: MessageInfo, _msg: WasmMsg) -> StdResult<Response> {
+    COUNTER.update(deps.storage, |counter| -> StdResult<_> { Ok(counter + 10) })?;
+    Ok(Response::default())
+}
+
+fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::default())
+}
+
+fn query(deps: Deps, _env: Env, _msg: CounterQueryMsg) -> StdResult<Binary> {
+    to_json_binary(&CounterResponseMsg {
+        value: COUNTER.load(deps.storage)?,
+    })
+}
+
+#[test]
+fn override_contract_code_should_keep_state() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let patched_code_id =
+        app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    app.override_contract_code(&contract_addr, patched_code_id)
+        .unwrap();
+    assert_eq!(
+        patched_code_id,
+        app.contract_data(&contract_addr).unwrap().code_id
+    );
+
+    // patched code works on the original state
+    let msg = WasmMsg::ClearAdmin {
+        contract_addr: contract_addr.to_string(),
+    };
+    app.execute_contract(owner, contract_addr.clone(), &msg, &[])
+        .unwrap();
+    let res: CounterResponseMsg = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &CounterQueryMsg::Counter {})
+        .unwrap();
+    assert_eq!(11, res.value);
+
+    // code must exist
+    app.override_contract_code(&contract_addr, 100).unwrap_err();
+    // contract must exist
+    let unknown = app.api().addr_make("unknown");
+    app.override_contract_code(&unknown, code_id).unwrap_err();
+}
@@ -0,0 +1,130 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{from_json, AnyMsg, CosmosMsg, Empty, Event, GovMsg, Uint128, VoteOption};
+use cw_multi_test::{
+    no_init, App, AppBuilder, Executor, GovKeeper, GovSudo, MsgPinCodes, MSG_PIN_CODES_TYPE_URL,
+};
+use prost::Message;
+
+#[test]
+fn pinning_code_should_work() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let other_code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+    let other_contract_addr = app
+        .instantiate_contract(other_code_id, owner, &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    let pinned = |app: &App, addr| app.wrap().query_wasm_contract_info(addr).unwrap().pinned;
+
+    assert!(!app.is_code_pinned(code_id).unwrap());
+    assert!(!pinned(&app, &contract_addr));
+
+    app.pin_code(code_id).unwrap();
+    assert!(app.is_code_pinned(code_id).unwrap());
+    assert!(pinned(&app, &contract_addr));
+    assert!(!app.is_code_pinned(other_code_id).unwrap());
+    assert!(!pinned(&app, &other_contract_addr));
+
+    // pinning is idempotent
+    app.pin_code(code_id).unwrap();
+    assert!(app.is_code_pinned(code_id).unwrap());
+
+    app.unpin_code(code_id).unwrap();
+    assert!(!app.is_code_pinned(code_id).unwrap());
+    assert!(!pinned(&app, &contract_addr));
+}
+
+#[test]
+fn pinning_unknown_code_should_fail() {
+    let mut app = App::default();
+    assert_eq!("code id: invalid", app.pin_code(0).unwrap_err().to_string());
+    assert_eq!(
+        "code id 5: no such code",
+        app.unpin_code(5).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn pinning_code_should_emit_events() {
+    let mut app = App::default();
+    let code_id = app.store_code(counter::contract());
+
+    let res = app.pin_code(code_id).unwrap();
+    assert!(res.has_event(&Event::new("pin_code").add_attribute("code_id", code_id.to_string())));
+
+    let res = app.unpin_code(code_id).unwrap();
+    assert!(res.has_event(&Event::new("unpin_code").add_attribute("code_id", code_id.to_string())));
+}
+
+#[test]
+fn pinning_code_by_governance_proposal_should_work() {
+    let mut app = AppBuilder::new().with_gov(GovKeeper::new()).build(no_init);
+    let code_id = app.store_code(counter::contract());
+    let voter = app.api().addr_make("voter");
+    app.sudo(
+        GovSudo::SetVotingPower {
+            voter: voter.to_string(),
+            power: Uint128::new(1),
+        }
+        .into(),
+    )
+    .unwrap();
+
+    let msg = MsgPinCodes {
+        authority: app.module_address("gov").unwrap().to_string(),
+        code_ids: vec![code_id],
+    };
+    let res = app
+        .sudo(
+            GovSudo::SubmitProposal {
+                proposer: voter.to_string(),
+                title: "pin counter code".to_string(),
+                messages: vec![CosmosMsg::Any(AnyMsg {
+                    type_url: MSG_PIN_CODES_TYPE_URL.to_string(),
+                    value: msg.encode_to_vec().into(),
+                })],
+            }
+            .into(),
+        )
+        .unwrap();
+    let proposal_id: u64 = from_json(res.data.unwrap()).unwrap();
+    app.execute(
+        voter,
+        GovMsg::Vote {
+            proposal_id,
+            option: VoteOption::Yes,
+        }
+        .into(),
+    )
+    .unwrap();
+    assert!(!app.is_code_pinned(code_id).unwrap());
+
+    app.sudo(GovSudo::Tally { proposal_id }.into()).unwrap();
+    assert!(app.is_code_pinned(code_id).unwrap());
+}
+
+#[test]
+fn pinning_code_by_other_account_should_fail() {
+    let mut app = App::default();
+    let code_id = app.store_code(counter::contract());
+    let sender = app.api().addr_make("sender");
+    let msg = MsgPinCodes {
+        authority: sender.to_string(),
+        code_ids: vec![code_id],
+    };
+    let err = app
+        .execute(
+            sender,
+            CosmosMsg::Any(AnyMsg {
+                type_url: MSG_PIN_CODES_TYPE_URL.to_string(),
+                value: msg.encode_to_vec().into(),
+            }),
+        )
+        .unwrap_err();
+    assert!(err.to_string().starts_with("unauthorized"), "{}", err);
+    assert!(!app.is_code_pinned(code_id).unwrap());
+}
@@ -0,0 +1,139 @@
+use crate::test_contracts::counter::{self, CounterQueryMsg, CounterResponseMsg};
+use cosmwasm_std::{coin, BankMsg, Empty, Order, Record, Storage, WasmMsg};
+use cw_multi_test::{next_block, App, Executor, QueryHandle};
+
+fn dump(storage: &dyn Storage) -> Vec<Record> {
+    storage.range(None, None, Order::Ascending).collect()
+}
+
+fn dump_in_thread(handle: &QueryHandle) -> Vec<Record> {
+    let handle = handle.clone();
+    std::thread::spawn(move || handle.read(|storage, _| dump(storage)))
+        .join()
+        .unwrap()
+}
+
+#[test]
+fn query_handle_should_follow_committed_state() {
+    let mut app = App::default();
+    let sender = app.api().addr_make("sender");
+    let recipient = app.api().addr_make("recipient");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(100, "ucosm")])
+            .unwrap();
+    });
+
+    let handle = app.query_handle();
+    assert_eq!(dump(app.storage()), dump_in_thread(&handle));
+
+    // executing a transaction refreshes the snapshot
+    let msg = BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: vec![coin(10, "ucosm")],
+    };
+    app.execute(sender, msg.into()).unwrap();
+    assert_eq!(dump(app.storage()), dump_in_thread(&handle));
+
+    // updating the block refreshes the snapshot
+    app.update_block(next_block);
+    assert_eq!(app.block_info(), handle.block_info());
+}
+
+#[test]
+fn query_handle_should_not_see_uncommitted_changes() {
+    let mut app = App::default();
+    let handle = app.query_handle();
+
+    // direct changes are not visible until the next refresh
+    app.storage_mut().set(b"key", b"value");
+    assert_eq!(None, handle.get(b"key"));
+
+    // all handles share the same snapshot
+    let other = app.query_handle();
+    assert_eq!(Some(b"value".to_vec()), handle.get(b"key"));
+    assert_eq!(Some(b"value".to_vec()), other.get(b"key"));
+}
+
+#[test]
+fn query_handle_should_read_prefixed_storage() {
+    let mut app = App::default();
+    app.prefixed_storage_mut(b"module").set(b"key", b"value");
+    let handle = app.query_handle();
+    let value = handle.read_prefixed(&[b"module"], |storage| storage.get(b"key"));
+    assert_eq!(Some(b"value".to_vec()), value);
+}
+
+#[test]
+fn query_handle_should_answer_router_queries_from_snapshot() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+    let handle = app.query_handle();
+
+    let query_counter = |handle: &QueryHandle, app: &App| -> u64 {
+        handle.with_querier(app.router(), app.api(), |querier| {
+            querier
+                .query_wasm_smart::<CounterResponseMsg>(
+                    &contract_addr,
+                    &CounterQueryMsg::Counter {},
+                )
+                .unwrap()
+                .value
+        })
+    };
+    assert_eq!(1, query_counter(&handle, &app));
+
+    // executed transactions are visible to smart queries
+    let msg = WasmMsg::ClearAdmin {
+        contract_addr: contract_addr.to_string(),
+    };
+    app.execute_contract(owner.clone(), contract_addr.clone(), &msg, &[])
+        .unwrap();
+    assert_eq!(2, query_counter(&handle, &app));
+
+    // direct changes are not visible until the next refresh
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &owner, vec![coin(100, "ucosm")])
+            .unwrap();
+    });
+    let balance = handle.with_querier(app.router(), app.api(), |querier| {
+        querier.query_balance(&owner, "ucosm").unwrap()
+    });
+    assert_eq!(coin(0, "ucosm"), balance);
+    let balance = app
+        .query_handle()
+        .with_querier(app.router(), app.api(), |querier| {
+            querier.query_balance(&owner, "ucosm").unwrap()
+        });
+    assert_eq!(coin(100, "ucosm"), balance);
+}
+
+#[test]
+fn query_handle_reads_should_not_see_refreshes() {
+    let mut app = App::default();
+    app.storage_mut().set(b"key", b"old");
+    let handle = app.query_handle();
+
+    handle.read(|storage, _| {
+        // refreshing while the snapshot is read does not change it
+        app.storage_mut().set(b"key", b"new");
+        app.storage_mut().set(b"other", b"value");
+        app.query_handle();
+        assert_eq!(Some(b"old".to_vec()), storage.get(b"key"));
+        assert_eq!(None, storage.get(b"other"));
+    });
+    assert_eq!(Some(b"new".to_vec()), handle.get(b"key"));
+
+    // removed records are removed from the snapshot too
+    app.storage_mut().remove(b"key");
+    app.update_block(next_block);
+    assert_eq!(None, handle.get(b"key"));
+    assert_eq!(dump(app.storage()), dump_in_thread(&handle));
+}
@@ -0,0 +1,62 @@
+use cosmwasm_std::{to_json_vec, Empty, GrpcQuery, Querier, QueryRequest};
+use cw_multi_test::{
+    next_block, no_init, AppBuilder, QueryRandomnessRequest, QueryRandomnessResponse,
+    StargateKeeper, RANDOMNESS_QUERY_PATH,
+};
+use prost::Message;
+
+#[test]
+fn randomness_should_be_deterministic_per_block() {
+    let build = || {
+        AppBuilder::default()
+            .with_random_seed(b"seed".to_vec())
+            .build(no_init)
+    };
+    let mut app1 = build();
+    let mut app2 = build();
+
+    // the same seed produces the same randomness
+    let first = app1.block_randomness().unwrap();
+    assert_eq!(32, first.len());
+    assert_eq!(Some(first.clone()), app2.block_randomness());
+
+    // randomness is rotated with every block
+    app1.update_block(next_block);
+    app2.update_block(next_block);
+    let second = app1.block_randomness().unwrap();
+    assert_ne!(first, second);
+    assert_eq!(Some(second), app2.block_randomness());
+
+    // other seed produces other randomness
+    app2.set_random_seed(b"other".to_vec());
+    assert_ne!(app1.block_randomness(), app2.block_randomness());
+}
+
+#[test]
+fn no_randomness_without_seed() {
+    let app = AppBuilder::default().build(no_init);
+    assert_eq!(None, app.block_randomness());
+}
+
+#[test]
+fn randomness_should_be_queryable() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateKeeper::new())
+        .with_random_seed(b"seed".to_vec())
+        .build(no_init);
+    app.update_block(next_block);
+
+    let request: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+        path: RANDOMNESS_QUERY_PATH.to_string(),
+        data: QueryRandomnessRequest {}.encode_to_vec().into(),
+    });
+    let response = app
+        .raw_query(&to_json_vec(&request).unwrap())
+        .unwrap()
+        .unwrap();
+    let response = QueryRandomnessResponse::decode(response.as_slice()).unwrap();
+    assert_eq!(
+        app.block_randomness().unwrap().to_vec(),
+        response.randomness
+    );
+}
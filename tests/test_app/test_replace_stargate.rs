@@ -0,0 +1,45 @@
+use cosmwasm_std::{coin, AnyMsg, CosmosMsg};
+use cw_multi_test::{App, AppBuilder, Executor, StargateAccepting, StargateFailing};
+
+#[test]
+fn replacing_stargate_should_work() {
+    let mut app = App::default();
+    let sender = app.api().addr_make("sender");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(100, "uatom")])
+            .unwrap();
+    });
+    let msg = CosmosMsg::Any(AnyMsg {
+        type_url: "/this.is.a.stargate.test.helper".to_string(),
+        value: Default::default(),
+    });
+
+    // default stargate module rejects all messages
+    app.execute(sender.clone(), msg.clone()).unwrap_err();
+
+    // replace the stargate module, the state should be preserved
+    let mut app = app.replace_stargate(StargateAccepting);
+    app.execute(sender.clone(), msg).unwrap();
+    assert_eq!(
+        coin(100, "uatom"),
+        app.wrap().query_balance(sender, "uatom").unwrap()
+    );
+}
+
+#[test]
+fn replacing_stargate_back_should_work() {
+    let mut app = AppBuilder::default()
+        .with_stargate(StargateAccepting)
+        .build(|_, _, _| {});
+    let sender = app.api().addr_make("sender");
+    let msg = CosmosMsg::Any(AnyMsg {
+        type_url: "/this.is.a.stargate.test.helper".to_string(),
+        value: Default::default(),
+    });
+    app.execute(sender.clone(), msg.clone()).unwrap();
+
+    let mut app = app.replace_stargate(StargateFailing);
+    app.execute(sender, msg).unwrap_err();
+}
@@ -0,0 +1,90 @@
+use crate::test_contracts::counter::{self, CounterQueryMsg, CounterResponseMsg};
+use cosmwasm_std::{to_json_binary, Empty, WasmMsg};
+use cw_multi_test::{no_init, AppBuilder, Executor, GasCosts, Wasm, WasmKeeper};
+
+#[test]
+fn simulate_should_not_change_state() {
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(GasCosts::default());
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    let msg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&WasmMsg::ClearAdmin {
+            contract_addr: contract_addr.to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+
+    // simulation returns events and gas
+    let simulated = app.simulate(owner.clone(), msg.clone().into()).unwrap();
+    let simulated_gas = simulated.tx.as_ref().unwrap().gas_used;
+    assert!(simulated_gas > 0);
+
+    // the counter was not incremented
+    let res: CounterResponseMsg = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &CounterQueryMsg::Counter {})
+        .unwrap();
+    assert_eq!(1, res.value);
+
+    // real execution gives the same events and consumes the same gas
+    let executed = app.execute(owner, msg.into()).unwrap();
+    assert_eq!(simulated.events, executed.events);
+    assert_eq!(simulated_gas, executed.tx.unwrap().gas_used);
+    let res: CounterResponseMsg = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &CounterQueryMsg::Counter {})
+        .unwrap();
+    assert_eq!(2, res.value);
+}
+
+#[test]
+fn simulate_should_not_change_trace_and_profiling() {
+    let wasm_keeper = WasmKeeper::new()
+        .with_gas_costs(GasCosts::default())
+        .with_tracing()
+        .with_profiling();
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    let msg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&WasmMsg::ClearAdmin {
+            contract_addr: contract_addr.to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    let executed = app.execute(owner.clone(), msg.clone().into()).unwrap();
+    let trace = app.last_trace().unwrap();
+    let report = app.profiling_report().unwrap();
+
+    let simulated = app.simulate(owner.clone(), msg.clone().into()).unwrap();
+    assert_eq!(
+        executed.tx.as_ref().unwrap().gas_used,
+        simulated.tx.as_ref().unwrap().gas_used
+    );
+    assert_eq!(trace, app.last_trace().unwrap());
+    assert_eq!(report, app.profiling_report().unwrap());
+
+    // the simulated transaction used the next index, which is still free
+    let next = app.execute(owner, msg.into()).unwrap();
+    assert_eq!(simulated.tx.unwrap().index, next.tx.unwrap().index);
+}
+
+#[test]
+fn wasm_trait_should_remain_object_safe() {
+    let wasm_keeper = WasmKeeper::<Empty, Empty>::new();
+    let wasm: &dyn Wasm<Empty, Empty> = &wasm_keeper;
+    assert_eq!(0, wasm.gas_used());
+}
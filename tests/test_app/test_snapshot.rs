@@ -0,0 +1,130 @@
+use crate::test_contracts::counter::{self, CounterQueryMsg, CounterResponseMsg};
+use cosmwasm_std::{coin, coins, from_json, to_json_vec, BankMsg, Empty, Storage, WasmMsg};
+use cw_multi_test::{assert_same_storage, next_block, App, AppSnapshot, BankSudo, Executor};
+
+#[test]
+fn restoring_snapshot_should_work() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    let query_counter = |app: &App| -> u64 {
+        app.wrap()
+            .query_wasm_smart::<CounterResponseMsg>(&contract_addr, &CounterQueryMsg::Counter {})
+            .unwrap()
+            .value
+    };
+    let increment = WasmMsg::ClearAdmin {
+        contract_addr: contract_addr.to_string(),
+    };
+
+    let snapshot = app.snapshot();
+    let mut expected = App::default();
+    expected.storage_mut().set(b"unused", b"value");
+    assert_eq!(&app.block_info(), snapshot.block());
+
+    // first continuation
+    app.update_block(next_block);
+    app.execute_contract(owner.clone(), contract_addr.clone(), &increment, &[])
+        .unwrap();
+    assert_eq!(2, query_counter(&app));
+
+    // second continuation starts from the snapshot
+    app.restore(&snapshot);
+    assert_eq!(snapshot.block(), &app.block_info());
+    assert_eq!(1, query_counter(&app));
+    expected.restore(&snapshot);
+    assert_same_storage(app.storage(), expected.storage());
+
+    // snapshot can be restored multiple times
+    app.execute_contract(owner.clone(), contract_addr.clone(), &increment, &[])
+        .unwrap();
+    app.execute_contract(owner, contract_addr.clone(), &increment, &[])
+        .unwrap();
+    assert_eq!(3, query_counter(&app));
+    app.restore(&snapshot);
+    assert_eq!(1, query_counter(&app));
+}
+
+#[test]
+fn snapshot_should_be_serializable() {
+    let mut app = App::default();
+    app.storage_mut().set(b"key", b"value");
+    let snapshot = app.snapshot();
+    let json = to_json_vec(&snapshot).unwrap();
+    assert_eq!(snapshot, from_json(json).unwrap());
+}
+
+#[test]
+fn snapshot_should_be_saved_to_file() {
+    let mut app = App::default();
+    let user = app.api().addr_make("user");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &user, vec![coin(100, "uatom")])
+            .unwrap();
+    });
+    let path = std::env::temp_dir().join(format!("snapshot-{}.json", std::process::id()));
+    app.snapshot().save(&path).unwrap();
+
+    // load the state into a fresh application
+    let snapshot = AppSnapshot::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let mut other = App::default();
+    other.restore(&snapshot);
+    assert_same_storage(app.storage(), other.storage());
+    assert_eq!(
+        coin(100, "uatom"),
+        other.wrap().query_balance(&user, "uatom").unwrap()
+    );
+
+    // loading a missing file fails
+    AppSnapshot::load(&path).unwrap_err();
+}
+
+#[test]
+fn restoring_snapshot_should_roll_back_app_state() {
+    let mut app = App::default();
+    let user = app.api().addr_make("user");
+    app.init_balances(&[(user.clone(), coins(100, "uatom"))])
+        .unwrap();
+    let send = BankMsg::Send {
+        to_address: user.to_string(),
+        amount: coins(10, "uatom"),
+    };
+    let first = app.execute(user.clone(), send.clone().into()).unwrap();
+    let first_hash = first.tx.unwrap().hash;
+    let height = app.block_info().height;
+
+    let snapshot = app.snapshot();
+
+    // transactions in the same block, end block results and chain upgrades
+    let second = app.execute(user.clone(), send.clone().into()).unwrap();
+    let second_tx = second.tx.unwrap();
+    assert_eq!(1, second_tx.index);
+    app.add_end_blocker(BankSudo::Mint {
+        to_address: user.to_string(),
+        amount: coins(1, "uatom"),
+    });
+    app.schedule_chain_upgrade(height + 10, "upgraded-2");
+    app.update_block(next_block);
+    assert_eq!(1, app.end_block_results().len());
+
+    app.restore(&snapshot);
+    assert!(app.tx(&first_hash).is_some());
+    assert!(app.tx(&second_tx.hash).is_none());
+    assert!(app.end_block_results().is_empty());
+
+    // the next transaction in the restored block gets the same index again
+    let third = app.execute(user.clone(), send.into()).unwrap();
+    assert_eq!(1, third.tx.unwrap().index);
+
+    // the chain upgrade scheduled after the snapshot is not applied
+    let chain_id = app.block_info().chain_id;
+    app.update_block(|block| block.height += 10);
+    assert_eq!(chain_id, app.block_info().chain_id);
+}
@@ -0,0 +1,57 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{coins, to_json_binary, to_json_vec, Empty, WasmMsg};
+use cw_multi_test::{App, BankSudo, Executor, SudoMsg};
+
+#[test]
+fn last_storage_writes_should_work() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    // instantiation writes contract's data and contract's storage
+    let writes = app.last_storage_writes();
+    let write = writes
+        .iter()
+        .find(|write| write.contract.as_ref() == Some(&contract_addr))
+        .unwrap();
+    assert_eq!(b"counter".to_vec(), write.key);
+    assert_eq!(None, write.old_value);
+    assert_eq!(Some(to_json_vec(&1).unwrap()), write.new_value);
+    assert!(writes.iter().any(|write| write.contract.is_none()));
+
+    // execution increments the counter, this is the only write
+    let msg = WasmMsg::ClearAdmin {
+        contract_addr: contract_addr.to_string(),
+    };
+    app.execute_contract(owner.clone(), contract_addr.clone(), &msg, &[])
+        .unwrap();
+    let writes = app.last_storage_writes();
+    assert_eq!(1, writes.len());
+    assert_eq!(Some(contract_addr.clone()), writes[0].contract);
+    assert_eq!(b"counter".to_vec(), writes[0].key);
+    assert_eq!(Some(to_json_vec(&1).unwrap()), writes[0].old_value);
+    assert_eq!(Some(to_json_vec(&2).unwrap()), writes[0].new_value);
+
+    // failed execution writes nothing
+    app.execute_contract(
+        owner.clone(),
+        contract_addr.clone(),
+        &to_json_binary(&Empty {}).unwrap(),
+        &[],
+    )
+    .unwrap_err();
+    assert!(app.last_storage_writes().is_empty());
+
+    // sudo writes are reported too, they do not touch contracts
+    app.sudo(SudoMsg::Bank(BankSudo::Mint {
+        to_address: owner.to_string(),
+        amount: coins(10, "uatom"),
+    }))
+    .unwrap();
+    let writes = app.last_storage_writes();
+    assert!(!writes.is_empty());
+    assert!(writes.iter().all(|write| write.contract.is_none()));
+}
@@ -0,0 +1,130 @@
+use crate::test_contracts::counter::{self, CounterQueryMsg, CounterResponseMsg};
+use cosmwasm_std::{to_json_binary, Empty, Event};
+use cw_multi_test::{App, Executor, WasmAdminSudo};
+
+#[test]
+fn sudo_migrate_should_ignore_admin() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract_with_migrate());
+    let new_code_id = app.duplicate_code(code_id).unwrap();
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    // contract without admin can not be migrated by anyone
+    app.migrate_contract(owner, contract_addr.clone(), &Empty {}, new_code_id)
+        .unwrap_err();
+
+    // but can be migrated by governance
+    let res = app
+        .sudo_migrate(&contract_addr, new_code_id, &Empty {})
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("migrate")
+            .add_attribute("_contract_address", &contract_addr)
+            .add_attribute("code_id", new_code_id.to_string())
+    ));
+    assert_eq!(
+        new_code_id,
+        app.contract_data(&contract_addr).unwrap().code_id
+    );
+    let res: CounterResponseMsg = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &CounterQueryMsg::Counter {})
+        .unwrap();
+    assert_eq!(100, res.value);
+
+    // migration to unknown code fails
+    app.sudo_migrate(&contract_addr, 100, &Empty {})
+        .unwrap_err();
+}
+
+#[test]
+fn governance_managed_contract_should_be_migrated() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let gov = app.module_address("gov").unwrap();
+    let code_id = app.store_code(counter::contract_with_migrate());
+    let new_code_id = app.duplicate_code(code_id).unwrap();
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "counter",
+            Some(owner.to_string()),
+        )
+        .unwrap();
+
+    // hand the contract over to governance
+    let res = app
+        .sudo_update_admin(&contract_addr, Some(gov.clone()))
+        .unwrap();
+    assert!(res.has_event(
+        &Event::new("update_contract_admin")
+            .add_attribute("_contract_address", &contract_addr)
+            .add_attribute("new_admin_address", &gov)
+    ));
+    assert!(!app.last_storage_writes().is_empty());
+    assert_eq!(
+        Some(gov.clone()),
+        app.contract_data(&contract_addr).unwrap().admin
+    );
+
+    // previous admin can not migrate the contract anymore
+    app.migrate_contract(owner, contract_addr.clone(), &Empty {}, new_code_id)
+        .unwrap_err();
+
+    // governance can
+    app.migrate_contract(gov, contract_addr.clone(), &Empty {}, new_code_id)
+        .unwrap();
+    assert_eq!(
+        new_code_id,
+        app.contract_data(&contract_addr).unwrap().code_id
+    );
+
+    // clearing the admin
+    app.sudo_update_admin(&contract_addr, None).unwrap();
+    assert_eq!(None, app.contract_data(&contract_addr).unwrap().admin);
+}
+
+#[test]
+fn sudo_update_admin_should_be_sent_as_sudo_message() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract_with_migrate());
+    let contract_addr = app
+        .instantiate_contract(
+            code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "counter",
+            Some(owner.to_string()),
+        )
+        .unwrap();
+
+    app.sudo(
+        WasmAdminSudo::UpdateAdmin {
+            contract_addr: contract_addr.clone(),
+            admin: None,
+        }
+        .into(),
+    )
+    .unwrap();
+    assert_eq!(None, app.contract_data(&contract_addr).unwrap().admin);
+
+    // failing privileged actions do not change the state
+    app.sudo(
+        WasmAdminSudo::Migrate {
+            contract_addr: contract_addr.clone(),
+            new_code_id: 100,
+            msg: to_json_binary(&Empty {}).unwrap(),
+        }
+        .into(),
+    )
+    .unwrap_err();
+    assert_eq!(code_id, app.contract_data(&contract_addr).unwrap().code_id);
+}
@@ -0,0 +1,87 @@
+use cosmwasm_std::{coin, BankMsg, CosmosMsg};
+use cw_multi_test::{next_block, App, Executor};
+
+fn send_msg(to_address: &str, amount: u128) -> CosmosMsg {
+    BankMsg::Send {
+        to_address: to_address.to_string(),
+        amount: vec![coin(amount, "ucosm")],
+    }
+    .into()
+}
+
+#[test]
+fn executed_transactions_should_have_metadata() {
+    let mut app = App::default();
+    let sender = app.api().addr_make("sender");
+    let recipient = app.api().addr_make("recipient");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(100, "ucosm")])
+            .unwrap();
+    });
+    let height = app.block_info().height;
+
+    // transactions in the same block get consecutive indexes
+    let res1 = app
+        .execute(sender.clone(), send_msg(recipient.as_str(), 10))
+        .unwrap();
+    let res2 = app
+        .execute(sender.clone(), send_msg(recipient.as_str(), 10))
+        .unwrap();
+    let tx1 = res1.tx.unwrap();
+    let tx2 = res2.tx.unwrap();
+    assert_eq!((height, 0), (tx1.height, tx1.index));
+    assert_eq!((height, 1), (tx2.height, tx2.index));
+    assert_eq!(64, tx1.hash.len());
+    assert_ne!(tx1.hash, tx2.hash);
+
+    // failed transactions consume the index too
+    app.execute(sender.clone(), send_msg(recipient.as_str(), 1000))
+        .unwrap_err();
+    let res4 = app
+        .execute(sender.clone(), send_msg(recipient.as_str(), 10))
+        .unwrap();
+    let tx4 = res4.tx.unwrap();
+    assert_eq!((height, 3), (tx4.height, tx4.index));
+
+    // index is reset in the next block
+    app.update_block(next_block);
+    let res3 = app
+        .execute(sender, send_msg(recipient.as_str(), 10))
+        .unwrap();
+    let tx3 = res3.tx.unwrap();
+    assert_eq!((height + 1, 0), (tx3.height, tx3.index));
+}
+
+#[test]
+fn executed_transactions_should_be_queryable_by_hash() {
+    let mut app = App::default();
+    let sender = app.api().addr_make("sender");
+    let recipient = app.api().addr_make("recipient");
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &sender, vec![coin(100, "ucosm")])
+            .unwrap();
+    });
+
+    let responses = app
+        .execute_multi(
+            sender,
+            vec![
+                send_msg(recipient.as_str(), 10),
+                send_msg(recipient.as_str(), 20),
+            ],
+        )
+        .unwrap();
+    let tx = responses[0].tx.clone().unwrap();
+    assert_eq!(Some(&tx), responses[1].tx.as_ref());
+
+    let stored = app.tx(&tx.hash).unwrap();
+    assert_eq!(2, stored.len());
+    assert_eq!(responses[0].events, stored[0].events);
+    assert_eq!(responses[1].events, stored[1].events);
+
+    assert!(app.tx("unknown").is_none());
+}
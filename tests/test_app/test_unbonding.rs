@@ -0,0 +1,72 @@
+use cosmwasm_std::testing::{mock_env, MockApi};
+use cosmwasm_std::{coin, Decimal, StakingMsg, Validator};
+use cw_multi_test::{App, AppBuilder, Executor, StakingInfo};
+
+#[test]
+fn unbonded_funds_should_be_released_after_unbonding_time() {
+    let api = MockApi::default();
+    let delegator = api.addr_make("delegator");
+    let validator = api.addr_make("validator");
+    let mut app = AppBuilder::default().build(|router, api, storage| {
+        router
+            .staking
+            .setup(
+                storage,
+                StakingInfo {
+                    bonded_denom: "ustake".to_string(),
+                    unbonding_time: 100,
+                    apr: Decimal::zero(),
+                },
+            )
+            .unwrap();
+        router
+            .staking
+            .add_validator(
+                api,
+                storage,
+                &mock_env().block,
+                Validator::new(
+                    validator.to_string(),
+                    Decimal::zero(),
+                    Decimal::one(),
+                    Decimal::one(),
+                ),
+            )
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &delegator, vec![coin(100, "ustake")])
+            .unwrap();
+    });
+    app.set_block(mock_env().block);
+
+    let balance = |app: &App| app.wrap().query_balance(&delegator, "ustake").unwrap();
+
+    app.execute(
+        delegator.clone(),
+        StakingMsg::Delegate {
+            validator: validator.to_string(),
+            amount: coin(100, "ustake"),
+        }
+        .into(),
+    )
+    .unwrap();
+    app.execute(
+        delegator.clone(),
+        StakingMsg::Undelegate {
+            validator: validator.to_string(),
+            amount: coin(100, "ustake"),
+        }
+        .into(),
+    )
+    .unwrap();
+    assert_eq!(coin(0, "ustake"), balance(&app));
+
+    // funds are not released before the unbonding time passes
+    app.update_block(|block| block.time = block.time.plus_seconds(99));
+    assert_eq!(coin(0, "ustake"), balance(&app));
+
+    // funds are released as soon as the block reaches the unbonding time
+    app.update_block(|block| block.time = block.time.plus_seconds(1));
+    assert_eq!(coin(100, "ustake"), balance(&app));
+}
@@ -8,6 +8,7 @@ use std::marker::PhantomData;
 mod test_with_api;
 mod test_with_bank;
 mod test_with_block;
+mod test_with_chain_profile;
 mod test_with_distribution;
 mod test_with_gov;
 mod test_with_ibc;
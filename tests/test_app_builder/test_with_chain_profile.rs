@@ -0,0 +1,95 @@
+use cosmwasm_std::testing::mock_env;
+use cosmwasm_std::{coin, BlockInfo, Decimal, StakingMsg, Validator};
+use cw_multi_test::{no_init, AppBuilder, ChainProfile, Executor, IntoBech32, StakingInfo};
+
+#[test]
+fn building_app_with_chain_profile_should_work() {
+    let mut app = AppBuilder::default()
+        .with_chain_profile(ChainProfile::juno())
+        .build(no_init);
+
+    // block uses the chain identifier from the profile
+    assert_eq!("juno-1", app.block_info().chain_id);
+
+    // addresses use the prefix from the profile
+    let delegator = app.api().addr_make("delegator");
+    assert_eq!("delegator".into_bech32_with_prefix("juno"), delegator);
+
+    // staking uses the bonded denomination from the profile
+    assert_eq!("ujuno", app.wrap().query_bonded_denom().unwrap());
+
+    let validator = app.api().addr_make("validator");
+    let block = app.block_info();
+    app.init_modules(|router, api, storage| {
+        router
+            .staking
+            .add_validator(
+                api,
+                storage,
+                &block,
+                Validator::new(
+                    validator.to_string(),
+                    Decimal::zero(),
+                    Decimal::one(),
+                    Decimal::one(),
+                ),
+            )
+            .unwrap();
+        router
+            .bank
+            .init_balance(storage, &delegator, vec![coin(100, "ujuno")])
+            .unwrap();
+    });
+    app.execute(
+        delegator,
+        StakingMsg::Delegate {
+            validator: validator.to_string(),
+            amount: coin(100, "ujuno"),
+        }
+        .into(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn building_app_with_custom_chain_profile_should_work() {
+    let profile = ChainProfile {
+        chain_id: "local-1",
+        bech32_prefix: "local",
+        bonded_denom: "ustake",
+    };
+    let app = AppBuilder::default()
+        .with_chain_profile(profile)
+        .build(no_init);
+
+    assert_eq!("local-1", app.block_info().chain_id);
+    assert!(app.api().addr_make("user").as_str().starts_with("local1"));
+    assert_eq!("ustake", app.wrap().query_bonded_denom().unwrap());
+    assert_eq!(
+        StakingInfo {
+            bonded_denom: "ustake".to_string(),
+            ..Default::default()
+        },
+        profile.staking_info()
+    );
+}
+
+#[test]
+fn block_set_after_chain_profile_should_overwrite_chain_id() {
+    let block = BlockInfo {
+        chain_id: "custom-1".to_string(),
+        ..mock_env().block
+    };
+
+    let app = AppBuilder::default()
+        .with_chain_profile(ChainProfile::juno())
+        .with_block(block.clone())
+        .build(no_init);
+    assert_eq!("custom-1", app.block_info().chain_id);
+
+    let app = AppBuilder::default()
+        .with_block(block)
+        .with_chain_profile(ChainProfile::juno())
+        .build(no_init);
+    assert_eq!("juno-1", app.block_info().chain_id);
+}
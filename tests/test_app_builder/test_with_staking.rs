@@ -45,6 +45,7 @@ fn building_app_with_custom_staking_should_work() {
             StakingSudo::Slash {
                 validator: validator_addr.into(),
                 percentage: Default::default(),
+                jail: false,
             }
             .into()
         )
@@ -1,3 +1,6 @@
+mod test_assert_same_storage;
+mod test_storage_injection;
+
 use crate::test_contracts::counter;
 use crate::test_contracts::counter::{CounterQueryMsg, CounterResponseMsg};
 use cosmwasm_std::{to_json_binary, Empty, WasmMsg};
@@ -0,0 +1,85 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{to_json_binary, Addr, Empty, WasmMsg};
+use cw_multi_test::{
+    assert_same_storage, assert_same_storage_filtered, storage_diff, App, Executor, StorageDiff,
+};
+
+fn counter_app() -> (App, Addr) {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner, &Empty {}, &[], "counter", None)
+        .unwrap();
+    (app, contract_addr)
+}
+
+fn increment(app: &mut App, contract_addr: &Addr) {
+    let owner = app.api().addr_make("owner");
+    let msg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: vec![],
+    };
+    app.execute_contract(owner, contract_addr.clone(), &msg, &[])
+        .unwrap();
+}
+
+#[test]
+fn same_contract_storage_should_pass() {
+    let (mut app_a, addr_a) = counter_app();
+    let (mut app_b, addr_b) = counter_app();
+    increment(&mut app_a, &addr_a);
+    increment(&mut app_b, &addr_b);
+    assert_same_storage(
+        &*app_a.contract_storage(&addr_a),
+        &*app_b.contract_storage(&addr_b),
+    );
+}
+
+#[test]
+fn different_contract_storage_should_be_reported() {
+    let (mut app_a, addr_a) = counter_app();
+    let (app_b, addr_b) = counter_app();
+    increment(&mut app_a, &addr_a);
+    app_a.contract_storage_mut(&addr_a).set(b"extra", b"1");
+
+    let diffs = storage_diff(
+        &*app_a.contract_storage(&addr_a),
+        &*app_b.contract_storage(&addr_b),
+        |_| true,
+    );
+    assert_eq!(
+        vec![
+            StorageDiff::Changed {
+                key: b"counter".to_vec(),
+                left: b"2".to_vec(),
+                right: b"1".to_vec(),
+            },
+            StorageDiff::OnlyLeft {
+                key: b"extra".to_vec(),
+                value: b"1".to_vec(),
+            },
+        ],
+        diffs
+    );
+
+    // filtered keys are not compared
+    assert_same_storage_filtered(
+        &*app_a.contract_storage(&addr_a),
+        &*app_b.contract_storage(&addr_b),
+        |key| key != b"counter" && key != b"extra",
+    );
+}
+
+#[test]
+#[should_panic(expected = "storages differ (1 keys):\n+ extra = 1")]
+fn different_contract_storage_should_panic() {
+    let (app_a, addr_a) = counter_app();
+    let (mut app_b, addr_b) = counter_app();
+    app_b.contract_storage_mut(&addr_b).set(b"extra", b"1");
+    assert_same_storage(
+        &*app_a.contract_storage(&addr_a),
+        &*app_b.contract_storage(&addr_b),
+    );
+}
@@ -0,0 +1,84 @@
+use crate::test_contracts::counter::{self, CounterQueryMsg, CounterResponseMsg};
+use cosmwasm_std::{Addr, Empty, WasmMsg};
+use cw_multi_test::{App, Executor};
+use cw_storage_plus::Map;
+
+fn counter_app() -> (App, Addr) {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner, &Empty {}, &[], "counter", None)
+        .unwrap();
+    (app, contract_addr)
+}
+
+fn counter_value(app: &App, contract_addr: &Addr) -> u64 {
+    let res: CounterResponseMsg = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &CounterQueryMsg::Counter {})
+        .unwrap();
+    res.value
+}
+
+#[test]
+fn set_contract_storage_should_work() {
+    let (mut app, contract_addr) = counter_app();
+    app.set_contract_storage(&contract_addr, b"counter", b"42");
+    assert_eq!(42, counter_value(&app, &contract_addr));
+}
+
+#[test]
+fn save_item_should_work() {
+    let (mut app, contract_addr) = counter_app();
+    app.save_item(&contract_addr, "counter", &100u64).unwrap();
+    assert_eq!(100, counter_value(&app, &contract_addr));
+}
+
+#[test]
+fn save_map_entry_should_work() {
+    const BALANCES: Map<&Addr, u128> = Map::new("balances");
+
+    let (mut app, contract_addr) = counter_app();
+    let holder = app.api().addr_make("holder");
+    app.save_map_entry(&contract_addr, "balances", &holder, &1000u128)
+        .unwrap();
+
+    let storage = app.contract_storage(&contract_addr);
+    assert_eq!(1000, BALANCES.load(&*storage, &holder).unwrap());
+    assert!(storage.get(b"counter").is_some());
+}
+
+#[test]
+fn load_item_should_work() {
+    let (mut app, contract_addr) = counter_app();
+    assert_eq!(1, app.load_item::<u64>(&contract_addr, "counter").unwrap());
+
+    let sender = app.api().addr_make("sender");
+    let msg = WasmMsg::ClearAdmin {
+        contract_addr: contract_addr.to_string(),
+    };
+    app.execute_contract(sender, contract_addr.clone(), &msg, &[])
+        .unwrap();
+    assert_eq!(2, app.load_item::<u64>(&contract_addr, "counter").unwrap());
+
+    assert!(app.load_item::<u64>(&contract_addr, "missing").is_err());
+}
+
+#[test]
+fn load_map_entry_should_work() {
+    let (mut app, contract_addr) = counter_app();
+    let holder = app.api().addr_make("holder");
+    app.save_map_entry(&contract_addr, "balances", &holder, &1000u128)
+        .unwrap();
+
+    let balance: u128 = app
+        .load_map_entry(&contract_addr, "balances", &holder)
+        .unwrap();
+    assert_eq!(1000, balance);
+
+    let other = app.api().addr_make("other");
+    assert!(app
+        .load_map_entry::<_, u128>(&contract_addr, "balances", &other)
+        .is_err());
+}
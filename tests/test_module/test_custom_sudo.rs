@@ -0,0 +1,82 @@
+use cosmwasm_std::{to_json_binary, Addr, Api, Binary, BlockInfo, Empty, Querier, Storage};
+use cw_multi_test::error::{bail, AnyResult};
+use cw_multi_test::{App, AppBuilder, AppResponse, CosmosRouter, Module, SudoMsg};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+
+const VALUE: Item<u64> = Item::new("value");
+
+#[derive(Debug, Serialize, Deserialize)]
+enum AdminSudo {
+    SetValue { value: u64 },
+}
+
+struct AdminModule;
+
+impl Module for AdminModule {
+    type ExecT = Empty;
+    type QueryT = Empty;
+    type SudoT = AdminSudo;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        _msg: Self::ExecT,
+    ) -> AnyResult<AppResponse> {
+        bail!("no messages")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        _request: Self::QueryT,
+    ) -> AnyResult<Binary> {
+        bail!("no queries")
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        msg: Self::SudoT,
+    ) -> AnyResult<AppResponse> {
+        match msg {
+            AdminSudo::SetValue { value } => VALUE.save(storage, &value)?,
+        }
+        Ok(AppResponse::default())
+    }
+}
+
+#[test]
+fn custom_sudo_should_be_routed_to_custom_module() {
+    let mut app = AppBuilder::default()
+        .with_custom(AdminModule)
+        .build(|_, _, _| {});
+
+    let msg = to_json_binary(&AdminSudo::SetValue { value: 42 }).unwrap();
+    app.sudo(SudoMsg::Custom(msg)).unwrap();
+    assert_eq!(42, VALUE.load(app.storage()).unwrap());
+
+    // malformed message is rejected
+    app.sudo(SudoMsg::Custom(Binary::from(b"{}"))).unwrap_err();
+}
+
+#[test]
+fn custom_sudo_should_fail_in_default_app() {
+    let mut app = App::default();
+    let msg = to_json_binary(&AdminSudo::SetValue { value: 42 }).unwrap();
+    assert!(app
+        .sudo(SudoMsg::Custom(msg))
+        .unwrap_err()
+        .to_string()
+        .starts_with("Unexpected sudo msg"));
+}
@@ -1,2 +1,7 @@
+mod test_gas_limit;
+mod test_grpc_queries;
 mod test_with_addr_gen;
 mod test_with_checksum_gen;
+mod test_with_gas_costs;
+mod test_with_storage_limits;
+mod test_with_wasmd_storage_layout;
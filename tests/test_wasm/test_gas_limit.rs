@@ -0,0 +1,115 @@
+use crate::test_contracts::counter::{self, CounterQueryMsg, CounterResponseMsg};
+use cosmwasm_std::{coins, to_json_binary, Addr, BankMsg, CosmosMsg, Empty, WasmMsg};
+use cw_multi_test::error::Error;
+use cw_multi_test::{next_block, no_init, AppBuilder, Executor, GasCosts, WasmKeeper};
+
+fn gas_costs() -> GasCosts {
+    GasCosts {
+        instantiate: 100,
+        execute: 10,
+        storage_read: 1,
+        storage_write: 2,
+        ..Default::default()
+    }
+}
+
+fn increment(contract_addr: &Addr) -> CosmosMsg {
+    WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&WasmMsg::ClearAdmin {
+            contract_addr: contract_addr.to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    }
+    .into()
+}
+
+#[test]
+fn execute_with_gas_limit_should_work() {
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(gas_costs());
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    // execution costs 10 + 1 + 2 gas
+    let res = app
+        .execute_with_gas_limit(owner.clone(), increment(&contract_addr), 13)
+        .unwrap();
+    assert_eq!(13, res.tx.unwrap().gas_used);
+
+    // storage write exceeds the limit, state changes are reverted
+    let err = app
+        .execute_with_gas_limit(owner.clone(), increment(&contract_addr), 12)
+        .unwrap_err();
+    assert_eq!(&Error::out_of_gas(13, 12), err.downcast_ref().unwrap());
+
+    // entry-point call exceeds the limit
+    let err = app
+        .execute_with_gas_limit(owner, increment(&contract_addr), 9)
+        .unwrap_err();
+    assert_eq!(
+        &Error::out_of_gas(10, 9),
+        err.root_cause().downcast_ref().unwrap()
+    );
+
+    let res: CounterResponseMsg = app
+        .wrap()
+        .query_wasm_smart(contract_addr, &CounterQueryMsg::Counter {})
+        .unwrap();
+    assert_eq!(2, res.value);
+}
+
+#[test]
+fn block_gas_limit_should_work() {
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(gas_costs());
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+    app.update_block(next_block);
+    app.set_block_gas_limit(Some(30));
+
+    // two executions fit in the block, the third one does not
+    app.execute(owner.clone(), increment(&contract_addr))
+        .unwrap();
+    app.execute(owner.clone(), increment(&contract_addr))
+        .unwrap();
+    assert_eq!(26, app.block_gas_used());
+    // the failed transaction consumes the remaining gas of the block
+    app.execute(owner.clone(), increment(&contract_addr))
+        .unwrap_err();
+    assert_eq!(30, app.block_gas_used());
+
+    // block gas is reset in the next block
+    app.update_block(next_block);
+    assert_eq!(0, app.block_gas_used());
+    app.execute(owner, increment(&contract_addr)).unwrap();
+    assert_eq!(13, app.block_gas_used());
+}
+
+#[test]
+fn failed_transaction_should_consume_block_gas() {
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(gas_costs());
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+    app.update_block(next_block);
+
+    // the contract is executed, then sending tokens fails due to missing funds
+    let send = BankMsg::Send {
+        to_address: contract_addr.to_string(),
+        amount: coins(10, "uatom"),
+    };
+    app.execute_multi(owner, vec![increment(&contract_addr), send.into()])
+        .unwrap_err();
+    assert_eq!(13, app.block_gas_used());
+}
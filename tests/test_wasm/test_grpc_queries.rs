@@ -0,0 +1,202 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{to_json_vec, Addr, Empty, GrpcQuery, Querier, QueryRequest};
+use cw_multi_test::{next_block, App, Executor};
+use prost::Message;
+
+#[derive(Clone, PartialEq, Message)]
+struct PageRequest {
+    #[prost(bytes = "vec", tag = "1")]
+    key: Vec<u8>,
+    #[prost(uint64, tag = "3")]
+    limit: u64,
+    #[prost(bool, tag = "5")]
+    reverse: bool,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct PageResponse {
+    #[prost(bytes = "vec", tag = "1")]
+    next_key: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractsByCodeRequest {
+    #[prost(uint64, tag = "1")]
+    code_id: u64,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryContractsByCodeResponse {
+    #[prost(string, repeated, tag = "1")]
+    contracts: Vec<String>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryCodesRequest {
+    #[prost(message, optional, tag = "1")]
+    pagination: Option<PageRequest>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct CodeInfoResponse {
+    #[prost(uint64, tag = "1")]
+    code_id: u64,
+    #[prost(string, tag = "2")]
+    creator: String,
+    #[prost(bytes = "vec", tag = "3")]
+    data_hash: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+struct QueryCodesResponse {
+    #[prost(message, repeated, tag = "1")]
+    code_infos: Vec<CodeInfoResponse>,
+    #[prost(message, optional, tag = "2")]
+    pagination: Option<PageResponse>,
+}
+
+fn query_grpc<R: Message + Default>(app: &App, path: &str, request: impl Message) -> R {
+    let query: QueryRequest<Empty> = QueryRequest::Grpc(GrpcQuery {
+        path: path.to_string(),
+        data: request.encode_to_vec().into(),
+    });
+    let data = app
+        .raw_query(&to_json_vec(&query).unwrap())
+        .unwrap()
+        .unwrap();
+    R::decode(data.as_slice()).unwrap()
+}
+
+fn instantiate(app: &mut App, code_id: u64, owner: &Addr) -> Addr {
+    app.instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap()
+}
+
+#[test]
+fn contracts_by_code_should_work() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id_1 = app.store_code(counter::contract());
+    let code_id_2 = app.store_code(counter::contract());
+    let mut contracts = vec![
+        instantiate(&mut app, code_id_1, &owner),
+        instantiate(&mut app, code_id_1, &owner),
+    ];
+    app.update_block(next_block);
+    contracts.push(instantiate(&mut app, code_id_1, &owner));
+    let other = instantiate(&mut app, code_id_2, &owner);
+
+    // contracts are ordered by instantiation, not by address
+    assert_eq!(contracts, app.contracts_by_code(code_id_1).unwrap());
+    assert_eq!(vec![other], app.contracts_by_code(code_id_2).unwrap());
+    assert!(app.contracts_by_code(100).unwrap().is_empty());
+
+    // first page of two contracts
+    let request = QueryContractsByCodeRequest {
+        code_id: code_id_1,
+        pagination: Some(PageRequest {
+            key: vec![],
+            limit: 2,
+            reverse: false,
+        }),
+    };
+    let response: QueryContractsByCodeResponse =
+        query_grpc(&app, "/cosmwasm.wasm.v1.Query/ContractsByCode", request);
+    assert_eq!(
+        vec![contracts[0].to_string(), contracts[1].to_string()],
+        response.contracts
+    );
+    let next_key = response.pagination.unwrap().next_key;
+    assert!(next_key.ends_with(contracts[2].as_bytes()));
+
+    // last page
+    let request = QueryContractsByCodeRequest {
+        code_id: code_id_1,
+        pagination: Some(PageRequest {
+            key: next_key,
+            limit: 2,
+            reverse: false,
+        }),
+    };
+    let response: QueryContractsByCodeResponse =
+        query_grpc(&app, "/cosmwasm.wasm.v1.Query/ContractsByCode", request);
+    assert_eq!(vec![contracts[2].to_string()], response.contracts);
+    assert!(response.pagination.unwrap().next_key.is_empty());
+}
+
+#[test]
+fn migrated_contracts_should_be_ordered_by_migration() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id_1 = app.store_code(counter::contract_with_migrate());
+    let code_id_2 = app.store_code(counter::contract_with_migrate());
+    let migrated = app
+        .instantiate_contract(
+            code_id_1,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "counter",
+            Some(owner.to_string()),
+        )
+        .unwrap();
+    app.update_block(next_block);
+    let other = instantiate(&mut app, code_id_2, &owner);
+
+    // the contract migrated later follows the contract instantiated earlier
+    app.update_block(next_block);
+    app.migrate_contract(owner, migrated.clone(), &Empty {}, code_id_2)
+        .unwrap();
+    assert!(app.contracts_by_code(code_id_1).unwrap().is_empty());
+    assert_eq!(
+        vec![other, migrated],
+        app.contracts_by_code(code_id_2).unwrap()
+    );
+}
+
+#[test]
+fn codes_should_work() {
+    let mut app = App::default();
+    let creator = app.api().addr_make("creator");
+    let code_id_1 = app.store_code_with_creator(creator.clone(), counter::contract());
+    let code_id_2 = app.store_code_with_creator(creator.clone(), counter::contract());
+    let code_id_3 = app
+        .store_code_with_id(creator.clone(), 10, counter::contract())
+        .unwrap();
+    assert_eq!(vec![code_id_1, code_id_2, code_id_3], app.code_ids());
+
+    // all codes
+    let response: QueryCodesResponse = query_grpc(
+        &app,
+        "/cosmwasm.wasm.v1.Query/Codes",
+        QueryCodesRequest { pagination: None },
+    );
+    let code_ids: Vec<u64> = response.code_infos.iter().map(|c| c.code_id).collect();
+    assert_eq!(vec![code_id_1, code_id_2, code_id_3], code_ids);
+    let code_info = app.wrap().query_wasm_code_info(code_id_3).unwrap();
+    assert_eq!(creator.to_string(), response.code_infos[2].creator);
+    assert_eq!(
+        code_info.checksum.as_slice(),
+        response.code_infos[2].data_hash.as_slice()
+    );
+
+    // codes in reverse order, in pages of two
+    let request = QueryCodesRequest {
+        pagination: Some(PageRequest {
+            key: vec![],
+            limit: 2,
+            reverse: true,
+        }),
+    };
+    let response: QueryCodesResponse = query_grpc(&app, "/cosmwasm.wasm.v1.Query/Codes", request);
+    let code_ids: Vec<u64> = response.code_infos.iter().map(|c| c.code_id).collect();
+    assert_eq!(vec![code_id_3, code_id_2], code_ids);
+    assert_eq!(
+        code_id_1.to_be_bytes().to_vec(),
+        response.pagination.unwrap().next_key
+    );
+}
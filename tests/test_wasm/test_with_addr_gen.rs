@@ -1,7 +1,9 @@
 use cosmwasm_std::testing::MockApi;
 use cosmwasm_std::{Addr, Api, Empty, Storage};
 use cw_multi_test::error::AnyResult;
-use cw_multi_test::{no_init, AddressGenerator, AppBuilder, Executor, WasmKeeper};
+use cw_multi_test::{
+    no_init, AddressGenerator, AppBuilder, Executor, SaltedAddressGenerator, WasmKeeper,
+};
 
 use crate::test_contracts;
 
@@ -176,3 +178,33 @@ fn creating_contract_with_the_same_predictable_address_should_fail() {
     )
     .unwrap_err();
 }
+
+#[test]
+fn salted_address_generator_should_work() {
+    fn contract_addresses(salt: &str) -> Vec<Addr> {
+        let mut app = AppBuilder::default()
+            .with_wasm(
+                WasmKeeper::default().with_address_generator(SaltedAddressGenerator::new(salt)),
+            )
+            .build(no_init);
+        let owner = app.api().addr_make("owner");
+        let code_id = app.store_code(test_contracts::counter::contract());
+        (0..2)
+            .map(|_| {
+                app.instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "Counter", None)
+                    .unwrap()
+            })
+            .collect()
+    }
+
+    let first = contract_addresses("first");
+    let second = contract_addresses("second");
+
+    // addresses are deterministic for the same salt
+    assert_eq!(first, contract_addresses("first"));
+    // instances get different addresses
+    assert_ne!(first[0], first[1]);
+    // different salts give different addresses
+    assert_ne!(first[0], second[0]);
+    assert_ne!(first[1], second[1]);
+}
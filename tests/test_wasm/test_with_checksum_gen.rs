@@ -55,3 +55,19 @@ fn custom_checksum_generator_should_work() {
         "c0ffee01c0ffee02c0ffee03c0ffee04c0ffee05c0ffee06c0ffee07c0ffee08"
     );
 }
+
+#[test]
+fn contract_checksum_should_take_precedence() {
+    // prepare application with custom checksum generator
+    let mut app = AppBuilder::default()
+        .with_wasm(WasmKeeper::default().with_checksum_generator(MyChecksumGenerator))
+        .build(no_init);
+
+    // store contract's code with the checksum of its wasm code
+    let checksum = Checksum::generate(b"wasm byte code");
+    let code_id = app.store_code(test_contracts::counter::contract_with_checksum(checksum));
+
+    // checksum of the contract's wasm code is reported, not the generated one
+    let code_info_response = app.wrap().query_wasm_code_info(code_id).unwrap();
+    assert_eq!(checksum, code_info_response.checksum);
+}
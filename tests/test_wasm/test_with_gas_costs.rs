@@ -0,0 +1,175 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Reply, Response, StdResult,
+    SubMsg, WasmMsg,
+};
+use cw_multi_test::{no_init, App, AppBuilder, ContractWrapper, Executor, GasCosts, WasmKeeper};
+use cw_storage_plus::Item;
+
+/// Gas used by submessages, as reported in replies.
+const GAS_USED: Item<Vec<u64>> = Item::new("gas_used");
+
+fn caller_instantiate(deps: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+    GAS_USED.save(deps.storage, &vec![])?;
+    Ok(Response::default())
+}
+
+fn caller_execute(_: DepsMut, _: Env, _: MessageInfo, msg: WasmMsg) -> StdResult<Response> {
+    Ok(Response::new().add_submessage(SubMsg::reply_always(msg, 1)))
+}
+
+fn caller_query(deps: Deps, _: Env, _: Empty) -> StdResult<Binary> {
+    to_json_binary(&GAS_USED.load(deps.storage)?)
+}
+
+fn caller_reply(deps: DepsMut, _: Env, msg: Reply) -> StdResult<Response> {
+    GAS_USED.update(deps.storage, |mut gas_used| -> StdResult<_> {
+        gas_used.push(msg.gas_used);
+        Ok(gas_used)
+    })?;
+    Ok(Response::default())
+}
+
+#[test]
+fn gas_should_not_be_metered_by_default() {
+    let mut app = App::default();
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+    let msg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: vec![],
+    };
+    let res = app
+        .execute_contract(owner, contract_addr, &msg, &[])
+        .unwrap();
+    assert_eq!(0, res.tx.unwrap().gas_used);
+}
+
+#[test]
+fn default_gas_costs_should_work() {
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(GasCosts::default());
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+
+    // instantiation writes the counter once
+    let res = app
+        .execute(
+            owner.clone(),
+            WasmMsg::Instantiate {
+                admin: None,
+                code_id,
+                msg: to_json_binary(&Empty {}).unwrap(),
+                funds: vec![],
+                label: "counter".to_string(),
+            }
+            .into(),
+        )
+        .unwrap();
+    assert_eq!(60_000 + 2000, res.tx.unwrap().gas_used);
+    let contract_addr = app.address_book().unwrap().get("counter").unwrap().clone();
+
+    // execution reads and writes the counter
+    let msg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&Empty {}).unwrap(),
+        funds: vec![],
+    };
+    let res = app
+        .execute_contract(owner, contract_addr, &msg, &[])
+        .unwrap();
+    assert_eq!(60_000 + 1000 + 2000, res.tx.unwrap().gas_used);
+}
+
+#[test]
+fn custom_gas_costs_should_work() {
+    let gas_costs = GasCosts {
+        instantiate: 100,
+        execute: 10,
+        storage_read: 1,
+        storage_write: 2,
+        ..Default::default()
+    };
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(gas_costs);
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    // two executions in one transaction are summed up
+    let msg = WasmMsg::Execute {
+        contract_addr: contract_addr.to_string(),
+        msg: to_json_binary(&WasmMsg::ClearAdmin {
+            contract_addr: contract_addr.to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    let responses = app
+        .execute_multi(owner, vec![msg.clone().into(), msg.into()])
+        .unwrap();
+    assert_eq!(2 * (10 + 1 + 2), responses[0].tx.as_ref().unwrap().gas_used);
+}
+
+#[test]
+fn gas_used_should_be_passed_to_reply() {
+    let gas_costs = GasCosts {
+        instantiate: 100,
+        execute: 10,
+        storage_read: 1,
+        storage_write: 2,
+        ..Default::default()
+    };
+    let wasm_keeper = WasmKeeper::new().with_gas_costs(gas_costs);
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let counter_code_id = app.store_code(counter::contract());
+    let caller_code_id = app.store_code(Box::new(
+        ContractWrapper::new(caller_execute, caller_instantiate, caller_query)
+            .with_reply(caller_reply),
+    ));
+    let counter_addr = app
+        .instantiate_contract(
+            counter_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "counter",
+            None,
+        )
+        .unwrap();
+    let caller_addr = app
+        .instantiate_contract(
+            caller_code_id,
+            owner.clone(),
+            &Empty {},
+            &[],
+            "caller",
+            None,
+        )
+        .unwrap();
+
+    // the submessage executes the counter, which reads and writes its state
+    let msg = WasmMsg::Execute {
+        contract_addr: counter_addr.to_string(),
+        msg: to_json_binary(&WasmMsg::ClearAdmin {
+            contract_addr: counter_addr.to_string(),
+        })
+        .unwrap(),
+        funds: vec![],
+    };
+    for _ in 0..2 {
+        app.execute_contract(owner.clone(), caller_addr.clone(), &msg, &[])
+            .unwrap();
+    }
+
+    // gas used by the same submessage is reported in every reply
+    let gas_used: Vec<u64> = app.wrap().query_wasm_smart(caller_addr, &Empty {}).unwrap();
+    assert_eq!(vec![10 + 1 + 2, 10 + 1 + 2], gas_used);
+}
@@ -0,0 +1,114 @@
+use cosmwasm_std::{
+    to_json_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdError,
+};
+use cw_multi_test::error::Error;
+use cw_multi_test::{
+    no_init, AppBuilder, Contract, ContractWrapper, Executor, StorageLimits, WasmKeeper,
+};
+use cw_storage_plus::Map;
+use serde::{Deserialize, Serialize};
+
+const ENTRIES: Map<&str, String> = Map::new("entries");
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SaveMsg {
+    key: String,
+    value: String,
+}
+
+fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> Result<Response, StdError> {
+    Ok(Response::default())
+}
+
+fn execute(deps: DepsMut, _: Env, _: MessageInfo, msg: SaveMsg) -> Result<Response, StdError> {
+    ENTRIES.save(deps.storage, &msg.key, &msg.value)?;
+    Ok(Response::default())
+}
+
+fn query(deps: Deps, _: Env, _: Empty) -> Result<Binary, StdError> {
+    let count = ENTRIES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count();
+    to_json_binary(&count)
+}
+
+fn contract() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+}
+
+fn save(key: &str, value_length: usize) -> SaveMsg {
+    SaveMsg {
+        key: key.to_string(),
+        value: "v".repeat(value_length),
+    }
+}
+
+#[test]
+fn storage_limits_should_work() {
+    let wasm_keeper = WasmKeeper::new().with_storage_limits(StorageLimits {
+        max_key_length: 32,
+        max_value_length: 64,
+        max_iterated_items: Some(2),
+    });
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner.clone(), &Empty {}, &[], "entries", None)
+        .unwrap();
+
+    // writes within the limits succeed, the stored value is JSON encoded with quotes
+    app.execute_contract(owner.clone(), contract_addr.clone(), &save("a", 62), &[])
+        .unwrap();
+    app.execute_contract(owner.clone(), contract_addr.clone(), &save("b", 1), &[])
+        .unwrap();
+
+    // too long value is rejected
+    let err = app
+        .execute_contract(owner.clone(), contract_addr.clone(), &save("c", 63), &[])
+        .unwrap_err();
+    assert_eq!(
+        &Error::region_length_too_big(65, 64),
+        err.root_cause().downcast_ref().unwrap()
+    );
+
+    // too long key is rejected, the key is prefixed with the namespace
+    let err = app
+        .execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &save(&"k".repeat(24), 1),
+            &[],
+        )
+        .unwrap_err();
+    assert_eq!(
+        &Error::region_length_too_big(33, 32),
+        err.root_cause().downcast_ref().unwrap()
+    );
+
+    // iterating over two items is allowed
+    let count: usize = app
+        .wrap()
+        .query_wasm_smart(&contract_addr, &Empty {})
+        .unwrap();
+    assert_eq!(2, count);
+
+    // iterating over three items is not
+    app.execute_contract(owner, contract_addr.clone(), &save("c", 1), &[])
+        .unwrap();
+    let err = app
+        .wrap()
+        .query_wasm_smart::<usize>(&contract_addr, &Empty {})
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains(&Error::too_many_iterated_items(2).to_string()));
+}
+
+#[test]
+fn default_storage_limits_should_follow_wasmd() {
+    let limits = StorageLimits::default();
+    assert_eq!(64 * 1024, limits.max_key_length);
+    assert_eq!(128 * 1024, limits.max_value_length);
+    assert_eq!(None, limits.max_iterated_items);
+}
@@ -0,0 +1,39 @@
+use crate::test_contracts::counter;
+use cosmwasm_std::{Api, Empty, Order, Storage};
+use cw_multi_test::{no_init, AppBuilder, Executor, WasmKeeper};
+
+#[test]
+fn contract_storage_should_use_wasmd_layout() {
+    let wasm_keeper = WasmKeeper::new().with_wasmd_storage_layout();
+    let mut app = AppBuilder::default().with_wasm(wasm_keeper).build(no_init);
+    let owner = app.api().addr_make("owner");
+    let code_id = app.store_code(counter::contract());
+    let contract_addr = app
+        .instantiate_contract(code_id, owner, &Empty {}, &[], "counter", None)
+        .unwrap();
+
+    // the state of the contract is stored under wasmd key
+    let mut key = vec![0x03];
+    key.extend(
+        app.api()
+            .addr_canonicalize(contract_addr.as_str())
+            .unwrap()
+            .as_slice(),
+    );
+    key.extend(b"counter");
+    assert_eq!(Some(b"1".to_vec()), app.storage().get(&key));
+
+    // contract storage and raw queries use the same layout
+    let state = app.dump_wasm_raw(&contract_addr);
+    assert_eq!(vec![(b"counter".to_vec(), b"1".to_vec())], state);
+    assert_eq!(
+        Some(b"1".to_vec()),
+        app.contract_storage(&contract_addr).get(b"counter")
+    );
+    assert_eq!(
+        1,
+        app.contract_storage(&contract_addr)
+            .range(None, None, Order::Ascending)
+            .count()
+    );
+}